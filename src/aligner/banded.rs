@@ -0,0 +1,113 @@
+//! Band of diagonals for banded alignment.
+
+use super::Band;
+use crate::utils::AlignmentUnit;
+use std::collections::{HashMap, HashSet};
+
+/// Words occurring more times than this at the top sequence are ignored when looking for
+/// the seed diagonal. They come from low complexity regions and only add noise.
+const MAX_WORD_HITS: usize = 64;
+
+/// Returns the [lower, upper] diagonals (j - i) of the band.
+pub(super) fn diagonals<A: AlignmentUnit>(
+    band: Band,
+    sequence_left: &[A],
+    sequence_top: &[A],
+) -> [isize; 2] {
+    let (centre, width) = match band {
+        Band::Fixed(width) => (0, width),
+        Band::Adaptive(width) => (
+            seed_diagonal(sequence_left, sequence_top).unwrap_or(0),
+            width,
+        ),
+        Band::Seeded { diagonal, width } => (diagonal, width),
+    };
+    let width = width as isize;
+    [centre - width, centre + width]
+}
+
+/// Clips the band diagonals to the ones of a rows * cols matrix.
+pub(super) fn clip([lower, upper]: [isize; 2], [rows, cols]: [usize; 2]) -> [isize; 2] {
+    let lower = lower.max(1 - rows as isize);
+    [lower, upper.min(cols as isize - 1).max(lower - 1)]
+}
+
+/// Whether any cell of the path is on an edge of the band. The edges that are also the
+/// matrix borders do not restrict the alignment, so they do not count.
+pub(super) fn touches_edge(
+    path: &[[usize; 2]],
+    band: Option<[isize; 2]>,
+    [rows, cols]: [usize; 2],
+) -> bool {
+    let Some([lower, upper]) = band else {
+        return false;
+    };
+    path.iter().any(|[i, j]| {
+        let diagonal = *j as isize - *i as isize;
+        (diagonal == lower && lower > 1 - rows as isize)
+            || (diagonal == upper && upper < cols as isize - 1)
+    })
+}
+
+/// The diagonal j - i with more shared words (k-mers) between both sequences, like the
+/// FASTA program initial regions. The word length grows with the top sequence length and
+/// alphabet, so chance hits stay rare. Returns None if the sequences share no words.
+pub(super) fn seed_diagonal<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+) -> Option<isize> {
+    let alphabet = sequence_top.iter().collect::<HashSet<&A>>().len().max(2);
+    let length = (sequence_top.len() as f64).ln() / (alphabet as f64).ln();
+    let word_length = (length.ceil() as usize + 1).max(3);
+    if word_length > sequence_left.len().min(sequence_top.len()) {
+        return None;
+    }
+
+    let mut words: HashMap<&[A], Vec<usize>> = HashMap::new();
+    for (j, word) in sequence_top.windows(word_length).enumerate() {
+        words.entry(word).or_default().push(j);
+    }
+    let mut votes: HashMap<isize, usize> = HashMap::new();
+    for (i, word) in sequence_left.windows(word_length).enumerate() {
+        match words.get(word) {
+            Some(hits) if hits.len() <= MAX_WORD_HITS => {
+                for j in hits {
+                    *votes.entry(*j as isize - i as isize).or_default() += 1;
+                }
+            }
+            _ => continue,
+        }
+    }
+    // Among equally voted diagonals, the lowest one.
+    votes
+        .into_iter()
+        .max_by_key(|(diagonal, count)| (*count, -diagonal))
+        .map(|(diagonal, _)| diagonal)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bioseq::{HasSequence, Protein};
+
+    #[test]
+    fn finds_seed_diagonal() {
+        let left = Protein::new("MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ").unwrap();
+        let top = Protein::new("GSHMMKTAYIAKQRQISFVKSHFSRQ").unwrap();
+        assert_eq!(Some(4), seed_diagonal(left.seq(), top.seq()));
+        assert_eq!([-1, 9], diagonals(Band::Adaptive(5), left.seq(), top.seq()));
+
+        let unrelated = Protein::new("WWWWCCCCWWWWCCCC").unwrap();
+        assert_eq!(None, seed_diagonal(left.seq(), unrelated.seq()));
+    }
+
+    #[test]
+    fn band_edges() {
+        let path = [[3, 4], [2, 3], [2, 2], [1, 1], [0, 0]];
+        assert!(touches_edge(&path, Some([-1, 1]), [4, 5]));
+        assert!(!touches_edge(&path, Some([-1, 2]), [4, 5]));
+        // the lower edge is the matrix border
+        assert!(!touches_edge(&[[3, 0], [2, 0]], Some([-3, 2]), [4, 5]));
+        assert_eq!([-3, 4], clip([-10, 10], [4, 5]));
+    }
+}
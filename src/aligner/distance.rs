@@ -0,0 +1,572 @@
+//! All-versus-all pairwise alignment of a set of sequences.
+//!
+//! Aligns every pair of sequences once and keeps, for each pair, the optimal score, the
+//! identity (identical pairs over aligned pairs) and a distance, in symmetric matrices.
+//! The diagonal holds the score of each sequence against itself, identity 1 and distance 0.
+//! The distances can be written in the PHYLIP format for phylogeny and clustering tools.
+
+use super::global_alignment::NeedlemanWunsch;
+use super::local_alignment::SmithWaterman;
+use super::{Aligner, TieBreak};
+use crate::bioseq::{Aac, HasSequence};
+use crate::matrix::Matrix;
+use crate::scoring_schema::aminoacid_schema::AaScoringKind;
+use crate::scoring_schema::gap_penalty::PenaltyKind;
+use crate::scoring_schema::{AaScoringSchema, ScoringSchema};
+use crate::utils::AlignmentUnit;
+use crate::InputError;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::{error, fmt};
+
+/// Largest Kimura distance, given to the pairs too divergent for the correction.
+pub const MAX_KIMURA_DISTANCE: f64 = 10.0;
+
+/// How the distance of a pair is computed from its alignment.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum DistanceKind {
+    /// The fraction of aligned pairs that differ, p = 1 - identity.
+    PDistance,
+    /// Kimura (1983) correction for multiple substitutions of proteins,
+    /// -ln(1 - p - 0.2 p²). It is MAX_KIMURA_DISTANCE for p ≥ 0.854, where the
+    /// logarithm is not defined.
+    #[default]
+    Kimura,
+    /// 1 - S(a, b) / S_max, where S_max is the mean of the scores of both sequences against
+    /// themselves, clamped to [0, 1]. The only distance available when aligning score-only.
+    ScoreNormalised,
+}
+
+/// How AllVersusAll aligns the pairs.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DistanceSettings {
+    /// Aligns the pairs with Needleman-Wunsch if true, or with Smith-Waterman.
+    pub global: bool,
+    /// Only computes the optimal scores, keeping memory proportional to the shorter
+    /// sequence instead of the traceback matrices. Then there are no identities and the
+    /// distance must be DistanceKind::ScoreNormalised.
+    pub score_only: bool,
+    pub distance: DistanceKind,
+}
+
+impl Default for DistanceSettings {
+    fn default() -> Self {
+        Self {
+            global: true,
+            score_only: false,
+            distance: DistanceKind::default(),
+        }
+    }
+}
+
+/// Aligner of every pair of a set of sequences, see the module documentation.
+pub struct AllVersusAll<A>
+where
+    A: AlignmentUnit,
+{
+    scoring_schema: Rc<dyn ScoringSchema<A>>,
+    settings: DistanceSettings,
+}
+
+impl AllVersusAll<Aac> {
+    /// All-versus-all aligner of proteins. Returns InputError if the gap penalty parameters
+    /// are not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::aligner::distance::{AllVersusAll, DistanceSettings};
+    /// use pairwasm_alignment::bioseq::Protein;
+    /// use pairwasm_alignment::scoring_schema::aminoacid_schema::AaScoringKind;
+    /// use pairwasm_alignment::scoring_schema::gap_penalty::PenaltyKind;
+    ///
+    /// let aligner = AllVersusAll::new(
+    ///     AaScoringKind::Blosum62,
+    ///     PenaltyKind::Affine(11.0, 1.0),
+    ///     DistanceSettings::default(),
+    /// )
+    /// .unwrap();
+    /// let records = [
+    ///     ("seq1", Protein::new("HGEYWAKLV").unwrap()),
+    ///     ("seq2", Protein::new("HGEFWKLV").unwrap()),
+    ///     ("seq3", Protein::new("HAEYWAKIV").unwrap()),
+    /// ];
+    /// let distances = aligner.distances(&records).unwrap();
+    /// assert_eq!(Some(7.0 / 8.0), distances.identity(0, 1));
+    /// assert!(distances.distance(0, 1) < distances.distance(1, 2));
+    /// assert!(distances.to_phylip().starts_with("3\nseq1       0.000000 "));
+    /// ```
+    pub fn new(
+        score_kind: AaScoringKind,
+        penalty_kind: PenaltyKind,
+        settings: DistanceSettings,
+    ) -> Result<Self, InputError> {
+        let scoring_schema = AaScoringSchema::new(score_kind, penalty_kind)?;
+        Ok(Self::with_scoring_schema(
+            Box::new(scoring_schema),
+            settings,
+        ))
+    }
+}
+
+impl<A> AllVersusAll<A>
+where
+    A: AlignmentUnit + 'static,
+{
+    pub fn with_scoring_schema(
+        scoring_schema: Box<dyn ScoringSchema<A>>,
+        settings: DistanceSettings,
+    ) -> Self {
+        Self::with_shared_schema(Rc::from(scoring_schema), settings)
+    }
+
+    pub(super) fn with_shared_schema(
+        scoring_schema: Rc<dyn ScoringSchema<A>>,
+        settings: DistanceSettings,
+    ) -> Self {
+        Self {
+            scoring_schema,
+            settings,
+        }
+    }
+
+    /// Aligns every pair of sequences, each with its identifier. The rows and columns of
+    /// the result keep the order of the records.
+    ///
+    /// Returns DistanceError if there are less than two records, a sequence is empty, or
+    /// the settings ask for identities without alignments.
+    pub fn distances(
+        &self,
+        records: &[(impl AsRef<str>, impl HasSequence<A>)],
+    ) -> Result<DistanceMatrix, DistanceError> {
+        if records.len() < 2 {
+            return Err(DistanceError::new(
+                DistanceErrorKind::NotEnoughSequences,
+                "",
+            ));
+        }
+        if let Some((id, _)) = records
+            .iter()
+            .find(|(_, sequence)| sequence.seq().is_empty())
+        {
+            return Err(DistanceError::new(
+                DistanceErrorKind::EmptySequence,
+                id.as_ref(),
+            ));
+        }
+        if self.settings.score_only
+            && self.settings.distance != DistanceKind::ScoreNormalised
+        {
+            return Err(DistanceError::new(DistanceErrorKind::MissingIdentities, ""));
+        }
+
+        let size = records.len();
+        let mut scores = Matrix::full(0.0, size, size);
+        let mut identities = match self.settings.score_only {
+            true => None,
+            false => Some(Matrix::full(1.0, size, size)),
+        };
+        for i in 0..size {
+            // Only the score of a sequence against itself is needed.
+            let sequence = records[i].1.seq();
+            scores[[i, i]] = self.aligner(sequence, sequence).score().score();
+            for j in i + 1..size {
+                let (score, identity) = self.align(sequence, records[j].1.seq());
+                scores[[i, j]] = score;
+                scores[[j, i]] = score;
+                if let (Some(identities), Some(identity)) =
+                    (identities.as_mut(), identity)
+                {
+                    identities[[i, j]] = identity;
+                    identities[[j, i]] = identity;
+                }
+            }
+        }
+
+        let mut distances = Matrix::full(0.0, size, size);
+        for i in 0..size {
+            for j in i + 1..size {
+                let distance = match (self.settings.distance, &identities) {
+                    (DistanceKind::PDistance, Some(identities)) => {
+                        1.0 - identities[[i, j]]
+                    }
+                    (DistanceKind::Kimura, Some(identities)) => {
+                        kimura(1.0 - identities[[i, j]])
+                    }
+                    _ => {
+                        let maximum = (scores[[i, i]] + scores[[j, j]]) as f64 / 2.0;
+                        match maximum > 0.0 {
+                            true => {
+                                (1.0 - scores[[i, j]] as f64 / maximum).clamp(0.0, 1.0)
+                            }
+                            false => 1.0,
+                        }
+                    }
+                };
+                distances[[i, j]] = distance;
+                distances[[j, i]] = distance;
+            }
+        }
+
+        Ok(DistanceMatrix {
+            ids: records
+                .iter()
+                .map(|(id, _)| id.as_ref().to_string())
+                .collect(),
+            scores,
+            identities,
+            distances,
+        })
+    }
+
+    // Optimal score of the pair and, unless score-only, its identity.
+    fn align(&self, sequence_left: &[A], sequence_top: &[A]) -> (f32, Option<f64>) {
+        let mut aligner = self.aligner(sequence_left, sequence_top);
+        if self.settings.score_only {
+            return (aligner.score().score(), None);
+        }
+        aligner.set_tie_break(TieBreak::PreferDiagonal);
+        let alignment = aligner.run().remove(0);
+        let pairs = alignment.length() - alignment.gaps();
+        let identity = match pairs {
+            0 => 0.0,
+            _ => alignment.identities() as f64 / pairs as f64,
+        };
+        (alignment.score(), Some(identity))
+    }
+
+    fn aligner(&self, sequence_left: &[A], sequence_top: &[A]) -> Box<dyn Aligner<A>> {
+        let sequence_left = Units(sequence_left.to_vec());
+        let sequence_top = Units(sequence_top.to_vec());
+        let scoring_schema = Box::new(self.scoring_schema.clone());
+        match self.settings.global {
+            true => Box::new(NeedlemanWunsch::with_scoring_schema(
+                sequence_left,
+                sequence_top,
+                scoring_schema,
+            )),
+            false => Box::new(SmithWaterman::with_scoring_schema(
+                sequence_left,
+                sequence_top,
+                scoring_schema,
+            )),
+        }
+    }
+}
+
+// Kimura distance of a fraction p of differing pairs.
+fn kimura(p: f64) -> f64 {
+    let argument = 1.0 - p - 0.2 * p * p;
+    match argument > 0.0 {
+        true => (-argument.ln()).min(MAX_KIMURA_DISTANCE),
+        false => MAX_KIMURA_DISTANCE,
+    }
+}
+
+// Owned copy of a sequence, for the aligners.
+pub(super) struct Units<A>(pub(super) Vec<A>);
+
+impl<A> HasSequence<A> for Units<A>
+where
+    A: AlignmentUnit,
+{
+    fn seq(&self) -> &Vec<A> {
+        &self.0
+    }
+}
+
+/// Scores, identities and distances of every pair of a set of sequences. Every matrix is
+/// symmetric, with a row and a column per sequence.
+pub struct DistanceMatrix {
+    ids: Vec<String>,
+    scores: Matrix<f32>,
+    identities: Option<Matrix<f64>>,
+    distances: Matrix<f64>,
+}
+
+impl DistanceMatrix {
+    /// Identifiers of the sequences, in the order of the rows.
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+
+    /// Number of sequences.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Optimal score of the pair. Panics if an index is out of range.
+    pub fn score(&self, i: usize, j: usize) -> f32 {
+        self.scores[[i, j]]
+    }
+
+    /// Identical pairs over aligned pairs, or None if aligned score-only. Panics if an
+    /// index is out of range.
+    pub fn identity(&self, i: usize, j: usize) -> Option<f64> {
+        self.identities
+            .as_ref()
+            .map(|identities| identities[[i, j]])
+    }
+
+    /// Distance of the pair, see DistanceKind. Panics if an index is out of range.
+    pub fn distance(&self, i: usize, j: usize) -> f64 {
+        self.distances[[i, j]]
+    }
+
+    pub fn scores(&self) -> &Matrix<f32> {
+        &self.scores
+    }
+
+    pub fn identities(&self) -> Option<&Matrix<f64>> {
+        self.identities.as_ref()
+    }
+
+    pub fn distances(&self) -> &Matrix<f64> {
+        &self.distances
+    }
+
+    /// The distances in the PHYLIP square format: the number of sequences, and a line per
+    /// sequence with its identifier and its distances to all the sequences. As PHYLIP
+    /// reads the first 10 characters as the name, identifiers are cut or padded to 10
+    /// characters. When a cut identifier repeats a previous name, its last characters are
+    /// replaced with a number that makes it unique.
+    pub fn to_phylip(&self) -> String {
+        let mut text = format!("{}\n", self.len());
+        for (i, name) in self.phylip_names().iter().enumerate() {
+            write!(text, "{:10}", name).unwrap();
+            for j in 0..self.len() {
+                write!(text, " {:.6}", self.distance(i, j)).unwrap();
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    // Identifiers cut to 10 characters, unique.
+    fn phylip_names(&self) -> Vec<String> {
+        let mut used: HashSet<String> = HashSet::new();
+        self.ids
+            .iter()
+            .map(|id| {
+                let mut name: String = id.chars().take(10).collect();
+                let mut number = 1;
+                while used.contains(&name) {
+                    let suffix = number.to_string();
+                    name = id.chars().take(10 - suffix.len()).collect();
+                    name.push_str(&suffix);
+                    number += 1;
+                }
+                used.insert(name.clone());
+                name
+            })
+            .collect()
+    }
+
+    /// Tab-separated values with a header and a line per pair: id_1, id_2, score, identity
+    /// (empty if aligned score-only) and distance.
+    pub fn to_tsv(&self) -> String {
+        let mut text = "id_1\tid_2\tscore\tidentity\tdistance\n".to_string();
+        for i in 0..self.len() {
+            for j in i + 1..self.len() {
+                let identity = self
+                    .identity(i, j)
+                    .map_or(String::new(), |identity| format!("{:.6}", identity));
+                writeln!(
+                    text,
+                    "{}\t{}\t{}\t{}\t{:.6}",
+                    self.ids[i],
+                    self.ids[j],
+                    self.score(i, j),
+                    identity,
+                    self.distance(i, j)
+                )
+                .unwrap();
+            }
+        }
+        text
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of DistanceError.
+pub enum DistanceErrorKind {
+    EmptySequence,
+    MissingIdentities,
+    NotEnoughSequences,
+}
+
+#[derive(Debug)]
+/// Error type for all-versus-all alignment.
+pub struct DistanceError {
+    kind: DistanceErrorKind,
+    message: String,
+}
+
+impl DistanceError {
+    fn new(kind: DistanceErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            DistanceErrorKind::EmptySequence => {
+                "The sequences must not be empty.".to_string()
+            }
+            DistanceErrorKind::MissingIdentities => {
+                "Score-only alignments only give score-normalised distances.".to_string()
+            }
+            DistanceErrorKind::NotEnoughSequences => {
+                "The input must contain at least two sequences.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &DistanceErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for DistanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for DistanceError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bioseq::Protein;
+
+    const SEQUENCES: [&str; 3] = ["HGEYWAKLV", "HGEFWKLV", "MKTAYIAKQR"];
+
+    fn distances(settings: DistanceSettings) -> Result<DistanceMatrix, DistanceError> {
+        let records: Vec<(String, Protein)> = SEQUENCES
+            .iter()
+            .enumerate()
+            .map(|(index, sequence)| {
+                (format!("seq{}", index + 1), Protein::new(sequence).unwrap())
+            })
+            .collect();
+        AllVersusAll::new(
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            settings,
+        )
+        .unwrap()
+        .distances(&records)
+    }
+
+    #[test]
+    fn symmetric_matrices() {
+        let matrix = distances(DistanceSettings {
+            distance: DistanceKind::PDistance,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(3, matrix.len());
+        for i in 0..3 {
+            assert_eq!(0.0, matrix.distance(i, i));
+            assert_eq!(Some(1.0), matrix.identity(i, i));
+            for j in 0..3 {
+                assert_eq!(matrix.score(i, j), matrix.score(j, i));
+                assert_eq!(matrix.distance(i, j), matrix.distance(j, i));
+            }
+        }
+        // HGEYWAKLV against itself
+        assert_eq!(54.0, matrix.score(0, 0));
+        assert_eq!(Some(7.0 / 8.0), matrix.identity(0, 1));
+        assert_eq!(1.0 / 8.0, matrix.distance(0, 1));
+        assert!(matrix.distance(0, 2) > matrix.distance(0, 1));
+    }
+
+    #[test]
+    fn corrected_and_score_only_distances() {
+        let kimura_distances = distances(DistanceSettings::default()).unwrap();
+        let p: f64 = 1.0 / 8.0;
+        assert_eq!(
+            -(1.0 - p - 0.2 * p * p).ln(),
+            kimura_distances.distance(0, 1)
+        );
+        assert_eq!(MAX_KIMURA_DISTANCE, kimura(0.9));
+
+        let settings = DistanceSettings {
+            score_only: true,
+            distance: DistanceKind::ScoreNormalised,
+            ..Default::default()
+        };
+        let score_only = distances(settings).unwrap();
+        assert_eq!(None, score_only.identity(0, 1));
+        assert_eq!(kimura_distances.score(0, 1), score_only.score(0, 1));
+        let maximum = (score_only.score(0, 0) + score_only.score(1, 1)) as f64 / 2.0;
+        assert_eq!(
+            1.0 - score_only.score(0, 1) as f64 / maximum,
+            score_only.distance(0, 1)
+        );
+
+        let local = distances(DistanceSettings {
+            global: false,
+            ..settings
+        })
+        .unwrap();
+        assert!(local.score(0, 2) >= score_only.score(0, 2));
+
+        let error = distances(DistanceSettings {
+            score_only: true,
+            ..Default::default()
+        });
+        assert!(error.is_err_and(|e| e.kind() == &DistanceErrorKind::MissingIdentities));
+    }
+
+    #[test]
+    fn phylip_and_tsv() {
+        let matrix = distances(DistanceSettings {
+            distance: DistanceKind::PDistance,
+            ..Default::default()
+        })
+        .unwrap();
+        let phylip = matrix.to_phylip();
+        let lines: Vec<&str> = phylip.lines().collect();
+        assert_eq!(4, lines.len());
+        assert_eq!("3", lines[0]);
+        assert!(lines[1].starts_with("seq1       0.000000 0.125000 "));
+        assert_eq!(10 + 3 * 9, lines[2].len());
+
+        // Identifiers that share their first 10 characters get unique names.
+        let records = [
+            ("sequence_A1", Protein::new("HGEYWAKLV").unwrap()),
+            ("sequence_A2", Protein::new("HGEFWKLV").unwrap()),
+            ("sequence_1", Protein::new("HAEYWAKIV").unwrap()),
+        ];
+        let prefixed = AllVersusAll::new(
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            DistanceSettings::default(),
+        )
+        .unwrap()
+        .distances(&records)
+        .unwrap();
+        let names: Vec<String> = prefixed
+            .to_phylip()
+            .lines()
+            .skip(1)
+            .map(|line| line[..10].to_string())
+            .collect();
+        assert_eq!(["sequence_A", "sequence_1", "sequence_2"].to_vec(), names);
+
+        let tsv = matrix.to_tsv();
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!("id_1\tid_2\tscore\tidentity\tdistance", lines[0]);
+        assert_eq!(4, lines.len());
+        assert!(lines[1].starts_with("seq1\tseq2\t"));
+        assert!(lines[1].ends_with("\t0.875000\t0.125000"));
+    }
+}
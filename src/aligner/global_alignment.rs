@@ -1,12 +1,20 @@
-use super::Aligner;
+use super::{
+    banded, linear_space, shuffle, Aligner, Band, EndGaps, Shuffle, TieBreak,
+    DEFAULT_MEMORY_BUDGET,
+};
 // The original Needleman-Wunsch uses a linear gap penalty
-use super::utils::{AffineTransversalOrder, AlignmentSequence, BackTrack};
+use super::utils::{
+    select_paths, AffineMatrices, AffineTransversalOrder, AlignmentResult,
+    AlignmentScore, BackTrack, ShuffleSignificance,
+};
 use crate::bioseq::{Aac, HasSequence};
-use crate::matrix::Matrix;
 use crate::scoring_schema::aminoacid_schema::AaScoringKind;
 use crate::scoring_schema::gap_penalty::PenaltyKind;
 use crate::scoring_schema::AaScoringSchema;
-use crate::{scoring_schema::ScoringSchema, utils::AlignmentUnit};
+use crate::statistics::KarlinAltschul;
+use crate::{
+    scoring_schema::ScoringSchema, utils::AlignmentUnit, InputError, InputErrorKind,
+};
 
 pub struct NeedlemanWunsch<A>
 where
@@ -15,7 +23,12 @@ pub struct NeedlemanWunsch<A>
     sequence_left: Box<dyn HasSequence<A>>,
     sequence_top: Box<dyn HasSequence<A>>,
     scoring_schema: Box<dyn ScoringSchema<A>>,
-    matrix: Matrix<BackTrack>,
+    matrices: AffineMatrices,
+    end_gaps: EndGaps,
+    memory_budget: usize,
+    band: Option<Band>,
+    co_optimal_limit: usize,
+    tie_break: TieBreak,
 }
 
 impl NeedlemanWunsch<Aac> {
@@ -25,89 +38,316 @@ pub fn new(
         sequence_top: impl HasSequence<Aac> + 'static,
         score_kind: AaScoringKind,
         penalty_kind: PenaltyKind,
-    ) -> Self {
+    ) -> Result<Self, InputError> {
         // Implementation only valid for linear and affine
         #[allow(unreachable_patterns)]
         match penalty_kind {
             PenaltyKind::Affine(_, _) => (),
             PenaltyKind::Linear(_) => (),
-            _ => panic!("Only allowed for Affine and Linear gap models."),
+            _ => return Err(InputError::new(InputErrorKind::GapModelNotExist, "")),
         }
-        let scoring_schema = Box::new(AaScoringSchema::new(score_kind, penalty_kind));
-        let rows = 1 + sequence_left.seq().len();
-        let cols = 1 + sequence_top.seq().len();
+        let scoring_schema = Box::new(AaScoringSchema::new(score_kind, penalty_kind)?);
+        Ok(Self::with_scoring_schema(
+            sequence_left,
+            sequence_top,
+            scoring_schema,
+        ))
+    }
+}
 
+impl<A> NeedlemanWunsch<A>
+where
+    A: AlignmentUnit,
+{
+    /// Creates the aligner from an already built scoring schema. Allows to align any kind of
+    /// sequence units.
+    pub fn with_scoring_schema(
+        sequence_left: impl HasSequence<A> + 'static,
+        sequence_top: impl HasSequence<A> + 'static,
+        scoring_schema: Box<dyn ScoringSchema<A>>,
+    ) -> Self {
+        // The matrices are allocated when running, only if they fit in the memory budget.
         Self {
             sequence_left: Box::new(sequence_left),
             sequence_top: Box::new(sequence_top),
-            scoring_schema: scoring_schema as Box<dyn ScoringSchema<Aac>>,
-            matrix: Matrix::full(BackTrack::Empty, rows, cols),
+            scoring_schema,
+            matrices: AffineMatrices::new(0, 0),
+            end_gaps: EndGaps::default(),
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            band: None,
+            co_optimal_limit: 1,
+            tie_break: TieBreak::default(),
         }
     }
+
+    /// Turns the aligner into a semi-global one: the terminal gaps set in end_gaps are not
+    /// penalised.
+    pub fn with_end_gaps(mut self, end_gaps: EndGaps) -> Self {
+        self.end_gaps = end_gaps;
+        self
+    }
 }
 
 impl<A> NeedlemanWunsch<A>
 where
     A: AlignmentUnit,
 {
-    fn run(&mut self) -> Vec<AlignmentSequence<A>> {
+    fn run(&mut self) -> Vec<AlignmentResult<A>> {
+        let rows = 1 + self.sequence_left.seq().len();
+        let cols = 1 + self.sequence_top.seq().len();
+        let diagonals = self.band_diagonals();
+        let stored_cols =
+            diagonals.map_or(cols, |[lower, upper]| (upper - lower + 1) as usize);
+
+        // A band that does not fit in memory falls back to the linear-space algorithm,
+        // which does not need a band to find the optimal alignment. It assumes the same
+        // gap costs everywhere, so position-specific schemas keep the matrices.
+        let linear_space = AffineMatrices::estimated_size(rows, stored_cols)
+            > self.memory_budget
+            && !self.scoring_schema.has_position_specific_gaps();
+        let (paths, score) = if linear_space {
+            let (path, score) = linear_space::global_alignment(
+                self.sequence_left.seq(),
+                self.sequence_top.seq(),
+                self.scoring_schema.as_ref(),
+                self.end_gaps,
+            );
+            (vec![(path, false)], score)
+        } else {
+            self.matrices = match diagonals {
+                Some(diagonals) => AffineMatrices::banded(rows, cols, diagonals),
+                None => AffineMatrices::new(rows, cols),
+            };
+            let (paths, score) = self.full_matrix_paths();
+            let paths = paths
+                .into_iter()
+                .map(|path| {
+                    let touches_band_edge =
+                        banded::touches_edge(&path, self.matrices.band(), [rows, cols]);
+                    (path, touches_band_edge)
+                })
+                .collect();
+            (paths, score)
+        };
+        let paths = paths
+            .into_iter()
+            .map(|(mut path, touches_band_edge)| {
+                self.add_terminal_gaps(&mut path);
+                (path, touches_band_edge)
+            })
+            .collect();
+
+        select_paths(paths, self.tie_break, self.co_optimal_limit)
+            .into_iter()
+            .map(|(path, touches_band_edge)| {
+                let mut alignment = AlignmentResult::new(
+                    path,
+                    self.sequence_left.as_ref(),
+                    self.sequence_top.as_ref(),
+                    score,
+                    self.scoring_schema.as_ref(),
+                );
+                alignment.set_touches_band_edge(touches_band_edge);
+                alignment
+            })
+            .collect()
+    }
+
+    fn score(&self) -> AlignmentScore {
+        let score = linear_space::global_score(
+            self.sequence_left.seq(),
+            self.sequence_top.seq(),
+            self.scoring_schema.as_ref(),
+            self.end_gaps,
+            self.band_diagonals(),
+        );
+        AlignmentScore::new(score, None)
+    }
+
+    // The band diagonals, clipped to the matrices, widened so the band contains a valid
+    // start and end cell. Without free end gaps those are the [0, 0] and last cells.
+    fn band_diagonals(&self) -> Option<[isize; 2]> {
+        let sequence_left = self.sequence_left.seq();
+        let sequence_top = self.sequence_top.seq();
+        let [mut lower, mut upper] =
+            banded::diagonals(self.band?, sequence_left, sequence_top);
+        let [rows, cols] = [sequence_left.len() as isize, sequence_top.len() as isize];
+        let end_gaps = self.end_gaps;
+
+        // Starts at [0, j] have diagonal j ≥ 0, starts at [i, 0] have diagonal -i ≤ 0.
+        match (end_gaps.leading_left, end_gaps.leading_top) {
+            (false, false) => [lower, upper] = [lower.min(0), upper.max(0)],
+            (true, false) => upper = upper.max(0),
+            (false, true) => lower = lower.min(0),
+            (true, true) => (),
+        }
+        // Ends at [rows, j] have diagonal j - rows ≤ cols - rows, ends at [i, cols] have
+        // diagonal cols - i ≥ cols - rows.
+        let last = cols - rows;
+        match (end_gaps.trailing_left, end_gaps.trailing_top) {
+            (false, false) => [lower, upper] = [lower.min(last), upper.max(last)],
+            (true, false) => lower = lower.min(last),
+            (false, true) => upper = upper.max(last),
+            (true, true) => (),
+        }
+        let [lower, upper] = [lower.min(cols), upper.max(-rows)];
+        Some(banded::clip(
+            [lower, upper],
+            [sequence_left.len() + 1, sequence_top.len() + 1],
+        ))
+    }
+
+    // Fills the whole matrices and backtracks from the end cells. Returns the co-optimal
+    // paths without the free terminal gaps, as many as needed by the tie-break policy, and
+    // their score.
+    fn full_matrix_paths(&mut self) -> (Vec<Vec<[usize; 2]>>, f32) {
         self.initialize();
         self.solve_subproblems();
-        let [row_dim, col_dim] = self.matrix.dim();
-        let [init_row, init_col] = [row_dim - 1, col_dim - 1];
-        let all_paths =
-            BackTrack::backtracking(&self.matrix, init_row, init_col, f32::NEG_INFINITY);
-        //let mut alignments: Vec<AlignmentSequence<A>> =
-        //    Vec::with_capacity(all_paths.len());
-
-        let longest_path = all_paths
+        let limit = match self.tie_break {
+            TieBreak::Longest => usize::MAX,
+            _ => self.co_optimal_limit.max(1),
+        };
+        let end_cells = self.end_cells();
+        let score = self.matrices.best(end_cells[0][0], end_cells[0][1]).1;
+
+        let mut paths: Vec<Vec<[usize; 2]>> = Vec::new();
+        for [init_row, init_col] in end_cells {
+            if paths.len() == limit {
+                break;
+            }
+            // The alignment can end in any state
+            let (end_states, _) = self.matrices.best(init_row, init_col);
+            paths.extend(self.matrices.backtracking(
+                init_row,
+                init_col,
+                end_states,
+                f32::NEG_INFINITY,
+                self.tie_break,
+                limit - paths.len(),
+            ));
+        }
+        (paths, score)
+    }
+
+    // The last cell is the end of a global alignment. With free trailing gaps at a sequence,
+    // the alignment can also end at any cell of the border where that sequence is consumed.
+    // Returns the cells with the best score, the last cell first, then the last row from
+    // right to left, then the last column from bottom to top.
+    fn end_cells(&self) -> Vec<[usize; 2]> {
+        let [row_dim, col_dim] = self.matrices.dim();
+        let [last_row, last_col] = [row_dim - 1, col_dim - 1];
+        let mut candidates: Vec<[usize; 2]> = vec![[last_row, last_col]];
+        if self.end_gaps.trailing_left {
+            candidates.extend((0..last_col).rev().map(|j| [last_row, j]));
+        }
+        if self.end_gaps.trailing_top {
+            candidates.extend((0..last_row).rev().map(|i| [i, last_col]));
+        }
+        let best_score = candidates
+            .iter()
+            .map(|[i, j]| self.matrices.best(*i, *j).1)
+            .fold(f32::NEG_INFINITY, f32::max);
+        candidates
             .into_iter()
-            .reduce(|acc, e| if acc.len() > e.len() { acc } else { e })
-            .unwrap();
-
-        //for backtrack_path in all_paths {
-        //    let new_alignment = AlignmentSequence::new(
-        //        backtrack_path,
-        //        self.sequence_left.as_ref(),
-        //        self.sequence_top.as_ref(),
-        //    );
-        //    alignments.push(new_alignment);
-
-        let alignments: Vec<AlignmentSequence<A>> = vec![AlignmentSequence::new(
-            longest_path,
-            self.sequence_left.as_ref(),
-            self.sequence_top.as_ref(),
-        )];
-        alignments
+            .filter(|[i, j]| self.matrices.best(*i, *j).1 == best_score)
+            .collect()
     }
+
+    // The backtracking stops at the first row or column, and may start before the last cell.
+    // Completes the path with the terminal gaps, from the last cell and to the [0, 0] cell.
+    fn add_terminal_gaps(&self, path: &mut Vec<[usize; 2]>) {
+        let row_dim = 1 + self.sequence_left.seq().len();
+        let col_dim = 1 + self.sequence_top.seq().len();
+        let [end_row, end_col] = path[0];
+        let trailing: Vec<[usize; 2]> = (end_row + 1..row_dim)
+            .rev()
+            .map(|i| [i, end_col])
+            .chain((end_col + 1..col_dim).rev().map(|j| [row_dim - 1, j]))
+            .collect();
+        path.splice(0..0, trailing);
+
+        let [start_row, start_col] = *path.last().unwrap();
+        path.extend((0..start_row).rev().map(|i| [i, 0]));
+        path.extend((0..start_col).rev().map(|j| [0, j]));
+    }
+
     fn initialize(&mut self) {
-        self.matrix[[0, 0]] = BackTrack::D(0.0);
-        let [rows, cols] = self.matrix.dim();
+        // Banded matrices only keep the border cells inside the band.
+        if self.matrices.in_band(0, 0) {
+            self.matrices.set(
+                0,
+                0,
+                [BackTrack::Empty, BackTrack::D(0.0), BackTrack::Empty],
+            );
+        }
+        let [rows, cols] = self.matrices.dim();
 
-        for i in 1..rows {
-            self.matrix[[i, 0]] = BackTrack::T(-self.scoring_schema.get_function(i));
+        // The first gap cell comes from the [0, 0] match state, the next ones extend it.
+        // Free leading gaps make every border cell a valid start, like [0, 0].
+        // The gaps cost is accumulated from the units they face, also outside the band.
+        let mut gap = 0.0;
+        for (i, unit) in (1..rows).zip(self.sequence_left.seq()) {
+            let [open, extend] = self.scoring_schema.get_gap_costs(*unit);
+            gap -= if i == 1 { open + extend } else { extend };
+            if !self.matrices.in_band(i, 0) {
+                continue;
+            }
+            let states = if self.end_gaps.leading_top {
+                [BackTrack::Empty, BackTrack::D(0.0), BackTrack::Empty]
+            } else if i == 1 {
+                [BackTrack::D(gap), BackTrack::Empty, BackTrack::Empty]
+            } else {
+                [BackTrack::T(gap), BackTrack::Empty, BackTrack::Empty]
+            };
+            self.matrices.set(i, 0, states);
         }
 
-        for j in 1..cols {
-            self.matrix[[0, j]] = BackTrack::L(-self.scoring_schema.get_function(j));
+        let mut gap = 0.0;
+        for (j, unit) in (1..cols).zip(self.sequence_top.seq()) {
+            let [open, extend] = self.scoring_schema.get_gap_costs(*unit);
+            gap -= if j == 1 { open + extend } else { extend };
+            if !self.matrices.in_band(0, j) {
+                continue;
+            }
+            let states = if self.end_gaps.leading_left {
+                [BackTrack::Empty, BackTrack::D(0.0), BackTrack::Empty]
+            } else if j == 1 {
+                [BackTrack::Empty, BackTrack::Empty, BackTrack::D(gap)]
+            } else {
+                [BackTrack::Empty, BackTrack::Empty, BackTrack::L(gap)]
+            };
+            self.matrices.set(0, j, states);
         }
     }
 
     fn solve_subproblems(&mut self) {
-        let [rows, cols] = self.matrix.dim();
+        let [rows, _] = self.matrices.dim();
         for i in 1..rows {
-            for j in 1..cols {
+            for j in self.matrices.row_range(i).filter(|j| *j > 0) {
                 let diagonal = Self::diagonal_score(
                     self.sequence_left.as_ref(),
                     self.sequence_top.as_ref(),
-                    &self.scoring_schema,
-                    &self.matrix,
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
+                    i,
+                    j,
+                    f32::NEG_INFINITY,
+                );
+                let top = Self::top_score(
+                    self.sequence_left.as_ref(),
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
                     i,
                     j,
                 );
-                let top = Self::top_score(&self.scoring_schema, &self.matrix, i, j);
-                let left = Self::left_score(&self.scoring_schema, &self.matrix, i, j);
-                self.matrix[[i, j]] = BackTrack::make_backtrack(top, diagonal, left).0;
+                let left = Self::left_score(
+                    self.sequence_top.as_ref(),
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
+                    i,
+                    j,
+                );
+                self.matrices.set(i, j, [top, diagonal, left]);
             }
         }
     }
@@ -119,19 +359,89 @@ impl<A> Aligner<A> for NeedlemanWunsch<A>
 where
     A: AlignmentUnit,
 {
-    fn run(&mut self) -> Vec<AlignmentSequence<A>> {
+    fn run(&mut self) -> Vec<AlignmentResult<A>> {
         self.run()
     }
+
+    fn set_memory_budget(&mut self, bytes: usize) {
+        self.memory_budget = bytes;
+    }
+
+    fn set_band(&mut self, band: Band) {
+        self.band = Some(band);
+    }
+
+    fn set_co_optimal_limit(&mut self, limit: usize) {
+        self.co_optimal_limit = limit;
+    }
+
+    fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+
+    // Global alignment scores have no Karlin-Altschul statistics.
+    fn set_statistics(&mut self, _statistics: KarlinAltschul) {}
+
+    fn set_search_space(&mut self, _search_space: f64) {}
+
+    fn score(&self) -> AlignmentScore {
+        self.score()
+    }
+
+    fn shuffle_significance(
+        &self,
+        shuffles: usize,
+        shuffle: Shuffle,
+        seed: u64,
+    ) -> ShuffleSignificance {
+        let score = linear_space::global_score(
+            self.sequence_left.seq(),
+            self.sequence_top.seq(),
+            self.scoring_schema.as_ref(),
+            self.end_gaps,
+            None,
+        );
+        shuffle::significance(
+            self.sequence_top.seq(),
+            score,
+            shuffles,
+            shuffle,
+            seed,
+            |shuffled| {
+                linear_space::global_score(
+                    self.sequence_left.seq(),
+                    shuffled,
+                    self.scoring_schema.as_ref(),
+                    self.end_gaps,
+                    None,
+                )
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        bioseq::{Aac, Protein},
-        scoring_schema::{aminoacid_schema::AaScoringKind, gap_penalty::PenaltyKind},
+        aligner::{
+            aminoacid_align_builder, nucleotide_align_builder, utils::AlignmentResult,
+            AlignerKind, Band, EndGaps, Shuffle, TieBreak,
+        },
+        bioseq::{Aac, Dna, HasSequence, Nuc, Protein, Rna},
+        scoring_schema::{
+            aminoacid_schema::AaScoringKind, gap_penalty::PenaltyKind,
+            nucleotide_schema::NucScoringKind, AaScoringSchema,
+        },
+        tests::brute_force::{
+            alignment_score, best_global_score, best_semi_global_score,
+            semi_global_alignment_score, TestRng, AMINOACIDS,
+        },
     };
 
     use super::NeedlemanWunsch;
+    use crate::aligner::{utils::AffineMatrices, Aligner};
+    use crate::InputErrorKind;
+    use std::collections::HashSet;
 
     #[test]
     fn nw_blossum62_affine() {
@@ -150,7 +460,8 @@ fn nw_blossum62_affine() {
             sequence_top,
             AaScoringKind::Blosum62,
             PenaltyKind::Affine(10.0, 1.0),
-        );
+        )
+        .unwrap();
 
         let alignments = nw.run();
 
@@ -286,4 +597,392 @@ fn nw_blossum62_affine() {
             )
         }
     }
+
+    #[test]
+    fn nw_dna_rna_match_mismatch() {
+        let mut nw = nucleotide_align_builder(
+            AlignerKind::NeedlemanWunsch,
+            Dna::new("ACGTAGCA").unwrap(),
+            Rna::new("ACGAGCU").unwrap(),
+            NucScoringKind::MatchMismatch(1, -1),
+            PenaltyKind::Linear(1.0),
+        )
+        .unwrap();
+        let expected_alignment = [
+            [Some(Nuc::A), Some(Nuc::A)],
+            [Some(Nuc::C), Some(Nuc::C)],
+            [Some(Nuc::G), Some(Nuc::G)],
+            [Some(Nuc::T), None],
+            [Some(Nuc::A), Some(Nuc::A)],
+            [Some(Nuc::G), Some(Nuc::G)],
+            [Some(Nuc::C), Some(Nuc::C)],
+            [Some(Nuc::A), Some(Nuc::U)],
+        ];
+        assert_eq!(expected_alignment.to_vec(), *nw.run()[0].read());
+    }
+
+    #[test]
+    fn nw_is_optimal() {
+        let penalties = [
+            PenaltyKind::Affine(10.0, 1.0),
+            PenaltyKind::Affine(3.0, 0.5),
+            PenaltyKind::Affine(1.0, 2.0),
+            PenaltyKind::Affine(1.0, 0.5),
+            PenaltyKind::Linear(1.0),
+        ];
+        let mut rng = TestRng::new(7);
+        for _ in 0..50 {
+            for penalty_kind in penalties {
+                let left_string = rng.sequence(AMINOACIDS, 6);
+                let top_string = rng.homolog(AMINOACIDS, &left_string);
+                let mut aligner = NeedlemanWunsch::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap();
+                let alignment = aligner.run().remove(0);
+
+                let scoring_schema =
+                    AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+                let expected = best_global_score(
+                    Protein::new(&left_string).unwrap().seq(),
+                    Protein::new(&top_string).unwrap().seq(),
+                    &scoring_schema,
+                );
+                let actual = alignment_score(alignment.read(), &scoring_schema);
+                assert_eq!(
+                    expected, actual,
+                    "Suboptimal alignment of {left_string} and {top_string}:\n{alignment}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn semi_global_domain_in_protein() {
+        // The domain aligns without gaps at the middle of the protein.
+        let mut aligner = aminoacid_align_builder(
+            AlignerKind::SemiGlobal(EndGaps {
+                leading_left: true,
+                trailing_left: true,
+                ..Default::default()
+            }),
+            Protein::new("HGEYW").unwrap(),
+            Protein::new("MKVLAAGHGEYWRTPLLK").unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(10.0, 1.0),
+        )
+        .unwrap();
+        let alignment = aligner.run().remove(0);
+        let pairs = alignment.read();
+        assert_eq!(18, pairs.len());
+        assert!(pairs[..7].iter().all(|[left, _]| left.is_none()));
+        assert!(pairs[12..].iter().all(|[left, _]| left.is_none()));
+        assert!(pairs[7..12].iter().all(|[left, top]| left == top));
+    }
+
+    #[test]
+    fn semi_global_overlap() {
+        // A suffix of the left sequence overlaps a prefix of the top sequence.
+        let mut nw = nucleotide_align_builder(
+            AlignerKind::SemiGlobal(EndGaps {
+                leading_top: true,
+                trailing_left: true,
+                ..Default::default()
+            }),
+            Dna::new("TTTTACGTACGG").unwrap(),
+            Dna::new("ACGTACGGCCCC").unwrap(),
+            NucScoringKind::MatchMismatch(1, -1),
+            PenaltyKind::Linear(2.0),
+        )
+        .unwrap();
+        let pairs = nw.run().remove(0).read().clone();
+        let expected_pairs: Vec<[Option<Nuc>; 2]> = [
+            vec![[Some(Nuc::T), None]; 4],
+            Dna::new("ACGTACGG")
+                .unwrap()
+                .seq()
+                .iter()
+                .map(|n| [Some(*n), Some(*n)])
+                .collect(),
+            vec![[None, Some(Nuc::C)]; 4],
+        ]
+        .concat();
+        assert_eq!(expected_pairs, pairs);
+    }
+
+    #[test]
+    fn semi_global_is_optimal() {
+        let penalties = [
+            PenaltyKind::Affine(10.0, 1.0),
+            PenaltyKind::Affine(1.0, 2.0),
+            PenaltyKind::Linear(1.0),
+        ];
+        let mut rng = TestRng::new(5);
+        for _ in 0..30 {
+            for penalty_kind in penalties {
+                let flags = rng.next_u64();
+                let end_gaps = EndGaps {
+                    leading_left: flags & 1 != 0,
+                    trailing_left: flags & 2 != 0,
+                    leading_top: flags & 4 != 0,
+                    trailing_top: flags & 8 != 0,
+                };
+                let left_string = rng.sequence(AMINOACIDS, 6);
+                let top_string = rng.homolog(AMINOACIDS, &left_string);
+                let mut aligner = NeedlemanWunsch::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap()
+                .with_end_gaps(end_gaps);
+                let alignment = aligner.run().remove(0);
+
+                let scoring_schema =
+                    AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+                let expected = best_semi_global_score(
+                    Protein::new(&left_string).unwrap().seq(),
+                    Protein::new(&top_string).unwrap().seq(),
+                    &scoring_schema,
+                    end_gaps,
+                );
+                let actual = semi_global_alignment_score(
+                    alignment.read(),
+                    &scoring_schema,
+                    end_gaps,
+                );
+                assert_eq!(
+                    expected, actual,
+                    "Suboptimal alignment of {left_string} and {top_string} with {end_gaps:?}:\n{alignment}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_gap_parameters() {
+        let aligner = NeedlemanWunsch::new(
+            Protein::new("HGEYW").unwrap(),
+            Protein::new("HGEW").unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(0.5, 1.0),
+        );
+        assert!(aligner.is_err_and(|e| e.kind == InputErrorKind::InvalidOpenCost));
+
+        let aligner = nucleotide_align_builder(
+            AlignerKind::SemiGlobal(EndGaps::all()),
+            Dna::new("ACGT").unwrap(),
+            Dna::new("ACT").unwrap(),
+            NucScoringKind::Ednafull,
+            PenaltyKind::Linear(f32::NAN),
+        );
+        assert!(aligner.is_err_and(|e| e.kind == InputErrorKind::InvalidExtendCost));
+    }
+
+    #[test]
+    fn memory_budget_switches_to_linear_space() {
+        let mut aligner = NeedlemanWunsch::new(
+            Protein::new("HEAGAWGHEE").unwrap(),
+            Protein::new("PAWHEAE").unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(10.0, 1.0),
+        )
+        .unwrap();
+        let estimated_size = AffineMatrices::estimated_size(11, 8);
+
+        aligner.set_memory_budget(estimated_size);
+        let full_matrices = aligner.run().remove(0);
+        assert_eq!([11, 8], aligner.matrices.dim());
+
+        aligner.matrices = AffineMatrices::new(0, 0);
+        aligner.set_memory_budget(estimated_size - 1);
+        let linear_space = aligner.run().remove(0);
+        assert_eq!([0, 0], aligner.matrices.dim());
+        assert_eq!(full_matrices.score(), linear_space.score());
+    }
+
+    #[test]
+    fn banded_nw() {
+        let mut rng = TestRng::new(17);
+        let penalty_kind = PenaltyKind::Affine(10.0, 1.0);
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+        for _ in 0..20 {
+            let left_string = rng.sequence(AMINOACIDS, 200);
+            let top_string = rng.homolog(AMINOACIDS, &left_string);
+            let aligner = || {
+                NeedlemanWunsch::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap()
+            };
+            let full_matrices = aligner().run().remove(0);
+
+            // A band as wide as the matrices is the full alignment.
+            let mut banded = aligner();
+            banded.set_band(Band::Fixed(400));
+            let alignment = banded.run().remove(0);
+            assert_eq!(full_matrices.score(), alignment.score());
+            assert!(!alignment.touches_band_edge());
+
+            // A narrow band can only miss the optimum when its path reaches the edge.
+            let mut banded = aligner();
+            banded.set_band(Band::Fixed(2));
+            let alignment = banded.run().remove(0);
+            assert_eq!(
+                alignment.score(),
+                alignment_score(alignment.read(), &scoring_schema)
+            );
+            assert!(alignment.score() <= full_matrices.score());
+            if alignment.score() < full_matrices.score() {
+                assert!(alignment.touches_band_edge());
+            }
+        }
+    }
+
+    #[test]
+    fn banded_nw_includes_the_corners() {
+        // The end cell is far from the main diagonal, the band is widened to reach it.
+        let mut aligner = NeedlemanWunsch::new(
+            Protein::new("MKTAYIAKQR").unwrap(),
+            Protein::new("GSHHHHHHMKTAYIAKQR").unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(10.0, 1.0),
+        )
+        .unwrap();
+        aligner.set_band(Band::Fixed(1));
+        let alignment = aligner.run().remove(0);
+        assert_eq!(Some([-1, 8]), aligner.matrices.band());
+        assert_eq!(
+            [
+                "--------MKTAYIAKQR".to_string(),
+                "GSHHHHHHMKTAYIAKQR".to_string()
+            ],
+            alignment.alignment().aligned_strings()
+        );
+        // The path runs along the widened upper edge.
+        assert!(alignment.touches_band_edge());
+    }
+
+    #[test]
+    fn co_optimal_alignments() {
+        let aligner = || {
+            NeedlemanWunsch::new(
+                Protein::new("GAA").unwrap(),
+                Protein::new("GA").unwrap(),
+                AaScoringKind::Blosum62,
+                PenaltyKind::Affine(10.0, 1.0),
+            )
+            .unwrap()
+        };
+        let aligned = |alignments: Vec<AlignmentResult<Aac>>| -> Vec<String> {
+            alignments
+                .iter()
+                .map(|alignment| alignment.alignment().aligned_strings()[1].clone())
+                .collect()
+        };
+
+        let mut aligner_instance = aligner();
+        assert_eq!(vec!["G-A"], aligned(aligner_instance.run()));
+        aligner_instance.set_co_optimal_limit(5);
+        assert_eq!(vec!["G-A", "GA-"], aligned(aligner_instance.run()));
+
+        let test_cases = [
+            (TieBreak::PreferDiagonal, vec!["G-A", "GA-"]),
+            (TieBreak::PreferGapLeft, vec!["G-A", "GA-"]),
+            (TieBreak::PreferGapTop, vec!["GA-", "G-A"]),
+        ];
+        for (tie_break, expected) in test_cases {
+            let mut aligner_instance = aligner();
+            aligner_instance.set_tie_break(tie_break);
+            assert_eq!(expected[..1], aligned(aligner_instance.run()));
+            aligner_instance.set_co_optimal_limit(5);
+            assert_eq!(expected, aligned(aligner_instance.run()), "{tie_break:?}");
+        }
+    }
+
+    #[test]
+    fn co_optimal_alignments_are_distinct_and_optimal() {
+        let mut rng = TestRng::new(41);
+        let penalty_kind = PenaltyKind::Affine(5.0, 2.0);
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+        for tie_break in [TieBreak::PreferGapLeft, TieBreak::Longest] {
+            for _ in 0..20 {
+                let left_string = rng.sequence(AMINOACIDS, 8);
+                let top_string = rng.homolog(AMINOACIDS, &left_string);
+                let mut aligner = NeedlemanWunsch::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap()
+                .with_end_gaps(EndGaps::all());
+                aligner.set_tie_break(tie_break);
+                aligner.set_co_optimal_limit(10);
+                let alignments = aligner.run();
+                assert!(alignments.len() <= 10);
+
+                let mut seen = HashSet::new();
+                for alignment in &alignments {
+                    assert!(seen.insert(alignment.alignment().aligned_strings()));
+                    assert_eq!(
+                        alignments[0].score(),
+                        semi_global_alignment_score(
+                            alignment.read(),
+                            &scoring_schema,
+                            EndGaps::all()
+                        )
+                    );
+                }
+                if tie_break == TieBreak::Longest {
+                    assert!(alignments
+                        .windows(2)
+                        .all(|w| w[0].length() >= w[1].length()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shuffle_significance() {
+        let mut rng = TestRng::new(16);
+        let mut left_string = String::new();
+        while left_string.len() < 150 {
+            left_string.push_str(&rng.sequence(AMINOACIDS, 50));
+        }
+        let homolog_string = rng.homolog(AMINOACIDS, &left_string);
+        let unrelated_string = rng.sequence(AMINOACIDS, left_string.len());
+        let aligner = |top_string: &str| {
+            NeedlemanWunsch::new(
+                Protein::new(&left_string).unwrap(),
+                Protein::new(top_string).unwrap(),
+                AaScoringKind::Blosum62,
+                PenaltyKind::Affine(10.0, 1.0),
+            )
+            .unwrap()
+        };
+
+        let homolog = aligner(&homolog_string);
+        for shuffle in [Shuffle::Permutation, Shuffle::Dinucleotide] {
+            let significance = homolog.shuffle_significance(99, shuffle, 1);
+            assert_eq!(homolog.score().score(), significance.score());
+            assert!(significance.z_score() > 5.0);
+            assert_eq!(0.01, significance.p_value());
+            assert_eq!(significance, homolog.shuffle_significance(99, shuffle, 1));
+        }
+
+        let significance =
+            aligner(&unrelated_string).shuffle_significance(99, Shuffle::Permutation, 1);
+        assert!(significance.z_score() < 3.0);
+        assert!(significance.p_value() > 0.01);
+    }
 }
@@ -0,0 +1,908 @@
+//! Linear-space alignment for sequences whose traceback matrices do not fit in memory.
+//!
+//! The optimal path is rebuilt with the Myers-Miller divide and conquer algorithm, the
+//! affine gap version of Hirschberg's algorithm (a linear gap penalty is the case where the
+//! open cost is 0). Only a few rows of scores are kept, so memory grows with the sequences
+//! length instead of with its product, at the cost of about twice the running time.
+//!
+//! Local and semi-global alignments first find where the optimal path starts and ends with
+//! two score-only sweeps (forward and over the reversed prefixes), then the path between
+//! those cells is an ordinary global alignment.
+
+use super::EndGaps;
+use crate::scoring_schema::ScoringSchema;
+use crate::utils::AlignmentUnit;
+
+/// Rows of scores and gap costs of a sweep. Keeping them between sweeps reuses their
+/// memory, as when scoring one sequence against many others.
+#[derive(Default)]
+pub(super) struct SweepBuffers {
+    top_costs: Vec<[f32; 2]>,
+    row_gaps: Vec<f32>,
+    previous: Vec<[f32; 3]>,
+    current: Vec<[f32; 3]>,
+}
+
+/// Score-only sweep of the [top, diagonal, left] states, row by row. Calls `visit` with
+/// every cell, including the first row and column. Only two rows are kept in memory.
+///
+/// The first row (column) cells are valid starts when `free_row` (`free_col`) is set,
+/// otherwise they can only be reached with gaps from [0, 0]. `floor` is a lower bound for
+/// the diagonal predecessor score, as in AffineTransversalOrder::diagonal_score. With a
+/// band of diagonals [lower, upper], only the cells inside it are computed and visited.
+fn sweep<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    free: [bool; 2],
+    floor: f32,
+    band: Option<[isize; 2]>,
+    visit: impl FnMut(usize, usize, [f32; 3]),
+) {
+    sweep_with(
+        &mut SweepBuffers::default(),
+        sequence_left,
+        sequence_top,
+        scoring_schema,
+        free,
+        floor,
+        band,
+        visit,
+    )
+}
+
+/// Same as sweep, keeping the rows in `buffers`.
+#[allow(clippy::too_many_arguments)]
+fn sweep_with<A: AlignmentUnit>(
+    buffers: &mut SweepBuffers,
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    [free_row, free_col]: [bool; 2],
+    floor: f32,
+    band: Option<[isize; 2]>,
+    mut visit: impl FnMut(usize, usize, [f32; 3]),
+) {
+    let unreachable = [f32::NEG_INFINITY; 3];
+    let start = [f32::NEG_INFINITY, 0.0, f32::NEG_INFINITY];
+    let SweepBuffers {
+        top_costs,
+        row_gaps,
+        previous,
+        current,
+    } = buffers;
+    // Gap costs of the top units, and of the leading gaps of each length in front of them.
+    top_costs.clear();
+    top_costs.extend(
+        sequence_top
+            .iter()
+            .map(|unit| scoring_schema.get_gap_costs(*unit)),
+    );
+    row_gaps.clear();
+    row_gaps.resize(sequence_top.len() + 1, 0.0);
+    for (j, [open, extend]) in (1..).zip(top_costs.iter()) {
+        row_gaps[j] = row_gaps[j - 1] + if j == 1 { open + extend } else { *extend };
+    }
+    let cols = sequence_top.len() + 1;
+    let [lower, upper] =
+        band.unwrap_or([-(sequence_left.len() as isize), sequence_top.len() as isize]);
+    let columns = |i: usize| {
+        let start = (i as isize + lower).clamp(0, cols as isize);
+        let end = (i as isize + upper + 1).clamp(start, cols as isize);
+        start as usize..end as usize
+    };
+
+    previous.clear();
+    previous.resize(cols, unreachable);
+    for j in columns(0) {
+        previous[j] = match j {
+            0 => start,
+            _ if free_row => start,
+            _ => [f32::NEG_INFINITY, f32::NEG_INFINITY, -row_gaps[j]],
+        };
+        visit(0, j, previous[j]);
+    }
+    current.clear();
+    current.resize(cols, unreachable);
+
+    let mut column_gap = 0.0;
+    for (i, left_unit) in (1..).zip(sequence_left) {
+        let [open, extend] = scoring_schema.get_gap_costs(*left_unit);
+        column_gap += if i == 1 { open + extend } else { extend };
+        // The cells next to the band are read as unreachable.
+        let range = columns(i);
+        if range.start > 0 {
+            current[range.start - 1] = unreachable;
+        }
+        if range.end < cols {
+            current[range.end] = unreachable;
+        }
+        for j in range {
+            if j == 0 {
+                current[0] = match free_col {
+                    true => start,
+                    false => [-column_gap, f32::NEG_INFINITY, f32::NEG_INFINITY],
+                };
+                visit(i, 0, current[0]);
+                continue;
+            }
+            let [top, diagonal, left] = previous[j - 1];
+            let diagonal = top.max(diagonal).max(left).max(floor)
+                + scoring_schema.get_score(*left_unit, sequence_top[j - 1]) as f32;
+
+            // A gap at top faces the left unit, a gap at left faces the top unit.
+            let [top, above, left] = previous[j];
+            let top = (top - extend)
+                .max(above - (open + extend))
+                .max(left - (open + extend));
+
+            let [top_open, top_extend] = top_costs[j - 1];
+            let [before_top, before, left] = current[j - 1];
+            let left = (left - top_extend)
+                .max(before - (top_open + top_extend))
+                .max(before_top - (top_open + top_extend));
+
+            current[j] = [top, diagonal, left];
+            visit(i, j, current[j]);
+        }
+        std::mem::swap(previous, current);
+    }
+}
+
+fn best(states: [f32; 3]) -> f32 {
+    states[0].max(states[1]).max(states[2])
+}
+
+/// Optimal global (or semi-global) alignment in linear space. Returns the backtracking
+/// path, from the end cell to the start cell, without the free terminal gaps, and the
+/// alignment score.
+pub(super) fn global_alignment<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    end_gaps: EndGaps,
+) -> (Vec<[usize; 2]>, f32) {
+    let [rows, cols] = [sequence_left.len(), sequence_top.len()];
+    let mut last_row: Vec<f32> = vec![0.0; cols + 1];
+    let mut last_col: Vec<f32> = vec![0.0; rows + 1];
+    sweep(
+        sequence_left,
+        sequence_top,
+        scoring_schema,
+        [end_gaps.leading_left, end_gaps.leading_top],
+        f32::NEG_INFINITY,
+        None,
+        |i, j, states| {
+            if i == rows {
+                last_row[j] = best(states);
+            }
+            if j == cols {
+                last_col[i] = best(states);
+            }
+        },
+    );
+
+    // Same candidates and preference order as the full matrix aligner.
+    let mut end_candidates: Vec<[usize; 2]> = vec![[rows, cols]];
+    if end_gaps.trailing_left {
+        end_candidates.extend((0..cols).rev().map(|j| [rows, j]));
+    }
+    if end_gaps.trailing_top {
+        end_candidates.extend((0..rows).rev().map(|i| [i, cols]));
+    }
+    let score_at = |[i, j]: [usize; 2]| if i == rows { last_row[j] } else { last_col[i] };
+    let end = end_candidates
+        .into_iter()
+        .reduce(|acc, e| if score_at(e) > score_at(acc) { e } else { acc })
+        .unwrap();
+    let score = score_at(end);
+
+    let start = if end_gaps.leading_left || end_gaps.leading_top {
+        semi_global_start(sequence_left, sequence_top, scoring_schema, end_gaps, end)
+    } else {
+        [0, 0]
+    };
+
+    (
+        myers_miller(sequence_left, sequence_top, scoring_schema, start, end),
+        score,
+    )
+}
+
+// The start cell of the best alignment ending at `end`, among [0, 0] and the first row
+// and column cells allowed by the free leading gaps. The sweep over the reversed prefixes
+// gives the global score from each candidate to the end cell.
+fn semi_global_start<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    end_gaps: EndGaps,
+    [end_row, end_col]: [usize; 2],
+) -> [usize; 2] {
+    let reversed_left: Vec<A> = sequence_left[..end_row].iter().rev().copied().collect();
+    let reversed_top: Vec<A> = sequence_top[..end_col].iter().rev().copied().collect();
+    let mut start = [0, 0];
+    let mut start_score = f32::NEG_INFINITY;
+    sweep(
+        &reversed_left,
+        &reversed_top,
+        scoring_schema,
+        [false, false],
+        f32::NEG_INFINITY,
+        None,
+        |i, j, states| {
+            let candidate = [end_row - i, end_col - j];
+            let allowed = candidate == [0, 0]
+                || (candidate[0] == 0 && end_gaps.leading_left)
+                || (candidate[1] == 0 && end_gaps.leading_top);
+            // [0, 0] is visited last, so ties keep the earlier candidate unless it is [0, 0].
+            if allowed
+                && (best(states) > start_score
+                    || (candidate == [0, 0] && best(states) == start_score))
+            {
+                start = candidate;
+                start_score = best(states);
+            }
+        },
+    );
+    start
+}
+
+/// Optimal local alignment in linear space. Returns the backtracking path, from the end
+/// cell to the start cell, and the alignment score. Without any positive score the
+/// alignment is empty.
+pub(super) fn local_alignment<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+) -> (Vec<[usize; 2]>, f32) {
+    // Local alignments always end in a match/mismatch state.
+    let mut end = [0, 0];
+    let mut score = 0.0;
+    sweep(
+        sequence_left,
+        sequence_top,
+        scoring_schema,
+        [true, true],
+        0.0,
+        None,
+        |i, j, [_, diagonal, _]| {
+            if diagonal > score {
+                score = diagonal;
+                end = [i, j];
+            }
+        },
+    );
+    if end == [0, 0] {
+        return (vec![[0, 0]], 0.0);
+    }
+
+    // And they start with a match/mismatch too. Among equal scores, keeps the longest one.
+    let [end_row, end_col] = end;
+    let reversed_left: Vec<A> = sequence_left[..end_row].iter().rev().copied().collect();
+    let reversed_top: Vec<A> = sequence_top[..end_col].iter().rev().copied().collect();
+    let mut start = [end_row - 1, end_col - 1];
+    let mut start_score = f32::NEG_INFINITY;
+    sweep(
+        &reversed_left,
+        &reversed_top,
+        scoring_schema,
+        [false, false],
+        f32::NEG_INFINITY,
+        None,
+        |i, j, [_, diagonal, _]| {
+            if diagonal >= start_score {
+                start_score = diagonal;
+                start = [end_row - i, end_col - j];
+            }
+        },
+    );
+
+    (
+        myers_miller(sequence_left, sequence_top, scoring_schema, start, end),
+        score,
+    )
+}
+
+/// Optimal global (or semi-global) alignment score, without the path. The columns are the
+/// shorter sequence, so it only keeps two rows of min(n, m) + 1 cells. With a band of
+/// diagonals [lower, upper], only the cells inside it are computed.
+pub(super) fn global_score<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    end_gaps: EndGaps,
+    band: Option<[isize; 2]>,
+) -> f32 {
+    if sequence_top.len() > sequence_left.len() {
+        let end_gaps = EndGaps {
+            leading_left: end_gaps.leading_top,
+            trailing_left: end_gaps.trailing_top,
+            leading_top: end_gaps.leading_left,
+            trailing_top: end_gaps.trailing_left,
+        };
+        let band = band.map(|[lower, upper]| [-upper, -lower]);
+        let transposed = Transposed(scoring_schema);
+        return global_score(sequence_top, sequence_left, &transposed, end_gaps, band);
+    }
+
+    let [rows, cols] = [sequence_left.len(), sequence_top.len()];
+    let mut score = f32::NEG_INFINITY;
+    sweep(
+        sequence_left,
+        sequence_top,
+        scoring_schema,
+        [end_gaps.leading_left, end_gaps.leading_top],
+        f32::NEG_INFINITY,
+        band,
+        |i, j, states| {
+            let end = [i, j] == [rows, cols]
+                || (i == rows && end_gaps.trailing_left)
+                || (j == cols && end_gaps.trailing_top);
+            if end {
+                score = score.max(best(states));
+            }
+        },
+    );
+    score
+}
+
+/// Optimal local alignment score and its end cell, without the path. The end cell is the
+/// first one in row-major order among the equally scored ones, or [0, 0] without any
+/// positive score. Uses O(min(n, m)) memory, see global_score.
+pub(super) fn local_score<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    band: Option<[isize; 2]>,
+) -> (f32, [usize; 2]) {
+    local_score_with(
+        &mut SweepBuffers::default(),
+        sequence_left,
+        sequence_top,
+        scoring_schema,
+        band,
+    )
+}
+
+/// Same as local_score, keeping the rows in `buffers`.
+pub(super) fn local_score_with<A: AlignmentUnit>(
+    buffers: &mut SweepBuffers,
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    band: Option<[isize; 2]>,
+) -> (f32, [usize; 2]) {
+    let transpose = sequence_top.len() > sequence_left.len();
+    let transposed = Transposed(scoring_schema);
+    let (sequence_left, sequence_top, scoring_schema, band) = if transpose {
+        let band = band.map(|[lower, upper]| [-upper, -lower]);
+        (
+            sequence_top,
+            sequence_left,
+            &transposed as &dyn ScoringSchema<A>,
+            band,
+        )
+    } else {
+        (sequence_left, sequence_top, scoring_schema, band)
+    };
+
+    let mut end = [0, 0];
+    let mut score = 0.0;
+    sweep_with(
+        buffers,
+        sequence_left,
+        sequence_top,
+        scoring_schema,
+        [true, true],
+        0.0,
+        band,
+        |i, j, [_, diagonal, _]| {
+            let cell = if transpose { [j, i] } else { [i, j] };
+            if diagonal > score || (diagonal == score && cell < end) {
+                score = diagonal;
+                end = cell;
+            }
+        },
+    );
+    (score, end)
+}
+
+/// Scoring schema of the swapped sequences: the left sequence of the alignment is the top
+/// sequence of the schema and vice versa.
+struct Transposed<'a, A: AlignmentUnit>(&'a dyn ScoringSchema<A>);
+
+impl<A: AlignmentUnit> ScoringSchema<A> for Transposed<'_, A> {
+    fn get_score(&self, code_1: A, code_2: A) -> i8 {
+        self.0.get_score(code_2, code_1)
+    }
+
+    fn get_function(&self, length: usize) -> f32 {
+        self.0.get_function(length)
+    }
+
+    fn get_open(&self) -> f32 {
+        self.0.get_open()
+    }
+
+    fn get_extend(&self) -> f32 {
+        self.0.get_extend()
+    }
+
+    fn get_gap_costs(&self, code: A) -> [f32; 2] {
+        self.0.get_gap_costs(code)
+    }
+
+    fn has_position_specific_gaps(&self) -> bool {
+        self.0.has_position_specific_gaps()
+    }
+}
+
+// Global alignment path between the start and end cells, from the end to the start.
+fn myers_miller<A: AlignmentUnit>(
+    sequence_left: &[A],
+    sequence_top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    [start_row, start_col]: [usize; 2],
+    [end_row, end_col]: [usize; 2],
+) -> Vec<[usize; 2]> {
+    let cols = end_col - start_col;
+    let mut solver = MyersMiller {
+        sequence_left: &sequence_left[start_row..end_row],
+        sequence_top: &sequence_top[start_col..end_col],
+        scoring_schema,
+        open: scoring_schema.get_open(),
+        extend: scoring_schema.get_extend(),
+        forward: vec![0.0; cols + 1],
+        forward_gap: vec![0.0; cols + 1],
+        reverse: vec![0.0; cols + 1],
+        reverse_gap: vec![0.0; cols + 1],
+        path: Vec::with_capacity(end_row - start_row + cols + 1),
+    };
+    solver.path.push([start_row, start_col]);
+    let open = solver.open;
+    solver.align([0, end_row - start_row], [0, cols], open, open);
+    solver.path.reverse();
+    solver.path
+}
+
+/// Myers & Miller (1988) "Optimal alignments in linear space", written for scores.
+///
+/// Each subproblem aligns a block of rows and columns. The rows are split in half, the
+/// forward scores of the upper half are joined with the reverse scores of the lower half
+/// to find the column where the optimal path crosses the middle row, and both parts are
+/// solved recursively. A crossing in the middle of a gap at the top sequence (a vertical
+/// run) is split around the gap, whose opening is then free for the two parts.
+struct MyersMiller<'a, A: AlignmentUnit> {
+    sequence_left: &'a [A],
+    sequence_top: &'a [A],
+    scoring_schema: &'a dyn ScoringSchema<A>,
+    open: f32,
+    extend: f32,
+    // best score and best score ending (starting) with a vertical gap, at the middle row
+    forward: Vec<f32>,
+    forward_gap: Vec<f32>,
+    reverse: Vec<f32>,
+    reverse_gap: Vec<f32>,
+    // cells of the path, from the start
+    path: Vec<[usize; 2]>,
+}
+
+impl<'a, A: AlignmentUnit> MyersMiller<'a, A> {
+    fn score(&self, i: usize, j: usize) -> f32 {
+        self.scoring_schema
+            .get_score(self.sequence_left[i], self.sequence_top[j]) as f32
+    }
+
+    fn gap(&self, length: usize) -> f32 {
+        match length {
+            0 => 0.0,
+            _ => self.open + self.extend * length as f32,
+        }
+    }
+
+    fn steps(&mut self, [di, dj]: [usize; 2], count: usize) {
+        for _ in 0..count {
+            let [i, j] = *self.path.last().unwrap();
+            self.path.push([i + di, j + dj]);
+        }
+    }
+
+    // `start_open` and `end_open` are the opening costs of a vertical gap touching the
+    // first or the last cell of the block: 0 when it continues a gap of the parent problem.
+    fn align(
+        &mut self,
+        [row_begin, row_end]: [usize; 2],
+        [col_begin, col_end]: [usize; 2],
+        start_open: f32,
+        end_open: f32,
+    ) {
+        let rows = row_end - row_begin;
+        let cols = col_end - col_begin;
+        if cols == 0 {
+            self.steps([1, 0], rows);
+            return;
+        }
+        if rows == 0 {
+            self.steps([0, 1], cols);
+            return;
+        }
+        if rows == 1 {
+            self.align_single_row(row_begin, [col_begin, col_end], start_open, end_open);
+            return;
+        }
+
+        let middle = row_begin + rows / 2;
+        self.forward_scores([row_begin, middle], [col_begin, col_end], start_open);
+        self.reverse_scores([middle, row_end], [col_begin, col_end], end_open);
+
+        let mut crossing = (0, false);
+        let mut crossing_score = f32::NEG_INFINITY;
+        for j in 0..=cols {
+            let through = self.forward[j] + self.reverse[j];
+            if through > crossing_score {
+                crossing = (j, false);
+                crossing_score = through;
+            }
+            let within_gap = self.forward_gap[j] + self.reverse_gap[j] + self.open;
+            if within_gap > crossing_score {
+                crossing = (j, true);
+                crossing_score = within_gap;
+            }
+        }
+
+        let col_middle = col_begin + crossing.0;
+        if crossing.1 {
+            self.align(
+                [row_begin, middle - 1],
+                [col_begin, col_middle],
+                start_open,
+                0.0,
+            );
+            self.steps([1, 0], 2);
+            self.align([middle + 1, row_end], [col_middle, col_end], 0.0, end_open);
+        } else {
+            let open = self.open;
+            self.align(
+                [row_begin, middle],
+                [col_begin, col_middle],
+                start_open,
+                open,
+            );
+            self.align([middle, row_end], [col_middle, col_end], open, end_open);
+        }
+    }
+
+    // A single residue of the left sequence is either aligned with one of the top
+    // sequence, or goes into a gap next to a single gap with the whole top block.
+    fn align_single_row(
+        &mut self,
+        row: usize,
+        [col_begin, col_end]: [usize; 2],
+        start_open: f32,
+        end_open: f32,
+    ) {
+        let cols = col_end - col_begin;
+        let mut aligned_col = 0;
+        let mut aligned_score = f32::NEG_INFINITY;
+        for j in 1..=cols {
+            let score =
+                self.score(row, col_begin + j - 1) - self.gap(j - 1) - self.gap(cols - j);
+            if score > aligned_score {
+                aligned_col = j;
+                aligned_score = score;
+            }
+        }
+        let gapped_score = -(start_open.min(end_open) + self.extend) - self.gap(cols);
+
+        if gapped_score > aligned_score {
+            if start_open <= end_open {
+                self.steps([1, 0], 1);
+                self.steps([0, 1], cols);
+            } else {
+                self.steps([0, 1], cols);
+                self.steps([1, 0], 1);
+            }
+        } else {
+            self.steps([0, 1], aligned_col - 1);
+            self.steps([1, 1], 1);
+            self.steps([0, 1], cols - aligned_col);
+        }
+    }
+
+    // Scores from the block start to each cell of the last row.
+    fn forward_scores(
+        &mut self,
+        [row_begin, row_end]: [usize; 2],
+        [col_begin, col_end]: [usize; 2],
+        start_open: f32,
+    ) {
+        let (open, extend) = (self.open, self.extend);
+        let cols = col_end - col_begin;
+        self.forward[0] = 0.0;
+        let mut gap = -open;
+        for j in 1..=cols {
+            gap -= extend;
+            self.forward[j] = gap;
+            self.forward_gap[j] = gap - open;
+        }
+
+        let mut gap = -start_open;
+        for i in row_begin..row_end {
+            let mut diagonal = self.forward[0];
+            gap -= extend;
+            let mut current = gap;
+            self.forward[0] = current;
+            let mut horizontal = gap - open;
+            for j in 1..=cols {
+                horizontal = horizontal.max(current - open) - extend;
+                self.forward_gap[j] =
+                    self.forward_gap[j].max(self.forward[j] - open) - extend;
+                current = self.forward_gap[j]
+                    .max(horizontal)
+                    .max(diagonal + self.score(i, col_begin + j - 1));
+                diagonal = self.forward[j];
+                self.forward[j] = current;
+            }
+        }
+        self.forward_gap[0] = self.forward[0];
+    }
+
+    // Scores from each cell of the first row to the block end.
+    fn reverse_scores(
+        &mut self,
+        [row_begin, row_end]: [usize; 2],
+        [col_begin, col_end]: [usize; 2],
+        end_open: f32,
+    ) {
+        let (open, extend) = (self.open, self.extend);
+        let cols = col_end - col_begin;
+        self.reverse[cols] = 0.0;
+        let mut gap = -open;
+        for j in (0..cols).rev() {
+            gap -= extend;
+            self.reverse[j] = gap;
+            self.reverse_gap[j] = gap - open;
+        }
+
+        let mut gap = -end_open;
+        for i in (row_begin..row_end).rev() {
+            let mut diagonal = self.reverse[cols];
+            gap -= extend;
+            let mut current = gap;
+            self.reverse[cols] = current;
+            let mut horizontal = gap - open;
+            for j in (0..cols).rev() {
+                horizontal = horizontal.max(current - open) - extend;
+                self.reverse_gap[j] =
+                    self.reverse_gap[j].max(self.reverse[j] - open) - extend;
+                current = self.reverse_gap[j]
+                    .max(horizontal)
+                    .max(diagonal + self.score(i, col_begin + j));
+                diagonal = self.reverse[j];
+                self.reverse[j] = current;
+            }
+        }
+        self.reverse_gap[cols] = self.reverse[cols];
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EndGaps;
+    use crate::{
+        aligner::{
+            global_alignment::NeedlemanWunsch, local_alignment::SmithWaterman, Aligner,
+            Band,
+        },
+        bioseq::{HasSequence, Protein},
+        scoring_schema::{
+            aminoacid_schema::AaScoringKind, gap_penalty::PenaltyKind, AaScoringSchema,
+        },
+        tests::brute_force::{
+            alignment_score, best_local_score, best_semi_global_score,
+            semi_global_alignment_score, TestRng, AMINOACIDS,
+        },
+    };
+
+    const PENALTIES: [PenaltyKind; 5] = [
+        PenaltyKind::Affine(10.0, 1.0),
+        PenaltyKind::Affine(1.0, 2.0),
+        PenaltyKind::Affine(5.0, 0.0),
+        PenaltyKind::Linear(1.0),
+        PenaltyKind::Linear(0.0),
+    ];
+
+    #[test]
+    fn linear_space_is_optimal() {
+        let mut rng = TestRng::new(23);
+        for _ in 0..30 {
+            for penalty_kind in PENALTIES {
+                let flags = rng.next_u64();
+                let end_gaps = EndGaps {
+                    leading_left: flags & 1 != 0,
+                    trailing_left: flags & 2 != 0,
+                    leading_top: flags & 4 != 0,
+                    trailing_top: flags & 8 != 0,
+                };
+                let left_string = rng.sequence(AMINOACIDS, 6);
+                let top_string = rng.homolog(AMINOACIDS, &left_string);
+                let left = Protein::new(&left_string).unwrap();
+                let top = Protein::new(&top_string).unwrap();
+                let scoring_schema =
+                    AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+
+                let mut aligner = NeedlemanWunsch::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap()
+                .with_end_gaps(end_gaps);
+                aligner.set_memory_budget(0);
+                let alignment = aligner.run().remove(0);
+                let expected = best_semi_global_score(
+                    left.seq(),
+                    top.seq(),
+                    &scoring_schema,
+                    end_gaps,
+                );
+                let actual = semi_global_alignment_score(
+                    alignment.read(),
+                    &scoring_schema,
+                    end_gaps,
+                );
+                assert_eq!(
+                    (expected, expected, expected),
+                    (actual, alignment.score(), aligner.score().score()),
+                    "{left_string} and {top_string} with {end_gaps:?}:\n{alignment}"
+                );
+
+                let mut aligner = SmithWaterman::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap();
+                aligner.set_memory_budget(0);
+                let alignment = aligner.run().remove(0);
+                let expected = best_local_score(left.seq(), top.seq(), &scoring_schema);
+                let actual = alignment_score(alignment.read(), &scoring_schema);
+                assert_eq!(
+                    (expected, expected, expected),
+                    (actual, alignment.score(), aligner.score().score()),
+                    "{left_string} and {top_string}:\n{alignment}"
+                );
+                assert_eq!(Some(alignment.end()), aligner.score().end());
+            }
+        }
+    }
+
+    #[test]
+    fn same_score_as_full_matrices() {
+        let mut rng = TestRng::new(3);
+        // Cheap gaps make the full matrices backtracking enumerate too many paths.
+        for penalty_kind in [
+            PenaltyKind::Affine(10.0, 1.0),
+            PenaltyKind::Affine(5.0, 2.0),
+        ] {
+            let left_string = rng.sequence(AMINOACIDS, 300);
+            let top_string = rng.homolog(AMINOACIDS, &left_string);
+            let scoring_schema =
+                AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+
+            let aligners: [Box<dyn Aligner<_>>; 2] = [
+                Box::new(
+                    NeedlemanWunsch::new(
+                        Protein::new(&left_string).unwrap(),
+                        Protein::new(&top_string).unwrap(),
+                        AaScoringKind::Blosum62,
+                        penalty_kind,
+                    )
+                    .unwrap(),
+                ),
+                Box::new(
+                    SmithWaterman::new(
+                        Protein::new(&left_string).unwrap(),
+                        Protein::new(&top_string).unwrap(),
+                        AaScoringKind::Blosum62,
+                        penalty_kind,
+                    )
+                    .unwrap(),
+                ),
+            ];
+            for mut aligner in aligners {
+                let full_matrices = aligner.run().remove(0);
+                aligner.set_memory_budget(0);
+                let linear_space = aligner.run().remove(0);
+                assert_eq!(full_matrices.score(), linear_space.score());
+                assert_eq!(
+                    linear_space.score(),
+                    alignment_score(linear_space.read(), &scoring_schema)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn banded_score_only() {
+        let mut rng = TestRng::new(31);
+        let penalty_kind = PenaltyKind::Affine(10.0, 1.0);
+        for round in 0..20 {
+            let mut left_string = rng.sequence(AMINOACIDS, 60);
+            let mut top_string = rng.homolog(AMINOACIDS, &left_string);
+            top_string.insert_str(0, "GSHHHHHH");
+            // Both orientations, the columns are the shorter sequence.
+            if round % 2 == 0 {
+                std::mem::swap(&mut left_string, &mut top_string);
+            }
+            for band in [Band::Fixed(2), Band::Adaptive(3), Band::Fixed(100)] {
+                let aligners: [Box<dyn Aligner<_>>; 3] = [
+                    Box::new(
+                        NeedlemanWunsch::new(
+                            Protein::new(&left_string).unwrap(),
+                            Protein::new(&top_string).unwrap(),
+                            AaScoringKind::Blosum62,
+                            penalty_kind,
+                        )
+                        .unwrap(),
+                    ),
+                    Box::new(
+                        NeedlemanWunsch::new(
+                            Protein::new(&left_string).unwrap(),
+                            Protein::new(&top_string).unwrap(),
+                            AaScoringKind::Blosum62,
+                            penalty_kind,
+                        )
+                        .unwrap()
+                        .with_end_gaps(EndGaps::all()),
+                    ),
+                    Box::new(
+                        SmithWaterman::new(
+                            Protein::new(&left_string).unwrap(),
+                            Protein::new(&top_string).unwrap(),
+                            AaScoringKind::Blosum62,
+                            penalty_kind,
+                        )
+                        .unwrap(),
+                    ),
+                ];
+                for mut aligner in aligners {
+                    aligner.set_band(band);
+                    let alignment = aligner.run().remove(0);
+                    assert_eq!(
+                        alignment.score(),
+                        aligner.score().score(),
+                        "{left_string} and {top_string} with {band:?}:\n{alignment}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reused_buffers() {
+        let mut rng = TestRng::new(37);
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, PenaltyKind::Affine(10.0, 1.0))
+                .unwrap();
+        let query = Protein::new(&rng.sequence(AMINOACIDS, 40)).unwrap();
+        // Targets shorter and longer than the query, so the rows shrink and grow.
+        let mut buffers = super::SweepBuffers::default();
+        for _ in 0..20 {
+            let target = Protein::new(&rng.sequence(AMINOACIDS, 80)).unwrap();
+            assert_eq!(
+                super::local_score(query.seq(), target.seq(), &scoring_schema, None),
+                super::local_score_with(
+                    &mut buffers,
+                    query.seq(),
+                    target.seq(),
+                    &scoring_schema,
+                    None
+                )
+            );
+        }
+    }
+}
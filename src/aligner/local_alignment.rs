@@ -1,13 +1,22 @@
 //! Algorithms for local alignment
 
-use super::utils::{AffineTransversalOrder, AlignmentSequence, BackTrack};
-use super::Aligner;
+use super::utils::{
+    select_paths, AffineMatrices, AffineTransversalOrder, AlignmentResult,
+    AlignmentScore, BackTrack, ShuffleSignificance,
+};
+use super::{
+    banded, linear_space, shuffle, Aligner, Band, Shuffle, TieBreak,
+    DEFAULT_MEMORY_BUDGET,
+};
 use crate::bioseq::{Aac, HasSequence};
-use crate::matrix::Matrix;
 use crate::scoring_schema::aminoacid_schema::AaScoringKind;
 use crate::scoring_schema::gap_penalty::PenaltyKind;
 use crate::scoring_schema::AaScoringSchema;
-use crate::{scoring_schema::ScoringSchema, utils::AlignmentUnit};
+use crate::statistics::KarlinAltschul;
+use crate::{
+    scoring_schema::ScoringSchema, utils::AlignmentUnit, InputError, InputErrorKind,
+};
+use std::collections::HashSet;
 
 /// Smith Waterman original algorithm. Returns the longest and best local alignment.
 pub struct SmithWaterman<A>
@@ -17,79 +26,344 @@ pub struct SmithWaterman<A>
     sequence_left: Box<dyn HasSequence<A>>,
     sequence_top: Box<dyn HasSequence<A>>,
     scoring_schema: Box<dyn ScoringSchema<A>>,
-    matrix: Matrix<BackTrack>,
+    matrices: AffineMatrices,
     /// The highest found score
     global_maximum: f32,
     /// Indices whose score is the global maximum
     maximum_indices: Vec<[usize; 2]>,
+    memory_budget: usize,
+    band: Option<Band>,
+    co_optimal_limit: usize,
+    tie_break: TieBreak,
+    /// Waterman-Eggert mode, the maximum number of hits and their minimum score
+    suboptimal: Option<(usize, f32)>,
+    /// Karlin-Altschul parameters of the scoring schema, if known
+    statistics: Option<KarlinAltschul>,
+    /// Search space of the E-values, if not the pairs of residues
+    search_space: Option<f64>,
 }
 
 impl<A> SmithWaterman<A>
 where
     A: AlignmentUnit,
 {
-    fn run(&mut self) -> Vec<AlignmentSequence<A>> {
+    fn run(&mut self) -> Vec<AlignmentResult<A>> {
+        let rows = 1 + self.sequence_left.seq().len();
+        let cols = 1 + self.sequence_top.seq().len();
+        let diagonals = self.band_diagonals();
+        let stored_cols =
+            diagonals.map_or(cols, |[lower, upper]| (upper - lower + 1) as usize);
+
+        // A band that does not fit in memory falls back to the linear-space algorithm,
+        // which does not need a band to find the optimal alignment. It assumes the same
+        // gap costs everywhere, so position-specific schemas keep the matrices. It only
+        // finds one alignment, so Waterman-Eggert keeps them too.
+        let linear_space = AffineMatrices::estimated_size(rows, stored_cols)
+            > self.memory_budget
+            && !self.scoring_schema.has_position_specific_gaps()
+            && self.suboptimal.is_none();
+        let hits: Vec<(Vec<[usize; 2]>, bool, f32)> = if linear_space {
+            let (path, score) = linear_space::local_alignment(
+                self.sequence_left.seq(),
+                self.sequence_top.seq(),
+                self.scoring_schema.as_ref(),
+            );
+            vec![(path, false, score)]
+        } else {
+            self.matrices = match diagonals {
+                Some(diagonals) => AffineMatrices::banded(rows, cols, diagonals),
+                None => AffineMatrices::new(rows, cols),
+            };
+            match self.suboptimal {
+                Some((hits, min_score)) => self.waterman_eggert(hits, min_score),
+                None => {
+                    let (paths, score) = self.full_matrix_paths();
+                    let paths = paths
+                        .into_iter()
+                        .map(|path| {
+                            let touches_band_edge = banded::touches_edge(
+                                &path,
+                                self.matrices.band(),
+                                [rows, cols],
+                            );
+                            (path, touches_band_edge)
+                        })
+                        .collect();
+                    select_paths(paths, self.tie_break, self.co_optimal_limit)
+                        .into_iter()
+                        .map(|(path, touches_band_edge)| (path, touches_band_edge, score))
+                        .collect()
+                }
+            }
+        };
+
+        // By default, the search space of a pairwise alignment are all the pairs of residues.
+        let search_space = self
+            .search_space
+            .unwrap_or(((rows - 1) * (cols - 1)) as f64);
+        hits.into_iter()
+            .map(|(path, touches_band_edge, score)| {
+                let mut alignment = AlignmentResult::new(
+                    path,
+                    self.sequence_left.as_ref(),
+                    self.sequence_top.as_ref(),
+                    score,
+                    self.scoring_schema.as_ref(),
+                );
+                alignment.set_touches_band_edge(touches_band_edge);
+                if let Some(statistics) = &self.statistics {
+                    alignment.set_significance(statistics, search_space);
+                }
+                alignment
+            })
+            .collect()
+    }
+
+    // The band diagonals, clipped to the matrices.
+    fn band_diagonals(&self) -> Option<[isize; 2]> {
+        let sequence_left = self.sequence_left.seq();
+        let sequence_top = self.sequence_top.seq();
+        let diagonals = banded::diagonals(self.band?, sequence_left, sequence_top);
+        Some(banded::clip(
+            diagonals,
+            [sequence_left.len() + 1, sequence_top.len() + 1],
+        ))
+    }
+
+    fn score(&self) -> AlignmentScore {
+        let (score, end) = linear_space::local_score(
+            self.sequence_left.seq(),
+            self.sequence_top.seq(),
+            self.scoring_schema.as_ref(),
+            self.band_diagonals(),
+        );
+        AlignmentScore::new(score, Some(end))
+    }
+
+    // Fills the whole matrices and backtracks from the best cells, in row-major order.
+    // Returns the co-optimal paths, as many as needed by the tie-break policy, and their
+    // score.
+    fn full_matrix_paths(&mut self) -> (Vec<Vec<[usize; 2]>>, f32) {
         self.initialize();
         self.solve_subproblems();
+        let limit = match self.tie_break {
+            TieBreak::Longest => usize::MAX,
+            _ => self.co_optimal_limit.max(1),
+        };
 
-        let mut all_paths: Vec<Vec<[usize; 2]>> = Vec::new();
-
+        let mut paths: Vec<Vec<[usize; 2]>> = Vec::new();
+        // Local alignments always end in a match/mismatch state.
         for [init_row, init_col] in &self.maximum_indices {
-            let mut path =
-                BackTrack::backtracking(&self.matrix, *init_row, *init_col, 0.0);
-            all_paths.append(&mut path);
+            if paths.len() == limit {
+                break;
+            }
+            paths.extend(self.matrices.backtracking(
+                *init_row,
+                *init_col,
+                BackTrack::D(self.global_maximum),
+                0.0,
+                self.tie_break,
+                limit - paths.len(),
+            ));
         }
 
-        let longest_path = all_paths
-            .into_iter()
-            .reduce(|acc, e| if acc.len() > e.len() { acc } else { e })
-            .unwrap();
+        // Without any positive score, the best local alignment is the empty one.
+        if paths.is_empty() {
+            paths.push(vec![[0, 0]]);
+        }
+        (paths, self.global_maximum.max(0.0))
+    }
 
-        let alignments: Vec<AlignmentSequence<A>> = vec![AlignmentSequence::new(
-            longest_path,
-            self.sequence_left.as_ref(),
-            self.sequence_top.as_ref(),
-        )];
-        alignments
+    // Waterman & Eggert (1987) "A new algorithm for best subsequence alignments with
+    // application to tRNA-rRNA comparisons". Takes the best local alignment, forbids its
+    // aligned pairs and recomputes the cells that depended on them, then repeats. Returns
+    // each hit path, its band edge flag and score.
+    fn waterman_eggert(
+        &mut self,
+        hits: usize,
+        min_score: f32,
+    ) -> Vec<(Vec<[usize; 2]>, bool, f32)> {
+        self.initialize();
+        self.solve_subproblems();
+        let limit = match self.tie_break {
+            TieBreak::Longest => usize::MAX,
+            _ => 1,
+        };
+        let mut forbidden: HashSet<[usize; 2]> = HashSet::new();
+        let mut found: Vec<(Vec<[usize; 2]>, bool, f32)> = Vec::new();
+
+        while found.len() < hits {
+            let Some(([end_row, end_col], score)) = self.best_cell() else {
+                break;
+            };
+            if score < min_score {
+                break;
+            }
+            let paths = self
+                .matrices
+                .backtracking(
+                    end_row,
+                    end_col,
+                    BackTrack::D(score),
+                    0.0,
+                    self.tie_break,
+                    limit,
+                )
+                .into_iter()
+                .map(|path| (path, false))
+                .collect();
+            let (path, _) = select_paths(paths, self.tie_break, 1).remove(0);
+
+            // The aligned pairs are the cells reached with a diagonal move.
+            forbidden.extend(
+                path.windows(2)
+                    .filter(|pair| pair[0] == [pair[1][0] + 1, pair[1][1] + 1])
+                    .map(|pair| pair[0]),
+            );
+            let [start_row, start_col] = *path.last().unwrap();
+            let touches_band_edge =
+                banded::touches_edge(&path, self.matrices.band(), self.matrices.dim());
+            found.push((path, touches_band_edge, score));
+            self.declump(&forbidden, [start_row + 1, start_col + 1], end_row);
+        }
+        found
+    }
+
+    // The first cell, in row-major order, with the best positive match/mismatch score.
+    fn best_cell(&self) -> Option<([usize; 2], f32)> {
+        let [rows, _] = self.matrices.dim();
+        let mut best: Option<([usize; 2], f32)> = None;
+        for i in 1..rows {
+            for j in self.matrices.row_range(i).filter(|j| *j > 0) {
+                let [_, diagonal, _] = self.matrices.scores(i, j);
+                if diagonal > best.map_or(0.0, |(_, score)| score) {
+                    best = Some(([i, j], diagonal));
+                }
+            }
+        }
+        best
+    }
+
+    // Recomputes the cells below and to the right of the first aligned pair of the last
+    // hit, without the forbidden pairs. Past the last row of the hit, a row without changes
+    // leaves the next ones unchanged too, so it stops there.
+    fn declump(
+        &mut self,
+        forbidden: &HashSet<[usize; 2]>,
+        [first_row, first_col]: [usize; 2],
+        last_row: usize,
+    ) {
+        let [rows, _] = self.matrices.dim();
+        for i in first_row..rows {
+            let mut changed = false;
+            for j in self.matrices.row_range(i).filter(|j| *j >= first_col) {
+                let diagonal = if forbidden.contains(&[i, j]) {
+                    BackTrack::Empty
+                } else {
+                    Self::diagonal_score(
+                        self.sequence_left.as_ref(),
+                        self.sequence_top.as_ref(),
+                        self.scoring_schema.as_ref(),
+                        &self.matrices,
+                        i,
+                        j,
+                        0.0,
+                    )
+                };
+                let top = Self::top_score(
+                    self.sequence_left.as_ref(),
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
+                    i,
+                    j,
+                );
+                let left = Self::left_score(
+                    self.sequence_top.as_ref(),
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
+                    i,
+                    j,
+                );
+
+                changed |= self.matrices.scores(i, j)
+                    != [top.score(), diagonal.score(), left.score()];
+                self.matrices.set(i, j, [top, diagonal, left]);
+            }
+            if !changed && i > last_row {
+                break;
+            }
+        }
     }
 
     fn initialize(&mut self) {
-        self.matrix[[0, 0]] = BackTrack::D(0.0);
-        let [rows, cols] = self.matrix.dim();
+        self.global_maximum = f32::NEG_INFINITY;
+        self.maximum_indices.clear();
+        let [rows, cols] = self.matrices.dim();
 
-        for i in 1..rows {
-            self.matrix[[i, 0]] = BackTrack::T(0.0);
+        // Any alignment can start at the first row or column, but not with a gap. Banded
+        // matrices only keep the border cells inside the band.
+        for i in 0..rows {
+            if !self.matrices.in_band(i, 0) {
+                continue;
+            }
+            self.matrices.set(
+                i,
+                0,
+                [BackTrack::Empty, BackTrack::D(0.0), BackTrack::Empty],
+            );
         }
 
         for j in 1..cols {
-            self.matrix[[0, j]] = BackTrack::L(0.0);
+            if !self.matrices.in_band(0, j) {
+                continue;
+            }
+            self.matrices.set(
+                0,
+                j,
+                [BackTrack::Empty, BackTrack::D(0.0), BackTrack::Empty],
+            );
         }
     }
 
     fn solve_subproblems(&mut self) {
-        let [rows, cols] = self.matrix.dim();
+        let [rows, _] = self.matrices.dim();
         for i in 1..rows {
-            for j in 1..cols {
+            for j in self.matrices.row_range(i).filter(|j| *j > 0) {
                 let diagonal = Self::diagonal_score(
                     self.sequence_left.as_ref(),
                     self.sequence_top.as_ref(),
-                    &self.scoring_schema,
-                    &self.matrix,
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
+                    i,
+                    j,
+                    0.0,
+                );
+                let top = Self::top_score(
+                    self.sequence_left.as_ref(),
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
+                    i,
+                    j,
+                );
+                let left = Self::left_score(
+                    self.sequence_top.as_ref(),
+                    self.scoring_schema.as_ref(),
+                    &self.matrices,
                     i,
                     j,
                 );
-                let top = Self::top_score(&self.scoring_schema, &self.matrix, i, j);
-                let left = Self::left_score(&self.scoring_schema, &self.matrix, i, j);
 
-                let (backtrack, current_maximum) =
-                    BackTrack::make_backtrack_similarity(top, diagonal, left);
-                self.update_maximum_entries(current_maximum, i, j);
-                self.matrix[[i, j]] = backtrack;
+                self.update_maximum_entries(diagonal.score(), i, j);
+                self.matrices.set(i, j, [top, diagonal, left]);
             }
         }
     }
 
     fn update_maximum_entries(&mut self, current_maximum: f32, i: usize, j: usize) {
+        // Cells without a positive score cannot end a local alignment.
+        if current_maximum <= 0.0 {
+            return;
+        }
         // This comparisons may need to be improved because similarity is calculated with subtractions
         // and they are ill-conditioned.
         if current_maximum > self.global_maximum {
@@ -110,41 +384,138 @@ pub fn new(
         sequence_top: impl HasSequence<Aac> + 'static,
         score_kind: AaScoringKind,
         penalty_kind: PenaltyKind,
-    ) -> Self {
+    ) -> Result<Self, InputError> {
         // Implementation only valid for linear and affine
         #[allow(unreachable_patterns)]
         match penalty_kind {
             PenaltyKind::Affine(_, _) => (),
             PenaltyKind::Linear(_) => (),
-            _ => panic!("Only allowed for Affine and Linear gap models."),
+            _ => return Err(InputError::new(InputErrorKind::GapModelNotExist, "")),
         }
-        let scoring_schema = Box::new(AaScoringSchema::new(score_kind, penalty_kind));
-        let rows = 1 + sequence_left.seq().len();
-        let cols = 1 + sequence_top.seq().len();
+        let statistics = KarlinAltschul::gapped(&score_kind, penalty_kind);
+        let scoring_schema = Box::new(AaScoringSchema::new(score_kind, penalty_kind)?);
+        let mut aligner =
+            Self::with_scoring_schema(sequence_left, sequence_top, scoring_schema);
+        aligner.statistics = statistics;
+        Ok(aligner)
+    }
+}
 
+impl<A> SmithWaterman<A>
+where
+    A: AlignmentUnit,
+{
+    /// Creates the aligner from an already built scoring schema. Allows to align any kind of
+    /// sequence units.
+    pub fn with_scoring_schema(
+        sequence_left: impl HasSequence<A> + 'static,
+        sequence_top: impl HasSequence<A> + 'static,
+        scoring_schema: Box<dyn ScoringSchema<A>>,
+    ) -> Self {
+        // The matrices are allocated when running, only if they fit in the memory budget.
         Self {
             sequence_left: Box::new(sequence_left),
             sequence_top: Box::new(sequence_top),
-            scoring_schema: scoring_schema as Box<dyn ScoringSchema<Aac>>,
-            matrix: Matrix::full(BackTrack::Empty, rows, cols),
+            scoring_schema,
+            matrices: AffineMatrices::new(0, 0),
             global_maximum: f32::NEG_INFINITY,
             maximum_indices: Vec::new(),
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            band: None,
+            co_optimal_limit: 1,
+            tie_break: TieBreak::default(),
+            suboptimal: None,
+            statistics: None,
+            search_space: None,
         }
     }
+
+    /// Turns the aligner into a Waterman-Eggert one: run returns up to `hits` local
+    /// alignments that share no aligned pair, from the best to the worst, each one with its
+    /// own score and coordinates. Only the ones scoring at least `min_score` are returned,
+    /// so the result may be empty. Useful to find internal repeats and multiple copies of a
+    /// domain. Each hit is chosen as set with Aligner::set_tie_break, and the co-optimal
+    /// limit is ignored. Finding the hits needs the traceback matrices, so the aligner keeps
+    /// them whatever the memory budget, see Aligner::set_memory_budget.
+    pub fn with_suboptimal(mut self, hits: usize, min_score: f32) -> Self {
+        self.suboptimal = Some((hits, min_score));
+        self
+    }
 }
 
 impl<A> Aligner<A> for SmithWaterman<A>
 where
     A: AlignmentUnit,
 {
-    fn run(&mut self) -> Vec<AlignmentSequence<A>> {
+    fn run(&mut self) -> Vec<AlignmentResult<A>> {
         self.run()
     }
+
+    fn set_memory_budget(&mut self, bytes: usize) {
+        self.memory_budget = bytes;
+    }
+
+    fn set_band(&mut self, band: Band) {
+        self.band = Some(band);
+    }
+
+    fn set_co_optimal_limit(&mut self, limit: usize) {
+        self.co_optimal_limit = limit;
+    }
+
+    fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+
+    fn set_statistics(&mut self, statistics: KarlinAltschul) {
+        self.statistics = Some(statistics);
+    }
+
+    fn set_search_space(&mut self, search_space: f64) {
+        self.search_space = Some(search_space);
+    }
+
+    fn score(&self) -> AlignmentScore {
+        self.score()
+    }
+
+    fn shuffle_significance(
+        &self,
+        shuffles: usize,
+        shuffle: Shuffle,
+        seed: u64,
+    ) -> ShuffleSignificance {
+        let (score, _) = linear_space::local_score(
+            self.sequence_left.seq(),
+            self.sequence_top.seq(),
+            self.scoring_schema.as_ref(),
+            None,
+        );
+        shuffle::significance(
+            self.sequence_top.seq(),
+            score,
+            shuffles,
+            shuffle,
+            seed,
+            |shuffled| {
+                linear_space::local_score(
+                    self.sequence_left.seq(),
+                    shuffled,
+                    self.scoring_schema.as_ref(),
+                    None,
+                )
+                .0
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::bioseq::Protein;
+    use crate::tests::brute_force::{
+        alignment_score, best_local_score, TestRng, AMINOACIDS,
+    };
 
     use super::*;
 
@@ -165,7 +536,8 @@ fn sw_blosum62_affine() {
             sequence_top,
             AaScoringKind::Blosum62,
             PenaltyKind::Affine(10.0, 1.0),
-        );
+        )
+        .unwrap();
 
         let alignments = sw.run();
 
@@ -214,18 +586,18 @@ fn sw_blosum62_affine() {
             [Some(Aac::A), Some(Aac::E)],
             [Some(Aac::L), Some(Aac::N)],
             [Some(Aac::R), Some(Aac::R)],
-            [Some(Aac::D), Some(Aac::K)],
-            [Some(Aac::E), Some(Aac::W)],
-            [Some(Aac::M), Some(Aac::M)],
-            [Some(Aac::R), Some(Aac::R)],
+            [None, Some(Aac::K)],
+            [None, Some(Aac::W)],
+            [None, Some(Aac::M)],
+            [None, Some(Aac::R)],
             [None, Some(Aac::E)],
             [None, Some(Aac::N)],
             [None, Some(Aac::V)],
             [None, Some(Aac::P)],
-            [None, Some(Aac::E)],
-            [None, Some(Aac::D)],
-            [None, Some(Aac::S)],
-            [None, Some(Aac::R)],
+            [Some(Aac::D), Some(Aac::E)],
+            [Some(Aac::E), Some(Aac::D)],
+            [Some(Aac::M), Some(Aac::S)],
+            [Some(Aac::R), Some(Aac::R)],
             [Some(Aac::A), Some(Aac::P)],
             [Some(Aac::L), Some(Aac::S)],
             [Some(Aac::A), Some(Aac::T)],
@@ -272,6 +644,16 @@ fn sw_blosum62_affine() {
 
         let actual_alignment = alignments[0].read();
 
+        // The former single matrix approximation found a suboptimal alignment (134).
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, PenaltyKind::Affine(10.0, 1.0))
+                .unwrap();
+        assert_eq!(136.0, alignment_score(actual_alignment, &scoring_schema));
+        assert_eq!(136.0, alignments[0].score());
+        assert_eq!([4, 3], alignments[0].begin());
+        assert_eq!([93, 98], alignments[0].end());
+        assert_eq!(expected_alignment.len(), actual_alignment.len());
+
         for p in 0..expected_alignment.len() {
             assert!(
                 expected_alignment[p][0] == actual_alignment[p][0]
@@ -317,7 +699,8 @@ fn sw_blosum62_affine_long() {
             sequence_top,
             AaScoringKind::Blosum45,
             PenaltyKind::Affine(10.0, 1.0),
-        );
+        )
+        .unwrap();
 
         // let actual_alignment = alignments[0].read();
         let output = sw
@@ -326,4 +709,281 @@ fn sw_blosum62_affine_long() {
             .fold(String::new(), |acc, e| acc + &format!("{}", e));
         println!("{}", output)
     }
+
+    #[test]
+    fn sw_is_optimal() {
+        let penalties = [
+            PenaltyKind::Affine(10.0, 1.0),
+            PenaltyKind::Affine(3.0, 0.5),
+            PenaltyKind::Affine(1.0, 2.0),
+            PenaltyKind::Affine(1.0, 0.5),
+            PenaltyKind::Affine(5.0, 0.0),
+            PenaltyKind::Linear(1.0),
+        ];
+        let mut rng = TestRng::new(11);
+        for _ in 0..40 {
+            for penalty_kind in penalties {
+                let left_string = rng.sequence(AMINOACIDS, 6);
+                let top_string = rng.homolog(AMINOACIDS, &left_string);
+                let mut aligner = SmithWaterman::new(
+                    Protein::new(&left_string).unwrap(),
+                    Protein::new(&top_string).unwrap(),
+                    AaScoringKind::Blosum62,
+                    penalty_kind,
+                )
+                .unwrap();
+                let alignment = aligner.run().remove(0);
+
+                let scoring_schema =
+                    AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+                let expected = best_local_score(
+                    Protein::new(&left_string).unwrap().seq(),
+                    Protein::new(&top_string).unwrap().seq(),
+                    &scoring_schema,
+                );
+                let actual = alignment_score(alignment.read(), &scoring_schema);
+                assert_eq!(
+                    expected, actual,
+                    "Suboptimal alignment of {left_string} and {top_string}:\n{alignment}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn banded_sw_adaptive() {
+        // The domain is 40 residues away from the main diagonal.
+        let domain = "MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ";
+        let protein = format!("{}{}GGSWWPL", "DEKRSTNQ".repeat(5), domain);
+        let aligner = |band: Band| {
+            let mut aligner = SmithWaterman::new(
+                Protein::new(domain).unwrap(),
+                Protein::new(&protein).unwrap(),
+                AaScoringKind::Blosum62,
+                PenaltyKind::Affine(10.0, 1.0),
+            )
+            .unwrap();
+            aligner.set_band(band);
+            aligner.run().remove(0)
+        };
+
+        let adaptive = aligner(Band::Adaptive(3));
+        assert_eq!([1, 41], adaptive.begin());
+        assert_eq!([33, 73], adaptive.end());
+        assert_eq!(33, adaptive.identities());
+        assert!(!adaptive.touches_band_edge());
+        let seeded = aligner(Band::Seeded {
+            diagonal: 40,
+            width: 0,
+        });
+        assert_eq!(adaptive.score(), seeded.score());
+
+        let fixed = aligner(Band::Fixed(3));
+        assert!(fixed.score() < adaptive.score());
+    }
+
+    #[test]
+    fn co_optimal_local_alignments() {
+        // Two copies of the same domain.
+        let mut aligner = SmithWaterman::new(
+            Protein::new("MKTAYIAKQR").unwrap(),
+            Protein::new("GSMKTAYIAKQRPPPPMKTAYIAKQRGS").unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(10.0, 1.0),
+        )
+        .unwrap();
+        assert_eq!(1, aligner.run().len());
+
+        aligner.set_co_optimal_limit(3);
+        aligner.set_tie_break(TieBreak::PreferDiagonal);
+        let alignments = aligner.run();
+        let coordinates: Vec<[[usize; 2]; 2]> = alignments
+            .iter()
+            .map(|alignment| [alignment.begin(), alignment.end()])
+            .collect();
+        assert_eq!(vec![[[1, 3], [10, 12]], [[1, 17], [10, 26]]], coordinates);
+        assert_eq!(alignments[0].score(), alignments[1].score());
+    }
+
+    #[test]
+    fn waterman_eggert_domain_copies() {
+        let domain = "MKTAYIAKQRQISFVKSHFSRQ";
+        let protein = format!("GS{domain}PPPPGGMKTAYIAKQRHISFVKSNFSRQW");
+        let hits = SmithWaterman::new(
+            Protein::new(domain).unwrap(),
+            Protein::new(&protein).unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(10.0, 1.0),
+        )
+        .unwrap()
+        .with_suboptimal(5, 40.0)
+        .run();
+        let coordinates: Vec<[[usize; 2]; 2]> =
+            hits.iter().map(|hit| [hit.begin(), hit.end()]).collect();
+        assert_eq!(vec![[[1, 3], [22, 24]], [[1, 31], [22, 52]]], coordinates);
+        assert_eq!(22, hits[0].identities());
+        assert_eq!(20, hits[1].identities());
+        assert!(hits[0].score() > hits[1].score());
+    }
+
+    #[test]
+    fn waterman_eggert_ignores_memory_budget() {
+        let domain = "MKTAYIAKQRQISFVKSHFSRQ";
+        let protein = format!("GS{domain}PPPPGGMKTAYIAKQRHISFVKSNFSRQW");
+        let aligner = || {
+            SmithWaterman::new(
+                Protein::new(domain).unwrap(),
+                Protein::new(&protein).unwrap(),
+                AaScoringKind::Blosum62,
+                PenaltyKind::Affine(10.0, 1.0),
+            )
+            .unwrap()
+            .with_suboptimal(5, 40.0)
+        };
+        let hits = aligner().run();
+        let mut small_budget = aligner();
+        small_budget.set_memory_budget(0);
+        let small_budget_hits = small_budget.run();
+        assert_eq!(2, small_budget_hits.len());
+        for (hit, small_budget_hit) in hits.iter().zip(&small_budget_hits) {
+            assert_eq!(hit.score(), small_budget_hit.score());
+            assert_eq!(hit.begin(), small_budget_hit.begin());
+            assert_eq!(hit.end(), small_budget_hit.end());
+        }
+    }
+
+    #[test]
+    fn waterman_eggert_internal_repeats() {
+        let repeat = "WCHKMYWP";
+        let sequence = format!("{repeat}GSGS{repeat}");
+        let hits = SmithWaterman::new(
+            Protein::new(&sequence).unwrap(),
+            Protein::new(&sequence).unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(10.0, 1.0),
+        )
+        .unwrap()
+        .with_suboptimal(3, 20.0)
+        .run();
+        let coordinates: Vec<[[usize; 2]; 2]> =
+            hits.iter().map(|hit| [hit.begin(), hit.end()]).collect();
+        // The identity, then the repeat at both sides of the main diagonal.
+        assert_eq!(
+            vec![[[1, 1], [20, 20]], [[1, 13], [8, 20]], [[13, 1], [20, 8]]],
+            coordinates
+        );
+        assert_eq!(hits[1].score(), hits[2].score());
+    }
+
+    #[test]
+    fn waterman_eggert_hits_do_not_intersect() {
+        let mut rng = TestRng::new(13);
+        let penalty_kind = PenaltyKind::Affine(5.0, 2.0);
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, penalty_kind).unwrap();
+        for _ in 0..20 {
+            let left_string = rng.sequence(AMINOACIDS, 40);
+            let top_string = rng.homolog(AMINOACIDS, &left_string);
+            let mut aligner = SmithWaterman::new(
+                Protein::new(&left_string).unwrap(),
+                Protein::new(&top_string).unwrap(),
+                AaScoringKind::Blosum62,
+                penalty_kind,
+            )
+            .unwrap()
+            .with_suboptimal(10, 1.0);
+            aligner.set_tie_break(TieBreak::PreferDiagonal);
+            let hits = aligner.run();
+            assert_eq!(aligner.score().score(), hits[0].score());
+
+            let mut pairs = HashSet::new();
+            for hit in &hits {
+                assert_eq!(hit.score(), alignment_score(hit.read(), &scoring_schema));
+                assert!(hit.score() >= 1.0);
+                let [mut i, mut j] = hit.begin();
+                for pair in hit.read() {
+                    if let [Some(_), Some(_)] = pair {
+                        assert!(pairs.insert([i, j]), "{left_string} and {top_string}");
+                    }
+                    i += pair[0].is_some() as usize;
+                    j += pair[1].is_some() as usize;
+                }
+            }
+            assert!(hits.windows(2).all(|w| w[0].score() >= w[1].score()));
+        }
+    }
+
+    #[test]
+    fn local_alignment_significance() {
+        let domain = "MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ";
+        let protein = format!("{}{}GGSWWPL", "DEKRSTNQ".repeat(5), domain);
+        let alignment = SmithWaterman::new(
+            Protein::new(domain).unwrap(),
+            Protein::new(&protein).unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+        )
+        .unwrap()
+        .run()
+        .remove(0);
+        let statistics = KarlinAltschul::new(0.267, 0.041, 0.14);
+        let search_space = (domain.len() * protein.len()) as f64;
+        assert_eq!(
+            Some(statistics.bit_score(alignment.score())),
+            alignment.bit_score()
+        );
+        assert_eq!(
+            Some(statistics.e_value(alignment.score(), search_space)),
+            alignment.e_value()
+        );
+        assert!(alignment.e_value().unwrap() < 1e-10);
+
+        // Without tabulated parameters there are no statistics.
+        let alignment = SmithWaterman::new(
+            Protein::new(domain).unwrap(),
+            Protein::new(&protein).unwrap(),
+            AaScoringKind::Pam160,
+            PenaltyKind::Affine(11.0, 1.0),
+        )
+        .unwrap()
+        .run()
+        .remove(0);
+        assert_eq!(None, alignment.bit_score());
+    }
+
+    #[test]
+    fn local_alignment_given_significance() {
+        let domain = "MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ";
+        let protein = format!("{}{}GGSWWPL", "DEKRSTNQ".repeat(5), domain);
+        let statistics = KarlinAltschul::new(0.3, 0.1, 0.2);
+        let aligner = || {
+            let mut aligner = SmithWaterman::new(
+                Protein::new(domain).unwrap(),
+                Protein::new(&protein).unwrap(),
+                AaScoringKind::Pam160,
+                PenaltyKind::Affine(11.0, 1.0),
+            )
+            .unwrap();
+            aligner.set_statistics(statistics);
+            aligner
+        };
+        let alignment = aligner().run().remove(0);
+        let pairs = (domain.len() * protein.len()) as f64;
+        assert_eq!(
+            Some(statistics.bit_score(alignment.score())),
+            alignment.bit_score()
+        );
+        assert_eq!(
+            Some(statistics.e_value(alignment.score(), pairs)),
+            alignment.e_value()
+        );
+
+        // A database one thousand times longer makes the hit a thousand times more likely.
+        let mut database = aligner();
+        database.set_search_space(pairs * 1000.0);
+        let database = database.run().remove(0);
+        assert_eq!(alignment.bit_score(), database.bit_score());
+        let ratio = database.e_value().unwrap() / alignment.e_value().unwrap();
+        assert!((ratio - 1000.0).abs() < 1e-6);
+    }
 }
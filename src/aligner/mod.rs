@@ -1,50 +1,288 @@
 //! Alignment algorithms
 
 use crate::{
-    bioseq::{Aac, HasSequence},
-    scoring_schema::{aminoacid_schema::AaScoringKind, gap_penalty::PenaltyKind},
+    bioseq::{Aac, HasSequence, Nuc},
+    scoring_schema::{
+        aminoacid_schema::AaScoringKind,
+        gap_penalty::PenaltyKind,
+        nucleotide_schema::NucScoringKind,
+        profile::{Profile, ProfileSequence, ProfileUnit},
+        NucScoringSchema,
+    },
+    statistics::KarlinAltschul,
     utils::AlignmentUnit,
+    InputError,
 };
 
 use self::{
-    global_alignment::NeedlemanWunsch, local_alignment::SmithWaterman,
-    utils::AlignmentSequence,
+    global_alignment::NeedlemanWunsch,
+    local_alignment::SmithWaterman,
+    utils::{AlignmentResult, AlignmentScore, ShuffleSignificance},
 };
 
+mod banded;
+pub mod distance;
 mod global_alignment;
+mod linear_space;
 mod local_alignment;
+pub mod progressive;
+pub mod search;
+mod shuffle;
 pub mod utils;
 
+/// Default memory budget of the aligners, in bytes.
+pub const DEFAULT_MEMORY_BUDGET: usize = 256 * 1024 * 1024;
+
 /// Flag for alignment algorithm implementations
 pub trait Aligner<A: AlignmentUnit> {
-    fn run(&mut self) -> Vec<AlignmentSequence<A>>;
+    fn run(&mut self) -> Vec<AlignmentResult<A>>;
+
+    /// Sets the memory, in bytes, the traceback matrices may take. When their estimated
+    /// size exceeds it, the aligner switches to a linear-space algorithm that finds an
+    /// alignment with the same optimal score, taking about twice the time. When several
+    /// alignments share that score, both algorithms may pick different ones. A budget of 0
+    /// always uses the linear-space algorithm. The default is DEFAULT_MEMORY_BUDGET.
+    /// Scoring schemas with position-specific gap costs, as profiles, and Waterman-Eggert
+    /// aligners always keep the matrices.
+    fn set_memory_budget(&mut self, bytes: usize);
+
+    /// Restricts the alignment to a band of diagonals. Only the cells inside the band are
+    /// computed and stored, see Band. The result tells whether the alignment touched the
+    /// band edge, see AlignmentResult::touches_band_edge.
+    fn set_band(&mut self, band: Band);
+
+    /// Sets how many co-optimal alignments, those sharing the optimal score, run returns
+    /// at most. They are distinct and ordered as set with set_tie_break. The default is 1.
+    /// The linear-space algorithm only finds one alignment, see set_memory_budget.
+    fn set_co_optimal_limit(&mut self, limit: usize);
+
+    /// Sets the policy that chooses and orders the co-optimal alignments, see TieBreak.
+    fn set_tie_break(&mut self, tie_break: TieBreak);
+
+    /// Sets the Karlin-Altschul parameters of the scoring schema, so the local alignments
+    /// report their bit score and E-value, see AlignmentResult::e_value. Useful for custom
+    /// matrices or gap costs that NCBI does not tabulate. Global alignments ignore it.
+    fn set_statistics(&mut self, statistics: KarlinAltschul);
+
+    /// Sets the search space of the E-values. By default it is the product of both
+    /// sequence lengths; a database search would take the query length times the database
+    /// length. Global alignments ignore it.
+    fn set_search_space(&mut self, search_space: f64);
+
+    /// Optimal score without building the alignment. Keeps only two rows of scores of the
+    /// shorter sequence length instead of the traceback matrices, so it is the cheap choice
+    /// for screening many pairs. Honors the band set with set_band.
+    fn score(&self) -> AlignmentScore;
+
+    /// Empirical significance of the optimal score. Aligns `shuffles` shuffled versions of
+    /// the top (second) sequence, drawn from `seed`, and compares the optimal score with
+    /// theirs. Useful where no analytic statistics apply, as for global alignments. The
+    /// scores are computed as in score, but ignoring the band.
+    fn shuffle_significance(
+        &self,
+        shuffles: usize,
+        shuffle: Shuffle,
+        seed: u64,
+    ) -> ShuffleSignificance;
 }
 
 pub enum AlignerKind {
     NeedlemanWunsch,
     SmithWaterman,
+    /// Needleman-Wunsch whose terminal gaps are free as set in EndGaps.
+    SemiGlobal(EndGaps),
+    /// Smith-Waterman returning up to `hits` non-intersecting local alignments scoring at
+    /// least `min_score`, from the best one. See Waterman & Eggert (1987).
+    WatermanEggert {
+        hits: usize,
+        min_score: f32,
+    },
+}
+
+/// Terminal gaps that are not penalised in a semi-global (ends-free) alignment. The left
+/// sequence is the first one passed to the aligner constructors, the top sequence is the
+/// second one.
+///
+/// A leading gap at a sequence is a gap before its first residue; a trailing gap is a gap
+/// after its last residue. For example, aligning a domain (left) against a full-length
+/// protein (top) uses free leading and trailing gaps at left, and finding a suffix of left
+/// that overlaps a prefix of top uses free leading gaps at top and trailing gaps at left.
+/// Setting all the flags to false gives the global alignment.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct EndGaps {
+    pub leading_left: bool,
+    pub trailing_left: bool,
+    pub leading_top: bool,
+    pub trailing_top: bool,
+}
+
+impl EndGaps {
+    /// All the terminal gaps are free.
+    pub fn all() -> Self {
+        Self {
+            leading_left: true,
+            trailing_left: true,
+            leading_top: true,
+            trailing_top: true,
+        }
+    }
+}
+
+/// Band of diagonals for banded alignment. The cell [i, j] is on the diagonal j - i; the
+/// band keeps the diagonals at most `width` away from a centre one, so filling the
+/// matrices takes time and memory proportional to n * (2 * width + 1) instead of n * m.
+/// Sequences that only differ by a few indels have their optimal alignment inside a narrow
+/// band.
+///
+/// Global alignments widen the band to include its start and end cells.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Band {
+    /// The band is centred on the main diagonal, 0.
+    Fixed(usize),
+    /// The band is centred on the diagonal with more words (k-mers) shared by both
+    /// sequences, or on the main diagonal if they share none.
+    Adaptive(usize),
+    /// The band is centred on a known seed diagonal, for example from a database hit.
+    Seeded { diagonal: isize, width: usize },
+}
+
+/// Policy to choose among co-optimal alignments. Except Longest, the policies follow the
+/// traceback from the end cell and, where several moves reach the optimal score, take them
+/// in the given order of preference. Then the first alignment is always the same and the
+/// next ones come in a reproducible order, without exploring every path.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum TieBreak {
+    /// The alignments with more columns first. It explores every co-optimal path, so it
+    /// may be slow when there are many of them. Equally long ones are ordered as in
+    /// PreferDiagonal.
+    #[default]
+    Longest,
+    /// Match/mismatch first, then a gap at the top sequence, then a gap at the left one.
+    PreferDiagonal,
+    /// Gap at the left sequence first, then match/mismatch, then a gap at the top one.
+    PreferGapLeft,
+    /// Gap at the top sequence first, then match/mismatch, then a gap at the left one.
+    PreferGapTop,
+}
+
+impl TieBreak {
+    /// The [top, diagonal, left] state indicators in order of preference.
+    fn state_order(self) -> [u8; 3] {
+        match self {
+            TieBreak::Longest | TieBreak::PreferDiagonal => [0b010, 0b001, 0b100],
+            TieBreak::PreferGapLeft => [0b100, 0b010, 0b001],
+            TieBreak::PreferGapTop => [0b001, 0b010, 0b100],
+        }
+    }
+}
+
+/// How the sequence is shuffled to estimate the significance of a score.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Shuffle {
+    /// Random permutation of the units. Keeps the composition of the sequence.
+    Permutation,
+    /// Keeps the count of every pair of adjacent units (dinucleotides for DNA and RNA,
+    /// dipeptides for proteins), and so the composition too. Shuffled sequences keep the
+    /// local biases of the original one, like CpG depletion or low complexity regions.
+    Dinucleotide,
 }
 
-/// Aligner constructor
+/// Aligner constructor. Returns InputError if the gap penalty parameters are not valid.
 pub fn aminoacid_align_builder(
     kind: AlignerKind,
     sequence_1: impl HasSequence<Aac> + 'static,
     sequence_2: impl HasSequence<Aac> + 'static,
     score_kind: AaScoringKind,
     penalty_kind: PenaltyKind,
-) -> Box<dyn Aligner<Aac>> {
-    match kind {
+) -> Result<Box<dyn Aligner<Aac>>, InputError> {
+    Ok(match kind {
         AlignerKind::NeedlemanWunsch => Box::new(NeedlemanWunsch::new(
             sequence_1,
             sequence_2,
             score_kind,
             penalty_kind,
-        )),
+        )?),
         AlignerKind::SmithWaterman => Box::new(SmithWaterman::new(
             sequence_1,
             sequence_2,
             score_kind,
             penalty_kind,
+        )?),
+        AlignerKind::SemiGlobal(end_gaps) => Box::new(
+            NeedlemanWunsch::new(sequence_1, sequence_2, score_kind, penalty_kind)?
+                .with_end_gaps(end_gaps),
+        ),
+        AlignerKind::WatermanEggert { hits, min_score } => Box::new(
+            SmithWaterman::new(sequence_1, sequence_2, score_kind, penalty_kind)?
+                .with_suboptimal(hits, min_score),
+        ),
+    })
+}
+
+/// Aligner constructor for DNA and RNA sequences. Returns InputError if the gap penalty
+/// parameters are not valid.
+pub fn nucleotide_align_builder(
+    kind: AlignerKind,
+    sequence_1: impl HasSequence<Nuc> + 'static,
+    sequence_2: impl HasSequence<Nuc> + 'static,
+    score_kind: NucScoringKind,
+    penalty_kind: PenaltyKind,
+) -> Result<Box<dyn Aligner<Nuc>>, InputError> {
+    let scoring_schema = Box::new(NucScoringSchema::new(score_kind, penalty_kind)?);
+    Ok(match kind {
+        AlignerKind::NeedlemanWunsch => Box::new(NeedlemanWunsch::with_scoring_schema(
+            sequence_1,
+            sequence_2,
+            scoring_schema,
+        )),
+        AlignerKind::SmithWaterman => Box::new(SmithWaterman::with_scoring_schema(
+            sequence_1,
+            sequence_2,
+            scoring_schema,
+        )),
+        AlignerKind::SemiGlobal(end_gaps) => Box::new(
+            NeedlemanWunsch::with_scoring_schema(sequence_1, sequence_2, scoring_schema)
+                .with_end_gaps(end_gaps),
+        ),
+        AlignerKind::WatermanEggert { hits, min_score } => Box::new(
+            SmithWaterman::with_scoring_schema(sequence_1, sequence_2, scoring_schema)
+                .with_suboptimal(hits, min_score),
+        ),
+    })
+}
+
+/// Aligner of a sequence against a profile, with its position-specific scores and gap
+/// costs. The profile is the left sequence, read as its consensus residues, and the
+/// sequence is the top one.
+pub fn profile_align_builder(
+    kind: AlignerKind,
+    profile: &Profile,
+    sequence: impl HasSequence<Aac>,
+) -> Box<dyn Aligner<ProfileUnit>> {
+    let sequence = ProfileSequence::new(&sequence);
+    let scoring_schema = Box::new(profile.clone());
+    match kind {
+        AlignerKind::NeedlemanWunsch => Box::new(NeedlemanWunsch::with_scoring_schema(
+            profile.clone(),
+            sequence,
+            scoring_schema,
+        )),
+        AlignerKind::SmithWaterman => Box::new(SmithWaterman::with_scoring_schema(
+            profile.clone(),
+            sequence,
+            scoring_schema,
         )),
+        AlignerKind::SemiGlobal(end_gaps) => Box::new(
+            NeedlemanWunsch::with_scoring_schema(
+                profile.clone(),
+                sequence,
+                scoring_schema,
+            )
+            .with_end_gaps(end_gaps),
+        ),
+        AlignerKind::WatermanEggert { hits, min_score } => Box::new(
+            SmithWaterman::with_scoring_schema(profile.clone(), sequence, scoring_schema)
+                .with_suboptimal(hits, min_score),
+        ),
     }
 }
@@ -0,0 +1,486 @@
+//! Progressive multiple sequence alignment.
+//!
+//! Aligns every pair of sequences with Needleman-Wunsch and takes one minus their identity
+//! (identical pairs over aligned pairs) as their distance. A guide tree built from the
+//! distances with UPGMA or neighbor joining, see tree::Tree::build, sets the order of the
+//! merges: from the leaves to the root, each node aligns the alignments of its children one
+//! after another, profile against profile, and keeps the gaps of both. See Feng & Doolittle
+//! (1987) and Thompson, Higgins & Gibson (1994, CLUSTAL W).
+//!
+//! Two columns score the mean substitution score of their pairs of rows, where the pairs
+//! with a gap score 0, rounded to the units of the substitution matrix. A gap facing a
+//! column costs the gap penalty scaled by the fraction of rows with a residue in that
+//! column, so gaps are cheaper where the other alignment already has them.
+
+use super::distance::{AllVersusAll, DistanceKind, DistanceSettings};
+use super::global_alignment::NeedlemanWunsch;
+use super::{Aligner, TieBreak};
+use crate::bioseq::{Aac, HasSequence};
+use crate::scoring_schema::aminoacid_schema::AaScoringKind;
+use crate::scoring_schema::gap_penalty::PenaltyKind;
+use crate::scoring_schema::{AaScoringSchema, ScoringSchema};
+use crate::tree::{Tree, TreeMethod};
+use crate::utils::AlignmentUnit;
+use crate::InputError;
+use std::rc::Rc;
+use std::{error, fmt};
+
+/// Multiple aligner of sequences, see the module documentation.
+pub struct ProgressiveAligner<A>
+where
+    A: AlignmentUnit,
+{
+    scoring_schema: Rc<dyn ScoringSchema<A>>,
+    guide_tree: TreeMethod,
+}
+
+impl ProgressiveAligner<Aac> {
+    /// Multiple aligner of proteins. Returns InputError if the gap penalty parameters are
+    /// not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::aligner::progressive::ProgressiveAligner;
+    /// use pairwasm_alignment::bioseq::Protein;
+    /// use pairwasm_alignment::scoring_schema::aminoacid_schema::AaScoringKind;
+    /// use pairwasm_alignment::scoring_schema::gap_penalty::PenaltyKind;
+    /// use pairwasm_alignment::tree::TreeMethod;
+    ///
+    /// let aligner = ProgressiveAligner::new(
+    ///     AaScoringKind::Blosum62,
+    ///     PenaltyKind::Affine(11.0, 1.0),
+    ///     TreeMethod::NeighborJoining,
+    /// )
+    /// .unwrap();
+    /// let records = [
+    ///     ("seq1", Protein::new("HGEYWAKLV").unwrap()),
+    ///     ("seq2", Protein::new("HGEFWKLV").unwrap()),
+    ///     ("seq3", Protein::new("HAEYWAKIV").unwrap()),
+    /// ];
+    /// let alignment = aligner.align(&records).unwrap();
+    /// assert_eq!(
+    ///     ["HGEYWAKLV", "HGEFW-KLV", "HAEYWAKIV"].to_vec(),
+    ///     alignment.aligned_strings()
+    /// );
+    /// ```
+    pub fn new(
+        score_kind: AaScoringKind,
+        penalty_kind: PenaltyKind,
+        guide_tree: TreeMethod,
+    ) -> Result<Self, InputError> {
+        let scoring_schema = AaScoringSchema::new(score_kind, penalty_kind)?;
+        Ok(Self::with_scoring_schema(
+            Box::new(scoring_schema),
+            guide_tree,
+        ))
+    }
+}
+
+impl<A> ProgressiveAligner<A>
+where
+    A: AlignmentUnit + 'static,
+{
+    pub fn with_scoring_schema(
+        scoring_schema: Box<dyn ScoringSchema<A>>,
+        guide_tree: TreeMethod,
+    ) -> Self {
+        Self {
+            scoring_schema: Rc::from(scoring_schema),
+            guide_tree,
+        }
+    }
+
+    /// Aligns the sequences, each with its identifier. The rows of the result keep the
+    /// order of the records.
+    ///
+    /// Returns MsaError if there are less than two records or a sequence is empty.
+    pub fn align(
+        &self,
+        records: &[(impl AsRef<str>, impl HasSequence<A>)],
+    ) -> Result<MultipleAlignment<A>, MsaError> {
+        if records.len() < 2 {
+            return Err(MsaError::new(MsaErrorKind::NotEnoughSequences, ""));
+        }
+        if let Some((id, _)) = records
+            .iter()
+            .find(|(_, sequence)| sequence.seq().is_empty())
+        {
+            return Err(MsaError::new(MsaErrorKind::EmptySequence, id.as_ref()));
+        }
+
+        let mut groups: Vec<Option<Group<A>>> = records
+            .iter()
+            .enumerate()
+            .map(|(index, (_, sequence))| {
+                Some(Group {
+                    members: vec![index],
+                    rows: vec![sequence.seq().iter().map(|unit| Some(*unit)).collect()],
+                })
+            })
+            .collect();
+
+        let settings = DistanceSettings {
+            global: true,
+            score_only: false,
+            distance: DistanceKind::PDistance,
+        };
+        let distances =
+            AllVersusAll::with_shared_schema(self.scoring_schema.clone(), settings)
+                .distances(records)
+                .expect("The records were checked.");
+        let size = records.len();
+        let tree = Tree::from_distances(&distances, self.guide_tree)
+            .expect("The distance matrix has a row per record.");
+        groups.resize_with(tree.nodes().len(), || None);
+        // The internal nodes follow the leaves, and their children come before them.
+        for (index, node) in tree.nodes().iter().enumerate().skip(size) {
+            let mut children = node.children().iter();
+            let first = children.next().and_then(|child| groups[*child].take());
+            groups[index] = children.fold(first, |group, child| {
+                Some(self.merge(group.as_ref(), groups[*child].take().as_ref()))
+            });
+        }
+
+        let root = groups[tree.root()]
+            .take()
+            .expect("The root merges every sequence.");
+        let mut rows: Vec<Vec<Option<A>>> = vec![Vec::new(); size];
+        for (member, row) in root.members.into_iter().zip(root.rows) {
+            rows[member] = row;
+        }
+        Ok(MultipleAlignment {
+            ids: records
+                .iter()
+                .map(|(id, _)| id.as_ref().to_string())
+                .collect(),
+            rows,
+        })
+    }
+
+    // Profile-profile alignment of two groups. The rows of the result are the ones of left
+    // followed by the ones of top.
+    fn merge(&self, left: Option<&Group<A>>, top: Option<&Group<A>>) -> Group<A> {
+        let (left, top) = (
+            left.expect("Each group merges once."),
+            top.expect("Each group merges once."),
+        );
+        let scoring_schema = ColumnSchema::new(self.scoring_schema.clone(), [left, top]);
+        let [left_columns, top_columns] = [false, true].map(|top| Columns {
+            units: (0..scoring_schema.profiles[top as usize].len())
+                .map(|index| Column { top, index })
+                .collect(),
+        });
+        let mut aligner = NeedlemanWunsch::with_scoring_schema(
+            left_columns,
+            top_columns,
+            Box::new(scoring_schema),
+        );
+        Aligner::set_tie_break(&mut aligner, TieBreak::PreferDiagonal);
+        let alignment = Aligner::run(&mut aligner).remove(0);
+
+        let mut rows: Vec<Vec<Option<A>>> = vec![
+            Vec::with_capacity(alignment.length());
+            left.rows.len() + top.rows.len()
+        ];
+        let (left_rows, top_rows) = rows.split_at_mut(left.rows.len());
+        for [left_column, top_column] in alignment.read() {
+            for (row, source) in left_rows.iter_mut().zip(&left.rows) {
+                row.push(left_column.and_then(|column| source[column.index]));
+            }
+            for (row, source) in top_rows.iter_mut().zip(&top.rows) {
+                row.push(top_column.and_then(|column| source[column.index]));
+            }
+        }
+        Group {
+            members: [left.members.as_slice(), top.members.as_slice()].concat(),
+            rows,
+        }
+    }
+}
+
+/// Aligned sequences, one row per sequence and one unit or gap (None) per column.
+#[cfg_attr(test, derive(Debug))]
+pub struct MultipleAlignment<A>
+where
+    A: AlignmentUnit,
+{
+    ids: Vec<String>,
+    rows: Vec<Vec<Option<A>>>,
+}
+
+impl<A> MultipleAlignment<A>
+where
+    A: AlignmentUnit,
+{
+    /// Identifiers of the sequences, in the order of the rows.
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+
+    pub fn rows(&self) -> &[Vec<Option<A>>] {
+        &self.rows
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+}
+
+// Aligned rows of some of the sequences, given by their indices.
+struct Group<A> {
+    members: Vec<usize>,
+    rows: Vec<Vec<Option<A>>>,
+}
+
+// A column of one of the two groups being merged.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Column {
+    top: bool,
+    index: usize,
+}
+
+impl AlignmentUnit for Column {}
+
+struct Columns {
+    units: Vec<Column>,
+}
+
+impl HasSequence<Column> for Columns {
+    fn seq(&self) -> &Vec<Column> {
+        &self.units
+    }
+}
+
+// The different units of a column with their counts, and the fraction of rows with a unit.
+struct ColumnProfile<A> {
+    units: Vec<(A, f32)>,
+    occupancy: f32,
+}
+
+// Scores the columns of two groups, see the module documentation.
+struct ColumnSchema<A>
+where
+    A: AlignmentUnit,
+{
+    scoring_schema: Rc<dyn ScoringSchema<A>>,
+    // Columns of the [left, top] groups
+    profiles: [Vec<ColumnProfile<A>>; 2],
+    // Number of pairs of rows
+    pairs: f32,
+}
+
+impl<A> ColumnSchema<A>
+where
+    A: AlignmentUnit,
+{
+    fn new(scoring_schema: Rc<dyn ScoringSchema<A>>, groups: [&Group<A>; 2]) -> Self {
+        let profiles = groups.map(|group| {
+            let width = group.rows.first().map_or(0, |row| row.len());
+            (0..width)
+                .map(|column| {
+                    let mut units: Vec<(A, f32)> = Vec::new();
+                    for unit in group.rows.iter().filter_map(|row| row[column]) {
+                        match units.iter_mut().find(|(other, _)| *other == unit) {
+                            Some((_, count)) => *count += 1.0,
+                            None => units.push((unit, 1.0)),
+                        }
+                    }
+                    let residues: f32 = units.iter().map(|(_, count)| count).sum();
+                    ColumnProfile {
+                        units,
+                        occupancy: residues / group.rows.len() as f32,
+                    }
+                })
+                .collect()
+        });
+        Self {
+            scoring_schema,
+            profiles,
+            pairs: (groups[0].rows.len() * groups[1].rows.len()) as f32,
+        }
+    }
+
+    fn profile(&self, column: Column) -> &ColumnProfile<A> {
+        &self.profiles[column.top as usize][column.index]
+    }
+}
+
+impl<A> ScoringSchema<Column> for ColumnSchema<A>
+where
+    A: AlignmentUnit,
+{
+    fn get_score(&self, code_1: Column, code_2: Column) -> i8 {
+        let (left, top) = match code_1.top {
+            false => (code_1, code_2),
+            true => (code_2, code_1),
+        };
+        let mut total = 0.0;
+        for (unit_left, count_left) in &self.profile(left).units {
+            for (unit_top, count_top) in &self.profile(top).units {
+                let score = self.scoring_schema.get_score(*unit_left, *unit_top);
+                total += count_left * count_top * score as f32;
+            }
+        }
+        (total / self.pairs)
+            .round()
+            .clamp(i8::MIN as f32, i8::MAX as f32) as i8
+    }
+
+    fn get_function(&self, length: usize) -> f32 {
+        self.scoring_schema.get_function(length)
+    }
+
+    fn get_open(&self) -> f32 {
+        self.scoring_schema.get_open()
+    }
+
+    fn get_extend(&self) -> f32 {
+        self.scoring_schema.get_extend()
+    }
+
+    fn get_gap_costs(&self, code: Column) -> [f32; 2] {
+        let occupancy = self.profile(code).occupancy;
+        [self.get_open() * occupancy, self.get_extend() * occupancy]
+    }
+
+    fn has_position_specific_gaps(&self) -> bool {
+        true
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of MsaError.
+pub enum MsaErrorKind {
+    EmptySequence,
+    NotEnoughSequences,
+}
+
+#[derive(Debug)]
+/// Error type for multiple sequence alignment.
+pub struct MsaError {
+    kind: MsaErrorKind,
+    message: String,
+}
+
+impl MsaError {
+    fn new(kind: MsaErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            MsaErrorKind::EmptySequence => "The sequences must not be empty.".to_string(),
+            MsaErrorKind::NotEnoughSequences => {
+                "The input must contain at least two sequences.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &MsaErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for MsaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for MsaError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bioseq::Protein;
+
+    fn aligner(guide_tree: TreeMethod) -> ProgressiveAligner<Aac> {
+        ProgressiveAligner::new(
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            guide_tree,
+        )
+        .unwrap()
+    }
+
+    fn records(sequences: &[&str]) -> Vec<(String, Protein)> {
+        sequences
+            .iter()
+            .enumerate()
+            .map(|(index, sequence)| {
+                (format!("seq{}", index + 1), Protein::new(sequence).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn progressive_alignment() {
+        let sequences = [
+            "HGEYWAKLV",
+            "HGEFWKLV",
+            "HAEYWAKIV",
+            "HGDYWRLV",
+            "MHGEYWAKLV",
+        ];
+        for guide_tree in [TreeMethod::Upgma, TreeMethod::NeighborJoining] {
+            let alignment = aligner(guide_tree).align(&records(&sequences)).unwrap();
+            assert_eq!(
+                ["seq1", "seq2", "seq3", "seq4", "seq5"].to_vec(),
+                alignment.ids()
+            );
+            assert_eq!(10, alignment.width());
+            // Removing the gaps gives back the sequences, in their order.
+            for (row, sequence) in alignment.aligned_strings().iter().zip(sequences) {
+                assert_eq!(10, row.len());
+                assert_eq!(sequence, row.replace('-', ""));
+            }
+            assert_eq!(
+                [
+                    "-HGEYWAKLV",
+                    "-HGEFW-KLV",
+                    "-HAEYWAKIV",
+                    "-HGDYW-RLV",
+                    "MHGEYWAKLV"
+                ]
+                .to_vec(),
+                alignment.aligned_strings()
+            );
+        }
+    }
+
+    #[test]
+    fn identical_sequences() {
+        let alignment = aligner(TreeMethod::default())
+            .align(&records(&["MKVLA", "MKVLA", "MKVLA"]))
+            .unwrap();
+        assert!(alignment
+            .rows()
+            .iter()
+            .all(|row| row.iter().all(|unit| unit.is_some())));
+        assert_eq!(5, alignment.width());
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = aligner(TreeMethod::default())
+            .align(&records(&["MKVLA"]))
+            .unwrap_err();
+        assert_eq!(&MsaErrorKind::NotEnoughSequences, error.kind());
+        struct Units(Vec<Aac>);
+        impl HasSequence<Aac> for Units {
+            fn seq(&self) -> &Vec<Aac> {
+                &self.0
+            }
+        }
+        let empty = [
+            ("seq1", Units(vec![Aac::M, Aac::K])),
+            ("seq2", Units(Vec::new())),
+        ];
+        let error = aligner(TreeMethod::default()).align(&empty).unwrap_err();
+        assert_eq!(&MsaErrorKind::EmptySequence, error.kind());
+    }
+}
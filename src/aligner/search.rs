@@ -0,0 +1,599 @@
+//! Database search: one query against every record of a FASTA library.
+//!
+//! The library is read one record at a time, so it never needs to be in memory. Each
+//! record is scored against the query with the score-only Smith-Waterman sweep, reusing
+//! the same rows of scores, and only the best hits found so far are kept. At the end, the
+//! kept hits are aligned with Smith-Waterman to build their alignments.
+//!
+//! With Karlin-Altschul parameters for the substitution matrix and the gap costs, see
+//! KarlinAltschul::gapped, every hit gets a bit score and an E-value. The E-value of a hit
+//! is the expected number of alignments with its score between the query and a random
+//! sequence of the hit length, multiplied by the number of records searched.
+
+use super::distance::Units;
+use super::linear_space::{self, SweepBuffers};
+use super::local_alignment::SmithWaterman;
+use super::utils::AlignmentResult;
+use super::{Aligner, TieBreak};
+use crate::bioseq::{Aac, HasSequence, Protein};
+use crate::fasta::FastaReader;
+use crate::scoring_schema::aminoacid_schema::AaScoringKind;
+use crate::scoring_schema::gap_penalty::PenaltyKind;
+use crate::scoring_schema::{AaScoringSchema, ScoringSchema};
+use crate::statistics::KarlinAltschul;
+use crate::InputError;
+use std::fmt::Write;
+use std::io::BufRead;
+use std::rc::Rc;
+use std::{error, fmt};
+
+/// Order of the hits, and which ones are kept.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum HitRanking {
+    /// Highest score first.
+    #[default]
+    Score,
+    /// Lowest E-value first. As the E-value grows with the length of the record, a short
+    /// record may rank before a longer one with a higher score. Needs Karlin-Altschul
+    /// parameters.
+    EValue,
+}
+
+/// How DatabaseSearch keeps and orders the hits.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SearchSettings {
+    /// Number of hits kept, at least 1.
+    pub max_hits: usize,
+    pub ranking: HitRanking,
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        Self {
+            max_hits: 50,
+            ranking: HitRanking::default(),
+        }
+    }
+}
+
+/// Search of a protein query against a library of proteins, see the module documentation.
+pub struct DatabaseSearch {
+    query: Vec<Aac>,
+    scoring_schema: Rc<dyn ScoringSchema<Aac>>,
+    statistics: Option<KarlinAltschul>,
+    settings: SearchSettings,
+}
+
+impl DatabaseSearch {
+    /// Search of a protein query. Returns InputError if the gap penalty parameters are not
+    /// valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::aligner::search::{DatabaseSearch, SearchSettings};
+    /// use pairwasm_alignment::bioseq::Protein;
+    /// use pairwasm_alignment::scoring_schema::aminoacid_schema::AaScoringKind;
+    /// use pairwasm_alignment::scoring_schema::gap_penalty::PenaltyKind;
+    ///
+    /// let search = DatabaseSearch::new(
+    ///     Protein::new("HGEYWAKLV").unwrap(),
+    ///     AaScoringKind::Blosum62,
+    ///     PenaltyKind::Affine(11.0, 1.0),
+    ///     SearchSettings::default(),
+    /// )
+    /// .unwrap();
+    /// let library = ">far\nPPPPPPPP\n>close\nMKHGEFWKLVR\n>same\nHGEYWAKLV\n";
+    /// let result = search.run(library.as_bytes()).unwrap();
+    /// assert_eq!(3, result.searched_records());
+    /// let ids: Vec<&str> = result.hits().iter().map(|hit| hit.id()).collect();
+    /// assert_eq!(["same", "close"].to_vec(), ids);
+    /// assert!(result.hits()[0].e_value().unwrap() < result.hits()[1].e_value().unwrap());
+    /// ```
+    pub fn new(
+        query: impl HasSequence<Aac>,
+        score_kind: AaScoringKind,
+        penalty_kind: PenaltyKind,
+        settings: SearchSettings,
+    ) -> Result<Self, InputError> {
+        let statistics = KarlinAltschul::gapped(&score_kind, penalty_kind);
+        let scoring_schema = AaScoringSchema::new(score_kind, penalty_kind)?;
+        Ok(Self {
+            query: query.seq().to_vec(),
+            scoring_schema: Rc::new(scoring_schema),
+            statistics,
+            settings,
+        })
+    }
+
+    /// Search with any protein scoring schema. Without Karlin-Altschul parameters the hits
+    /// have no bit score nor E-value, and they can only be ranked by score.
+    pub fn with_scoring_schema(
+        query: impl HasSequence<Aac>,
+        scoring_schema: Box<dyn ScoringSchema<Aac>>,
+        statistics: Option<KarlinAltschul>,
+        settings: SearchSettings,
+    ) -> Self {
+        Self {
+            query: query.seq().to_vec(),
+            scoring_schema: Rc::from(scoring_schema),
+            statistics,
+            settings,
+        }
+    }
+
+    /// Searches every record of a FASTA library, read from a file, the standard input or
+    /// a byte slice. Only the records with a positive local score are hits.
+    ///
+    /// Returns SearchError if the query is empty, the settings are not valid, or a record
+    /// of the library is not a valid protein.
+    pub fn run(&self, library: impl BufRead) -> Result<SearchResult, SearchError> {
+        if self.query.is_empty() {
+            return Err(SearchError::new(SearchErrorKind::EmptyQuery, ""));
+        }
+        if self.settings.max_hits == 0 {
+            return Err(SearchError::new(
+                SearchErrorKind::InvalidSettings,
+                "At least one hit must be kept.",
+            ));
+        }
+        if self.settings.ranking == HitRanking::EValue && self.statistics.is_none() {
+            return Err(SearchError::new(SearchErrorKind::NoStatistics, ""));
+        }
+
+        let max_hits = self.settings.max_hits;
+        let invalid_library = |error: &dyn error::Error| {
+            SearchError::new(SearchErrorKind::InvalidLibrary, &error.to_string())
+        };
+        let mut buffers = SweepBuffers::default();
+        // Best candidates so far, ordered by rank.
+        let mut candidates: Vec<Candidate> = Vec::new();
+        let mut searched_records = 0;
+        let mut searched_residues = 0;
+        for record in FastaReader::new(library) {
+            let record = record.map_err(|error| invalid_library(&error))?;
+            let target = record.protein().map_err(|error| invalid_library(&error))?;
+            searched_records += 1;
+            searched_residues += target.seq().len();
+
+            let (score, _) = linear_space::local_score_with(
+                &mut buffers,
+                &self.query,
+                target.seq(),
+                self.scoring_schema.as_ref(),
+                None,
+            );
+            if score <= 0.0 {
+                continue;
+            }
+            let rank = self.rank(score, target.seq().len());
+            if candidates.len() == max_hits
+                && candidates.last().is_some_and(|last| last.rank <= rank)
+            {
+                continue;
+            }
+            // After the candidates of the same rank, so the earlier records stay first.
+            let position = candidates.partition_point(|candidate| candidate.rank <= rank);
+            candidates.insert(
+                position,
+                Candidate {
+                    id: record.id().to_string(),
+                    description: record.description().to_string(),
+                    target,
+                    rank,
+                },
+            );
+            candidates.truncate(max_hits);
+        }
+
+        let hits = candidates
+            .into_iter()
+            .map(|candidate| self.align(candidate, searched_records))
+            .collect();
+        Ok(SearchResult {
+            query_length: self.query.len(),
+            hits,
+            searched_records,
+            searched_residues,
+        })
+    }
+
+    // Lower ranks first.
+    fn rank(&self, score: f32, target_length: usize) -> f64 {
+        match (self.settings.ranking, &self.statistics) {
+            (HitRanking::EValue, Some(statistics)) => {
+                statistics.e_value(score, (self.query.len() * target_length) as f64)
+            }
+            _ => -score as f64,
+        }
+    }
+
+    fn align(&self, candidate: Candidate, searched_records: usize) -> Hit {
+        let length = candidate.target.seq().len();
+        let mut aligner = SmithWaterman::with_scoring_schema(
+            Units(self.query.clone()),
+            candidate.target,
+            Box::new(self.scoring_schema.clone()),
+        );
+        aligner.set_tie_break(TieBreak::PreferDiagonal);
+        let mut alignment = Aligner::run(&mut aligner).remove(0);
+        if let Some(statistics) = &self.statistics {
+            let search_space = (self.query.len() * length * searched_records) as f64;
+            alignment.set_significance(statistics, search_space);
+        }
+        Hit {
+            id: candidate.id,
+            description: candidate.description,
+            length,
+            alignment,
+        }
+    }
+}
+
+// A record kept while searching, before its alignment is built.
+struct Candidate {
+    id: String,
+    description: String,
+    target: Protein,
+    rank: f64,
+}
+
+/// A library record that matches the query, with its local alignment. The query is the
+/// first sequence of the alignment and the record the second one.
+#[cfg_attr(test, derive(Debug))]
+pub struct Hit {
+    id: String,
+    description: String,
+    length: usize,
+    alignment: AlignmentResult<Aac>,
+}
+
+impl Hit {
+    /// Identifier of the record.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Rest of the record header, see FastaRecord::description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Length of the record sequence.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn score(&self) -> f32 {
+        self.alignment.score()
+    }
+
+    /// Normalised score, in bits. None without Karlin-Altschul parameters.
+    pub fn bit_score(&self) -> Option<f64> {
+        self.alignment.bit_score()
+    }
+
+    /// Expected number of hits with this score in a library of random sequences, see the
+    /// module documentation. None without Karlin-Altschul parameters.
+    pub fn e_value(&self) -> Option<f64> {
+        self.alignment.e_value()
+    }
+
+    pub fn alignment(&self) -> &AlignmentResult<Aac> {
+        &self.alignment
+    }
+
+    pub fn into_alignment(self) -> AlignmentResult<Aac> {
+        self.alignment
+    }
+}
+
+/// Hits of a search, ranked as set in SearchSettings, and the size of the library.
+#[cfg_attr(test, derive(Debug))]
+pub struct SearchResult {
+    query_length: usize,
+    hits: Vec<Hit>,
+    searched_records: usize,
+    searched_residues: usize,
+}
+
+impl SearchResult {
+    pub fn hits(&self) -> &[Hit] {
+        &self.hits
+    }
+
+    pub fn into_hits(self) -> Vec<Hit> {
+        self.hits
+    }
+
+    pub fn query_length(&self) -> usize {
+        self.query_length
+    }
+
+    /// Number of records in the library.
+    pub fn searched_records(&self) -> usize {
+        self.searched_records
+    }
+
+    /// Number of residues in the library.
+    pub fn searched_residues(&self) -> usize {
+        self.searched_residues
+    }
+
+    /// The hits as tab-separated values, one line per hit after a header line. The bit
+    /// score and the E-value are empty without Karlin-Altschul parameters; the identity is
+    /// a percentage.
+    ///
+    /// The columns are: id, score, bit_score, e_value, identity, length, query_begin,
+    /// query_end, target_begin, target_end, target_length.
+    pub fn hit_table(&self) -> String {
+        let mut table = String::from(
+            "id\tscore\tbit_score\te_value\tidentity\tlength\tquery_begin\tquery_end\t\
+             target_begin\ttarget_end\ttarget_length\n",
+        );
+        for hit in &self.hits {
+            let alignment = hit.alignment();
+            let bit_score = hit
+                .bit_score()
+                .map(|bit_score| format!("{:.1}", bit_score))
+                .unwrap_or_default();
+            let e_value = hit
+                .e_value()
+                .map(|e_value| format!("{:.2e}", e_value))
+                .unwrap_or_default();
+            let _ = writeln!(
+                table,
+                "{}\t{}\t{}\t{}\t{:.1}\t{}\t{}\t{}\t{}\t{}\t{}",
+                hit.id(),
+                hit.score(),
+                bit_score,
+                e_value,
+                alignment.percent_identity(),
+                alignment.length(),
+                alignment.begin()[0],
+                alignment.end()[0],
+                alignment.begin()[1],
+                alignment.end()[1],
+                hit.length(),
+            );
+        }
+        table
+    }
+}
+
+#[derive(Debug)]
+/// Error type of DatabaseSearch.
+pub struct SearchError {
+    kind: SearchErrorKind,
+    message: String,
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of SearchError.
+pub enum SearchErrorKind {
+    EmptyQuery,
+    InvalidLibrary,
+    InvalidSettings,
+    NoStatistics,
+}
+
+impl SearchError {
+    fn new(kind: SearchErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            SearchErrorKind::EmptyQuery => "The query must not be empty.".to_string(),
+            SearchErrorKind::InvalidLibrary => {
+                "The library is not a valid FASTA text of proteins.".to_string()
+            }
+            SearchErrorKind::InvalidSettings => "The search settings are not valid.".to_string(),
+            SearchErrorKind::NoStatistics => {
+                "Ranking by E-value needs Karlin-Altschul parameters for the scoring schema."
+                    .to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &SearchErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for SearchError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const QUERY: &str = "HGEYWAKLV";
+
+    fn search(settings: SearchSettings) -> DatabaseSearch {
+        DatabaseSearch::new(
+            Protein::new(QUERY).unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            settings,
+        )
+        .unwrap()
+    }
+
+    fn ids(result: &SearchResult) -> Vec<&str> {
+        result.hits().iter().map(|hit| hit.id()).collect()
+    }
+
+    #[test]
+    fn ranked_hits() {
+        let library = ">noise\nPPPPPPPP\n>close some protein\nMKHGEFWK\nLVR\n\
+                       >exact\nMKTHGEYWAKLVRRA\n>far\nPPHAEYWPP\n";
+        let result = search(SearchSettings::default())
+            .run(library.as_bytes())
+            .unwrap();
+        assert_eq!(4, result.searched_records());
+        assert_eq!(8 + 11 + 15 + 9, result.searched_residues());
+        assert_eq!(["exact", "close", "far"].to_vec(), ids(&result));
+
+        let exact = &result.hits()[0];
+        assert_eq!(54.0, exact.score());
+        assert_eq!(15, exact.length());
+        assert_eq!([1, 4], exact.alignment().begin());
+        assert_eq!([9, 12], exact.alignment().end());
+        let close = &result.hits()[1];
+        assert_eq!("some protein", close.description());
+        // Pairwise E-value times the number of records
+        let statistics = KarlinAltschul::gapped(
+            &AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+        )
+        .unwrap();
+        let expected = statistics.e_value(close.score(), (9 * 11 * 4) as f64);
+        assert!((close.e_value().unwrap() - expected).abs() < 1e-12);
+        assert_eq!(
+            statistics.bit_score(close.score()),
+            close.bit_score().unwrap()
+        );
+        let scores: Vec<f32> = result.hits().iter().map(|hit| hit.score()).collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn same_scores_as_pairwise_alignments() {
+        let targets = [
+            "MKTHGEYWAKLVRRA",
+            "HGEFWKLV",
+            "WAKLVHGEY",
+            "YWAHGEKLVV",
+            "GGHG",
+        ];
+        let library: String = targets
+            .iter()
+            .enumerate()
+            .map(|(index, target)| format!(">t{}\n{}\n", index, target))
+            .collect();
+        let result = search(SearchSettings::default())
+            .run(library.as_bytes())
+            .unwrap();
+        for hit in result.hits() {
+            let index: usize = hit.id()[1..].parse().unwrap();
+            let aligner = SmithWaterman::new(
+                Protein::new(QUERY).unwrap(),
+                Protein::new(targets[index]).unwrap(),
+                AaScoringKind::Blosum62,
+                PenaltyKind::Affine(11.0, 1.0),
+            )
+            .unwrap();
+            assert_eq!(Aligner::score(&aligner).score(), hit.score());
+        }
+    }
+
+    #[test]
+    fn keeps_the_best_hits() {
+        // Both records score the same; the shorter one has the lower E-value.
+        let library =
+            ">long\nHGEYWAKLVGGGGGGGGGGGGGGGGG\n>short\nHGEYWAKLV\n>close\nHGEFWKLV\n";
+        let settings = SearchSettings {
+            max_hits: 2,
+            ranking: HitRanking::Score,
+        };
+        let result = search(settings).run(library.as_bytes()).unwrap();
+        assert_eq!(["long", "short"].to_vec(), ids(&result));
+        assert_eq!(3, result.searched_records());
+
+        let settings = SearchSettings {
+            max_hits: 1,
+            ranking: HitRanking::EValue,
+        };
+        let result = search(settings).run(library.as_bytes()).unwrap();
+        assert_eq!(["short"].to_vec(), ids(&result));
+    }
+
+    #[test]
+    fn hit_table() {
+        let library = ">exact\nMKTHGEYWAKLVRRA\n";
+        let result = search(SearchSettings::default())
+            .run(library.as_bytes())
+            .unwrap();
+        let table = result.hit_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(2, lines.len());
+        assert_eq!(
+            "id\tscore\tbit_score\te_value\tidentity\tlength\tquery_begin\tquery_end\t\
+             target_begin\ttarget_end\ttarget_length",
+            lines[0]
+        );
+        let fields: Vec<&str> = lines[1].split('\t').collect();
+        assert_eq!(["exact", "54"].to_vec(), fields[..2]);
+        assert_eq!(
+            ["100.0", "9", "1", "9", "4", "12", "15"].to_vec(),
+            fields[4..]
+        );
+
+        // Without statistics
+        let search = DatabaseSearch::new(
+            Protein::new(QUERY).unwrap(),
+            AaScoringKind::Pam160,
+            PenaltyKind::Affine(9.0, 1.0),
+            SearchSettings::default(),
+        )
+        .unwrap();
+        let table = search.run(library.as_bytes()).unwrap().hit_table();
+        let fields: Vec<&str> = table.lines().nth(1).unwrap().split('\t').collect();
+        assert_eq!(["", ""].to_vec(), fields[2..4]);
+    }
+
+    #[test]
+    fn search_errors() {
+        let library = ">exact\nMKTHGEYWAKLVRRA\n";
+        let empty = DatabaseSearch::new(
+            Units(Vec::new()),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            SearchSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            &SearchErrorKind::EmptyQuery,
+            empty.run(library.as_bytes()).unwrap_err().kind()
+        );
+
+        let settings = SearchSettings {
+            max_hits: 0,
+            ranking: HitRanking::Score,
+        };
+        assert_eq!(
+            &SearchErrorKind::InvalidSettings,
+            search(settings).run(library.as_bytes()).unwrap_err().kind()
+        );
+
+        let no_statistics = DatabaseSearch::new(
+            Protein::new(QUERY).unwrap(),
+            AaScoringKind::Pam160,
+            PenaltyKind::Affine(9.0, 1.0),
+            SearchSettings {
+                max_hits: 10,
+                ranking: HitRanking::EValue,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            &SearchErrorKind::NoStatistics,
+            no_statistics.run(library.as_bytes()).unwrap_err().kind()
+        );
+
+        let invalid = ">exact\nMKTHGEYWAKLVRRA\n>broken\nMKT\nHG1EY\n";
+        let error = search(SearchSettings::default())
+            .run(invalid.as_bytes())
+            .unwrap_err();
+        assert_eq!(&SearchErrorKind::InvalidLibrary, error.kind());
+        assert!(error.to_string().contains("line 5"), "{}", error);
+    }
+}
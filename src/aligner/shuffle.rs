@@ -0,0 +1,222 @@
+//! Empirical significance of alignment scores by shuffling one of the sequences.
+
+use super::utils::ShuffleSignificance;
+use super::Shuffle;
+use crate::utils::AlignmentUnit;
+use std::collections::HashMap;
+
+/// Compares `score` with the scores of `shuffles` shuffled versions of `sequence`, computed
+/// by `score_of`. The shuffles are reproducible for a given seed.
+pub(super) fn significance<A: AlignmentUnit>(
+    sequence: &[A],
+    score: f32,
+    shuffles: usize,
+    shuffle: Shuffle,
+    seed: u64,
+    mut score_of: impl FnMut(&[A]) -> f32,
+) -> ShuffleSignificance {
+    let mut rng = Xorshift::new(seed);
+    let mut shuffled_scores: Vec<f64> = Vec::with_capacity(shuffles);
+    for _ in 0..shuffles {
+        let shuffled = match shuffle {
+            Shuffle::Permutation => permutation(sequence, &mut rng),
+            Shuffle::Dinucleotide => doublet_shuffle(sequence, &mut rng),
+        };
+        shuffled_scores.push(score_of(&shuffled) as f64);
+    }
+
+    let count = shuffled_scores.len().max(1) as f64;
+    let mean = shuffled_scores.iter().sum::<f64>() / count;
+    let variance = shuffled_scores
+        .iter()
+        .map(|shuffled_score| (shuffled_score - mean).powi(2))
+        .sum::<f64>()
+        / (count - 1.0).max(1.0);
+    let standard_deviation = variance.sqrt();
+    // The observed score counts as one of the samples, so the p-value is never 0.
+    let as_good = shuffled_scores
+        .iter()
+        .filter(|shuffled_score| **shuffled_score >= score as f64)
+        .count();
+    let p_value = (as_good + 1) as f64 / (shuffled_scores.len() + 1) as f64;
+
+    ShuffleSignificance::new(score, mean, standard_deviation, p_value)
+}
+
+// Fisher-Yates shuffle. Keeps the composition of the sequence.
+fn permutation<A: AlignmentUnit>(sequence: &[A], rng: &mut Xorshift) -> Vec<A> {
+    let mut shuffled = sequence.to_vec();
+    for i in (1..shuffled.len()).rev() {
+        shuffled.swap(i, rng.below(i + 1));
+    }
+    shuffled
+}
+
+// Altschul & Erickson (1985) "Significance of nucleotide sequence alignments: a method for
+// random sequence permutation that preserves dinucleotide and codon usage". The sequence
+// is a walk over the graph whose edges join each unit with the next one. A random Eulerian
+// walk of that graph, from the same first unit, keeps the count of every adjacent pair.
+fn doublet_shuffle<A: AlignmentUnit>(sequence: &[A], rng: &mut Xorshift) -> Vec<A> {
+    if sequence.len() < 3 {
+        return sequence.to_vec();
+    }
+    // Vertices numbered by first appearance, so the result only depends on the seed.
+    let mut vertices: HashMap<A, usize> = HashMap::new();
+    let mut units: Vec<A> = Vec::new();
+    let indices: Vec<usize> = sequence
+        .iter()
+        .map(|unit| {
+            *vertices.entry(*unit).or_insert_with(|| {
+                units.push(*unit);
+                units.len() - 1
+            })
+        })
+        .collect();
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); units.len()];
+    for pair in indices.windows(2) {
+        edges[pair[0]].push(pair[1]);
+    }
+    let last = *indices.last().unwrap();
+
+    // Each vertex but the last leaves by a last edge. The walk uses every edge only if
+    // the last edges form a tree rooted at the last vertex; draw them until they do.
+    let last_edges: Vec<Option<usize>> = loop {
+        let candidate: Vec<Option<usize>> = edges
+            .iter()
+            .enumerate()
+            .map(|(vertex, out)| match vertex == last || out.is_empty() {
+                true => None,
+                false => Some(rng.below(out.len())),
+            })
+            .collect();
+        let reaches_last = |start: usize| {
+            let mut vertex = start;
+            for _ in 0..units.len() {
+                match candidate[vertex] {
+                    _ if vertex == last => return true,
+                    Some(edge) => vertex = edges[vertex][edge],
+                    None => return false,
+                }
+            }
+            vertex == last
+        };
+        if (0..units.len()).all(reaches_last) {
+            break candidate;
+        }
+    };
+
+    for (vertex, last_edge) in last_edges.into_iter().enumerate() {
+        let out = &mut edges[vertex];
+        let kept = last_edge.map(|edge| out.remove(edge));
+        for i in (1..out.len()).rev() {
+            out.swap(i, rng.below(i + 1));
+        }
+        out.extend(kept);
+    }
+
+    let mut next_edge = vec![0; units.len()];
+    let mut vertex = indices[0];
+    let mut shuffled = Vec::with_capacity(sequence.len());
+    shuffled.push(units[vertex]);
+    for _ in 1..sequence.len() {
+        let edge = next_edge[vertex];
+        next_edge[vertex] += 1;
+        vertex = edges[vertex][edge];
+        shuffled.push(units[vertex]);
+    }
+    shuffled
+}
+
+// Marsaglia's xorshift64* generator. Small and good enough to draw shuffles.
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn new(seed: u64) -> Self {
+        // A splitmix64 step spreads the seed over the state, which must not be 0: the
+        // generator would only return 0.
+        let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        state ^= state >> 31;
+        match state {
+            0 => Self(0x9E37_79B9_7F4A_7C15),
+            state => Self(state),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Uniform integer in 0..bound
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bioseq::{Dna, HasSequence, Nuc};
+
+    fn pair_counts(sequence: &[Nuc]) -> HashMap<[Nuc; 2], usize> {
+        let mut counts = HashMap::new();
+        for pair in sequence.windows(2) {
+            *counts.entry([pair[0], pair[1]]).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn shuffles_keep_the_composition() {
+        let dna = Dna::new("ATGGCGTACGCTAGCTAGGATCCGATCGATCGTTAGCAAAGCT").unwrap();
+        let sequence = dna.seq();
+        let mut rng = Xorshift::new(7);
+        let mut sorted = sequence.to_vec();
+        sorted.sort();
+
+        let shuffled = permutation(sequence, &mut rng);
+        assert_ne!(sequence, &shuffled[..]);
+        let mut shuffled_sorted = shuffled.clone();
+        shuffled_sorted.sort();
+        assert_eq!(sorted, shuffled_sorted);
+
+        for _ in 0..20 {
+            let shuffled = doublet_shuffle(sequence, &mut rng);
+            assert_eq!(sequence.len(), shuffled.len());
+            assert_eq!(sequence[0], shuffled[0]);
+            assert_eq!(sequence.last(), shuffled.last());
+            assert_eq!(pair_counts(sequence), pair_counts(&shuffled));
+        }
+    }
+
+    #[test]
+    fn shuffles_are_reproducible() {
+        let dna = Dna::new("ATGGCGTACGCTAGCTAGGATCCGATCG").unwrap();
+        let draw = |seed: u64| {
+            let mut rng = Xorshift::new(seed);
+            doublet_shuffle(dna.seq(), &mut rng)
+        };
+        assert_eq!(draw(3), draw(3));
+        assert_ne!(draw(3), draw(4));
+    }
+
+    #[test]
+    fn no_zero_state() {
+        // The seed that gave a zero state when it was only XORed with the constant.
+        let seed = 0x9E37_79B9_7F4A_7C15;
+        let mut rng = Xorshift::new(seed);
+        assert!((0..4).any(|_| rng.next_u64() != 0));
+
+        let dna = Dna::new("ATGGCGTACGCTAGCTAGGATCCGATCG").unwrap();
+        let mut rng = Xorshift::new(seed);
+        let shuffled = doublet_shuffle(dna.seq(), &mut rng);
+        assert_eq!(pair_counts(dna.seq()), pair_counts(&shuffled));
+        let shuffles: Vec<Vec<Nuc>> =
+            (0..5).map(|_| permutation(dna.seq(), &mut rng)).collect();
+        assert!(shuffles.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
@@ -1,10 +1,13 @@
 //! common data structures and functions used for multiple align algorithms
 
+use super::TieBreak;
 use crate::{
     bioseq::HasSequence, matrix::Matrix, scoring_schema::ScoringSchema,
-    utils::AlignmentUnit,
+    statistics::KarlinAltschul, utils::AlignmentUnit,
 };
-use std::mem::replace;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::ops::Range;
 
 /// Represent values for backtracking
 #[derive(Clone, Copy)]
@@ -46,9 +49,13 @@ fn nonempty_from_discriminant(discriminant: u8, score: f32) -> Self {
         }
     }
 
-    /// generate the back track direction from the scores
+    /// generate the back track direction from the scores.
+    /// If all the scores are -∞ there is no valid direction and returns BackTrack::Empty.
     pub fn make_backtrack(top: f32, diagonal: f32, left: f32) -> (BackTrack, f32) {
         let max_score = [top, diagonal, left].into_iter().reduce(f32::max).unwrap();
+        if max_score == f32::NEG_INFINITY {
+            return (BackTrack::Empty, max_score);
+        }
         let mut discriminant: u8 = 0b000;
         for (value, indicator) in [(top, 0b001), (diagonal, 0b010), (left, 0b100)] {
             if value == max_score {
@@ -78,83 +85,20 @@ pub fn make_backtrack_similarity(
         (backtrack, max_score)
     }
 
-    /// from an entry matrix cell tracks all the paths
-    ///
-    /// * `cutoff_score`: a lower bound for the score of a single matrix element.
-    /// If the cell contains a score equal or lower than cutoff, then the backtrack
-    /// in that branch stops. You can use f32::NEG_INFINITY if do not want to set any cutoff
-    pub fn backtracking(
-        matrix: &Matrix<BackTrack>,
-        init_row: usize,
-        init_col: usize,
-        cutoff_score: f32,
-    ) -> Vec<Vec<[usize; 2]>> {
-        let mut paths: Vec<Vec<[usize; 2]>> = Vec::new();
-        let mut pending_stack: Vec<Vec<[usize; 2]>> = Vec::new();
-        let mut current_path: Vec<[usize; 2]> =
-            Vec::with_capacity(matrix.dim()[0].max(matrix.dim()[1]));
-        current_path.push([init_row, init_col]);
+    /// Returns the same direction with a different score. BackTrack::Empty remains empty.
+    pub fn rescore(self, score: f32) -> Self {
+        match Self::decompose(self) {
+            (b'\x00', _) => BackTrack::Empty,
+            (discriminant, _) => Self::nonempty_from_discriminant(discriminant, score),
+        }
+    }
 
-        loop {
-            let [row, col] = *current_path.last().unwrap();
-            let (indicator, score) = Self::decompose(matrix[[row, col]]);
-            if ((row == 0) && (col == 0)) || (score <= cutoff_score) {
-                match pending_stack.pop() {
-                    Some(next_path) => {
-                        let old_path = replace(&mut current_path, next_path);
-                        paths.push(old_path);
-                    }
-                    None => {
-                        paths.push(current_path);
-                        return paths;
-                    }
-                };
-            } else {
-                match indicator {
-                    // T
-                    b'\x01' => current_path.push([row - 1, col]),
-                    //D
-                    b'\x02' => current_path.push([row - 1, col - 1]),
-                    //L
-                    b'\x04' => current_path.push([row, col - 1]),
-                    //DT
-                    b'\x03' => {
-                        let mut branch = current_path.clone();
-                        branch.push([row - 1, col]);
-                        pending_stack.push(branch);
-                        current_path.push([row - 1, col - 1]);
-                        },
-                    //DL
-                    b'\x06' => {
-                        let mut branch = current_path.clone();
-                        branch.push([row, col - 1]);
-                        pending_stack.push(branch);
-                        current_path.push([row - 1, col - 1]);
-                    }
-                    //TL
-                    b'\x05' => {
-                        let mut branch = current_path.clone();
-                        branch.push([row, col - 1]);
-                        pending_stack.push(branch);
-                        current_path.push([row - 1, col]);
-                    }
-                    //All
-                    b'\x07' => {
-                        let mut branch = current_path.clone();
-                        branch.push([row, col - 1]);
-                        pending_stack.push(branch);
-
-                        let mut branch = current_path.clone();
-                        branch.push([row - 1, col]);
-                        pending_stack.push(branch);
-
-                        current_path.push([row - 1, col - 1])}
-
-                    _ => panic!(
-                        "Empty at [{row}, {col}]. Any implementation must remove all Empty from the matrix."
-                    ),
-                };
-            };
+    /// Returns the associated score. BackTrack::Empty represents an unreachable cell, so its
+    /// score is -∞.
+    pub fn score(&self) -> f32 {
+        match Self::decompose(*self) {
+            (b'\x00', _) => f32::NEG_INFINITY,
+            (_, score) => score,
         }
     }
 
@@ -226,99 +170,530 @@ pub fn new(
     }
 }
 
-// Be aware this implimentation is intended to be used with Affine gap models and
-// their subsets; i.e., Linear and constant models.
+/// An optimal alignment together with its score, coordinates and statistics.
+#[cfg_attr(test, derive(Debug))]
+pub struct AlignmentResult<A>
+where
+    A: AlignmentUnit,
+{
+    alignment: AlignmentSequence<A>,
+    score: f32,
+    /// 1-based position of the first aligned residue at [left, top] sequence
+    begin: [usize; 2],
+    /// 1-based position of the last aligned residue at [left, top] sequence
+    end: [usize; 2],
+    identities: usize,
+    positives: usize,
+    gaps: usize,
+    /// Whether a banded aligner path reached the band edge
+    touches_band_edge: bool,
+    /// Karlin-Altschul bit score and E-value of local alignments
+    significance: Option<[f64; 2]>,
+}
+
+impl<A> AlignmentResult<A>
+where
+    A: AlignmentUnit,
+{
+    /// Builds the alignment from a backtrack path, see AlignmentSequence::new, and computes
+    /// its statistics.
+    ///
+    /// * `score`: the optimal score found by the aligner.
+    /// * `scoring_schema`: used to count the positives, the pairs with a positive
+    ///   substitution score.
+    pub fn new(
+        backtrack_path: Vec<[usize; 2]>,
+        sequence_left: &(impl HasSequence<A> + ?Sized),
+        sequence_top: &(impl HasSequence<A> + ?Sized),
+        score: f32,
+        scoring_schema: &dyn ScoringSchema<A>,
+    ) -> Self {
+        let [end_row, end_col] = backtrack_path[0];
+        let [start_row, start_col] = *backtrack_path.last().unwrap();
+        // A sequence without aligned residues has no coordinates
+        let coordinates = |start: usize, end: usize| {
+            if end > start {
+                [start + 1, end]
+            } else {
+                [0, 0]
+            }
+        };
+        let [begin_left, end_left] = coordinates(start_row, end_row);
+        let [begin_top, end_top] = coordinates(start_col, end_col);
+
+        let alignment =
+            AlignmentSequence::new(backtrack_path, sequence_left, sequence_top);
+        let mut identities = 0;
+        let mut positives = 0;
+        let mut gaps = 0;
+        for pair in alignment.read() {
+            match pair {
+                [Some(left), Some(top)] => {
+                    if left == top {
+                        identities += 1;
+                    }
+                    if scoring_schema.get_score(*left, *top) > 0 {
+                        positives += 1;
+                    }
+                }
+                _ => gaps += 1,
+            }
+        }
+
+        Self {
+            alignment,
+            score,
+            begin: [begin_left, begin_top],
+            end: [end_left, end_top],
+            identities,
+            positives,
+            gaps,
+            touches_band_edge: false,
+            significance: None,
+        }
+    }
+
+    pub fn alignment(&self) -> &AlignmentSequence<A> {
+        &self.alignment
+    }
+
+    /// The alignment pairs, see AlignmentSequence::read.
+    pub fn read(&self) -> &Vec<[Option<A>; 2]> {
+        self.alignment.read()
+    }
+
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// 1-based positions of the first aligned residue at the [left, top] sequences. It is 0
+    /// if no residue of the sequence is aligned.
+    pub fn begin(&self) -> [usize; 2] {
+        self.begin
+    }
+
+    /// 1-based positions of the last aligned residue at the [left, top] sequences. It is 0
+    /// if no residue of the sequence is aligned.
+    pub fn end(&self) -> [usize; 2] {
+        self.end
+    }
+
+    /// Number of columns of the alignment, including the gaps.
+    pub fn length(&self) -> usize {
+        self.alignment.read().len()
+    }
+
+    /// Number of columns with the same residue at both sequences.
+    pub fn identities(&self) -> usize {
+        self.identities
+    }
+
+    /// Number of columns whose substitution score is positive.
+    pub fn positives(&self) -> usize {
+        self.positives
+    }
+
+    /// Number of columns with a gap.
+    pub fn gaps(&self) -> usize {
+        self.gaps
+    }
+
+    /// Identities over the alignment length, as a percentage. It is 0 for an empty alignment.
+    pub fn percent_identity(&self) -> f32 {
+        if self.length() == 0 {
+            return 0.0;
+        }
+        100.0 * self.identities as f32 / self.length() as f32
+    }
+
+    /// Whether the alignment was found inside a band of diagonals and its path reached the
+    /// band edge. Then a better alignment may exist outside the band; a wider band can
+    /// find it.
+    pub fn touches_band_edge(&self) -> bool {
+        self.touches_band_edge
+    }
+
+    pub(super) fn set_touches_band_edge(&mut self, touches_band_edge: bool) {
+        self.touches_band_edge = touches_band_edge;
+    }
+
+    /// Normalised score, in bits, see KarlinAltschul::bit_score. Only for local alignments
+    /// whose scoring system has known Karlin-Altschul parameters.
+    pub fn bit_score(&self) -> Option<f64> {
+        self.significance.map(|[bit_score, _]| bit_score)
+    }
+
+    /// Expected number of alignments with this score between two random sequences of the
+    /// same lengths, see KarlinAltschul::e_value. Available as bit_score.
+    pub fn e_value(&self) -> Option<f64> {
+        self.significance.map(|[_, e_value]| e_value)
+    }
+
+    pub(super) fn set_significance(
+        &mut self,
+        parameters: &KarlinAltschul,
+        search_space: f64,
+    ) {
+        self.significance = Some([
+            parameters.bit_score(self.score),
+            parameters.e_value(self.score, search_space),
+        ]);
+    }
+}
+
+/// The optimal score of an alignment, computed without its traceback, see Aligner::score.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AlignmentScore {
+    score: f32,
+    /// 1-based position of the last aligned residue at [left, top] sequence, local only
+    end: Option<[usize; 2]>,
+}
+
+impl AlignmentScore {
+    pub fn new(score: f32, end: Option<[usize; 2]>) -> Self {
+        Self { score, end }
+    }
+
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// For local alignments, the 1-based positions of the last aligned residue at the
+    /// [left, top] sequences, as AlignmentResult::end. None for global and semi-global
+    /// alignments.
+    pub fn end(&self) -> Option<[usize; 2]> {
+        self.end
+    }
+}
+
+/// Empirical significance of an optimal score, from the scores of shuffled sequences, see
+/// Aligner::shuffle_significance.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ShuffleSignificance {
+    score: f32,
+    mean: f64,
+    standard_deviation: f64,
+    p_value: f64,
+}
+
+impl ShuffleSignificance {
+    pub fn new(score: f32, mean: f64, standard_deviation: f64, p_value: f64) -> Self {
+        Self {
+            score,
+            mean,
+            standard_deviation,
+            p_value,
+        }
+    }
+
+    /// The optimal score of the original sequences.
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// Mean score of the shuffled sequences.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Sample standard deviation of the shuffled sequences scores.
+    pub fn standard_deviation(&self) -> f64 {
+        self.standard_deviation
+    }
+
+    /// Standard deviations between the score and the mean of the shuffled ones. Infinite
+    /// (or NaN) when all the shuffled scores are equal.
+    pub fn z_score(&self) -> f64 {
+        (self.score as f64 - self.mean) / self.standard_deviation
+    }
+
+    /// Fraction of the shuffled sequences scoring at least as well as the original ones,
+    /// counting the original as one of them, (k + 1) / (N + 1). So it is never below
+    /// 1 / (N + 1).
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+}
+
+/// Selects the alignments returned by an aligner from its backtracking paths, each one with
+/// its band edge flag. Removes the repeated paths, keeping the first one, orders them as
+/// set by the tie-break policy and keeps at most `limit` of them, but at least one.
+pub fn select_paths(
+    candidates: Vec<(Vec<[usize; 2]>, bool)>,
+    tie_break: TieBreak,
+    limit: usize,
+) -> Vec<(Vec<[usize; 2]>, bool)> {
+    let mut seen: HashSet<Vec<[usize; 2]>> = HashSet::new();
+    let mut selected: Vec<(Vec<[usize; 2]>, bool)> = candidates
+        .into_iter()
+        .filter(|(path, _)| seen.insert(path.clone()))
+        .collect();
+    if tie_break == TieBreak::Longest {
+        // Stable, so equally long paths keep the backtracking order.
+        selected.sort_by_key(|(path, _)| Reverse(path.len()));
+    }
+    selected.truncate(limit.max(1));
+    selected
+}
+
+/// Gotoh's three-state dynamic programming matrices.
+///
+/// Each matrix keeps the best score of the prefixes alignments ending in a given state. In
+/// these matrices the BackTrack bits denote states instead of directions: T (1) is the gap at
+/// top sequence state, D (2) the match/mismatch state and L (4) the gap at left sequence state.
+/// The BackTrack of a cell points to the state(s) of the previous cell from which its score
+/// comes, so the traceback knows if a gap is being opened or extended.
+pub struct AffineMatrices {
+    /// Alignments ending with a gap at top sequence. Their previous cell is [i-1, j].
+    top: Matrix<BackTrack>,
+    /// Alignments ending with a match/mismatch. Their previous cell is [i-1, j-1].
+    diagonal: Matrix<BackTrack>,
+    /// Alignments ending with a gap at left sequence. Their previous cell is [i, j-1].
+    left: Matrix<BackTrack>,
+}
+
+impl AffineMatrices {
+    /// Creates the three state matrices, all of them filled with BackTrack::Empty.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            top: Matrix::full(BackTrack::Empty, rows, cols),
+            diagonal: Matrix::full(BackTrack::Empty, rows, cols),
+            left: Matrix::full(BackTrack::Empty, rows, cols),
+        }
+    }
+
+    /// Creates the three state matrices storing only the band of diagonals
+    /// lower ≤ j - i ≤ upper, see Matrix::banded. The cells outside the band are Empty.
+    pub fn banded(rows: usize, cols: usize, diagonals: [isize; 2]) -> Self {
+        let matrix =
+            || Matrix::banded(BackTrack::Empty, BackTrack::Empty, rows, cols, diagonals);
+        Self {
+            top: matrix(),
+            diagonal: matrix(),
+            left: matrix(),
+        }
+    }
+
+    /// Returns the matrices dimension
+    pub fn dim(&self) -> [usize; 2] {
+        self.diagonal.dim()
+    }
+
+    /// Returns the columns of the row i inside the band, see Matrix::row_range.
+    pub fn row_range(&self, i: usize) -> Range<usize> {
+        self.diagonal.row_range(i)
+    }
+
+    /// Returns whether the cell i, j is inside the band.
+    pub fn in_band(&self, i: usize, j: usize) -> bool {
+        self.diagonal.in_band(i, j)
+    }
+
+    /// Returns the stored diagonals [lower, upper] of band-compressed matrices.
+    pub fn band(&self) -> Option<[isize; 2]> {
+        self.diagonal.band()
+    }
+
+    /// Memory, in bytes, taken by the three state matrices of dimension rows * cols. For a
+    /// band-compressed matrix, cols is the number of stored diagonals.
+    pub fn estimated_size(rows: usize, cols: usize) -> usize {
+        rows.saturating_mul(cols)
+            .saturating_mul(3 * std::mem::size_of::<BackTrack>())
+    }
+
+    /// Returns the [top, diagonal, left] states scores of the cell i, j.
+    /// Unreachable states have a -∞ score.
+    pub fn scores(&self, i: usize, j: usize) -> [f32; 3] {
+        [
+            self.top[[i, j]].score(),
+            self.diagonal[[i, j]].score(),
+            self.left[[i, j]].score(),
+        ]
+    }
+
+    /// Sets the [top, diagonal, left] states of the cell i, j.
+    pub fn set(&mut self, i: usize, j: usize, states: [BackTrack; 3]) {
+        let [top, diagonal, left] = states;
+        self.top[[i, j]] = top;
+        self.diagonal[[i, j]] = diagonal;
+        self.left[[i, j]] = left;
+    }
+
+    /// Returns the best score of the cell i, j and a BackTrack pointing to the state(s) that
+    /// reach it.
+    pub fn best(&self, i: usize, j: usize) -> (BackTrack, f32) {
+        let [top, diagonal, left] = self.scores(i, j);
+        BackTrack::make_backtrack(top, diagonal, left)
+    }
+
+    /// From an entry matrix cell tracks all the paths. The traceback moves between the state
+    /// matrices, then every path is consistent with the gap model. Paths end at the first
+    /// row or column; from there the only way to [0, 0] is along the border.
+    ///
+    /// * `init_states`: the state(s) of the starting cell from which the paths begin.
+    ///   Its score is ignored.
+    /// * `cutoff_score`: a lower bound for the score of a single state cell.
+    ///   If the state contains a score equal or lower than cutoff, then the backtrack
+    ///   in that branch stops. You can use f32::NEG_INFINITY if do not want to set any cutoff
+    /// * `tie_break`: the order in which the tied states are explored, see TieBreak.
+    /// * `limit`: stops after finding this many paths.
+    pub fn backtracking(
+        &self,
+        init_row: usize,
+        init_col: usize,
+        init_states: BackTrack,
+        cutoff_score: f32,
+        tie_break: TieBreak,
+        limit: usize,
+    ) -> Vec<Vec<[usize; 2]>> {
+        let state_order = tie_break.state_order();
+        let mut paths: Vec<Vec<[usize; 2]>> = Vec::new();
+        let mut pending_stack: Vec<(Vec<[usize; 2]>, u8)> = Vec::new();
+        let mut first_path: Vec<[usize; 2]> =
+            Vec::with_capacity(self.dim()[0] + self.dim()[1]);
+        first_path.push([init_row, init_col]);
+        Self::push_branches(
+            &mut pending_stack,
+            first_path,
+            BackTrack::decompose(init_states).0,
+            state_order,
+        );
+
+        while let Some((mut current_path, state)) = pending_stack.pop() {
+            if paths.len() == limit {
+                break;
+            }
+            let [row, col] = *current_path.last().unwrap();
+            let (indicator, score) =
+                BackTrack::decompose(self.state_matrix(state)[[row, col]]);
+            if (row == 0) || (col == 0) || (score <= cutoff_score) {
+                paths.push(current_path);
+                continue;
+            }
+            if indicator == b'\x00' {
+                panic!(
+                    "Empty at [{row}, {col}]. Any implementation must remove all Empty from the matrix."
+                )
+            }
+            current_path.push(match state {
+                b'\x01' => [row - 1, col],
+                b'\x02' => [row - 1, col - 1],
+                _ => [row, col - 1],
+            });
+            Self::push_branches(&mut pending_stack, current_path, indicator, state_order);
+        }
+        paths
+    }
+
+    // Adds a pending path for each state denoted by the indicator. The preferred state is
+    // pushed the last, so it is the first to be explored.
+    fn push_branches(
+        pending_stack: &mut Vec<(Vec<[usize; 2]>, u8)>,
+        path: Vec<[usize; 2]>,
+        indicator: u8,
+        state_order: [u8; 3],
+    ) {
+        let states: Vec<u8> = state_order
+            .into_iter()
+            .rev()
+            .filter(|state| indicator & state != 0)
+            .collect();
+        if let Some((last, others)) = states.split_last() {
+            for state in others {
+                pending_stack.push((path.clone(), *state));
+            }
+            pending_stack.push((path, *last));
+        }
+    }
+
+    fn state_matrix(&self, state: u8) -> &Matrix<BackTrack> {
+        match state {
+            b'\x01' => &self.top,
+            b'\x02' => &self.diagonal,
+            b'\x04' => &self.left,
+            _ => panic!("The value {} is not a valid state.", state),
+        }
+    }
+}
+
+/// Gotoh's recursion for the affine gap model. Be aware this implementation is intended to be
+/// used with Affine gap models and their subsets; i.e., Linear and constant models.
+///
+/// Each function computes a single state of the cell i, j from the states of its previous cell.
 pub trait AffineTransversalOrder<A>
 where
     A: AlignmentUnit,
 {
+    /// Match/mismatch state. `floor` is a lower bound for the previous cell score; local
+    /// alignments use 0 so an alignment can start at any cell.
     fn diagonal_score(
         sequence_left: &(impl HasSequence<A> + ?Sized),
         sequence_top: &(impl HasSequence<A> + ?Sized),
-        scoring_schema: &Box<dyn ScoringSchema<A>>,
-        matrix: &Matrix<BackTrack>,
+        scoring_schema: &dyn ScoringSchema<A>,
+        matrices: &AffineMatrices,
         i: usize,
         j: usize,
-    ) -> f32 {
+        floor: f32,
+    ) -> BackTrack {
         // Read the sequences i,j element. Remember the Matrix has (n+1)(m+1) elements, with the
         // extra row and colum at the start.
         let left_alignable: A = sequence_left.seq()[i - 1];
         let top_alignable: A = sequence_top.seq()[j - 1];
         let score_ij = scoring_schema.get_score(left_alignable, top_alignable);
-        let value = match matrix[[i - 1, j - 1]] {
-            BackTrack::T(v) => v,
-            BackTrack::D(v) => v,
-            BackTrack::L(v) => v,
-            BackTrack::DT(v) => v,
-            BackTrack::DL(v) => v,
-            BackTrack::TL(v) => v,
-            BackTrack::All(v) => v,
-            BackTrack::Empty => {
-                panic!("This must be unreachable.Check the transversal order.")
-            }
-        };
-        value + score_ij as f32
+        let [top, diagonal, left] = matrices.scores(i - 1, j - 1);
+        let (backtrack, value) = BackTrack::make_backtrack(top, diagonal, left);
+        backtrack.rescore(value.max(floor) + score_ij as f32)
     }
 
+    /// Gap at top sequence state. The gap costs are those of the left unit it faces.
     fn top_score(
-        scoring_schema: &Box<dyn ScoringSchema<A>>,
-        matrix: &Matrix<BackTrack>,
+        sequence_left: &(impl HasSequence<A> + ?Sized),
+        scoring_schema: &dyn ScoringSchema<A>,
+        matrices: &AffineMatrices,
         i: usize,
         j: usize,
-    ) -> f32 {
+    ) -> BackTrack {
         // i-1, j
-        match matrix[[i - 1, j]] {
-            // top_gap + top_gap is an extension
-            BackTrack::T(v) => v - scoring_schema.get_extend(),
-            // not(top_gap) + top_gap is and opening
-            BackTrack::D(v) => v - scoring_schema.get_function(1),
-            BackTrack::L(v) => v - scoring_schema.get_function(1),
-            BackTrack::DL(v) => v - scoring_schema.get_function(1),
-            // Max(v - extend_existing_gap, v - add_new_gap) = v - extend_gap
-            // because extend_existing_gap <= add_new_gap
-            BackTrack::DT(v) => v - scoring_schema.get_extend(),
-            BackTrack::TL(v) => v - scoring_schema.get_extend(),
-            BackTrack::All(v) => v - scoring_schema.get_extend(),
-            BackTrack::Empty => {
-                panic!("This must be unreachable.Check the transversal order.")
-            }
-        }
+        let [top, diagonal, left] = matrices.scores(i - 1, j);
+        let [open, extend] = scoring_schema.get_gap_costs(sequence_left.seq()[i - 1]);
+        // top_gap + top_gap is an extension, anything else + top_gap is an opening
+        BackTrack::make_backtrack(
+            top - extend,
+            diagonal - (open + extend),
+            left - (open + extend),
+        )
+        .0
     }
 
+    /// Gap at left sequence state. The gap costs are those of the top unit it faces.
     fn left_score(
-        scoring_schema: &Box<dyn ScoringSchema<A>>,
-        matrix: &Matrix<BackTrack>,
+        sequence_top: &(impl HasSequence<A> + ?Sized),
+        scoring_schema: &dyn ScoringSchema<A>,
+        matrices: &AffineMatrices,
         i: usize,
         j: usize,
-    ) -> f32 {
+    ) -> BackTrack {
         // i, j-1
-        match matrix[[i, j - 1]] {
-            // left_gap + left_gap is and extension
-            BackTrack::L(v) => v - scoring_schema.get_extend(),
-            // not(left_gap) + left_gap is a new gap
-            BackTrack::T(v) => v - scoring_schema.get_function(1),
-            BackTrack::D(v) => v - scoring_schema.get_function(1),
-            BackTrack::DT(v) => v - scoring_schema.get_function(1),
-            // Max(v - extend_existing_gap, v - add_new_gap) = v - extend_gap
-            // because extend_existing_gap <= add_new_gap
-            BackTrack::DL(v) => v - scoring_schema.get_extend(),
-            BackTrack::TL(v) => v - scoring_schema.get_extend(),
-            BackTrack::All(v) => v - scoring_schema.get_extend(),
-            BackTrack::Empty => {
-                panic!("This must be unreachable.Check the transversal order.")
-            }
-        }
+        let [top, diagonal, left] = matrices.scores(i, j - 1);
+        let [open, extend] = scoring_schema.get_gap_costs(sequence_top.seq()[j - 1]);
+        // left_gap + left_gap is an extension, anything else + left_gap is an opening
+        BackTrack::make_backtrack(
+            top - (open + extend),
+            diagonal - (open + extend),
+            left - extend,
+        )
+        .0
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashSet;
-
-    use super::{AlignmentSequence, BackTrack};
+    use super::{AlignmentResult, AlignmentSequence, BackTrack};
     use crate::{
         bioseq::{Aac, Protein},
-        matrix::Matrix,
+        scoring_schema::{
+            aminoacid_schema::AaScoringKind, gap_penalty::PenaltyKind, AaScoringSchema,
+        },
     };
 
     #[test]
@@ -358,476 +733,15 @@ fn backtrack_from_scores() {
     }
 
     #[test]
-    fn matrix_backtrack_one_path_any_start() {
-        let container = vec![
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::L(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-        ];
-        let matrix = Matrix::from_vec(container, 4, 4);
-
-        let test_cases = [
-            ([3, 3], vec![vec![[3, 3], [2, 2], [1, 1], [0, 0]]]), // start at last cell
-            // start any other cell
-            ([2, 3], vec![vec![[2, 3], [2, 2], [1, 1], [0, 0]]]),
-            ([3, 2], vec![vec![[3, 2], [2, 2], [1, 1], [0, 0]]]),
-        ];
-
-        for (start, expected_path) in test_cases {
-            let [init_row, init_col] = start;
-            let actual_path =
-                BackTrack::backtracking(&matrix, init_row, init_col, f32::NEG_INFINITY);
-            assert_eq!(
-                expected_path, actual_path,
-                "Failed with starting cell [{init_row}, {init_col}]."
-            )
-        }
-    }
-
-    #[test]
-    #[should_panic(
-        expected = "Empty at [2, 2]. Any implementation must remove all Empty from the matrix."
-    )]
-    fn matrix_backtrack_fails_bad_cell_in_path() {
-        let container = vec![
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::L(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::T(0.0),
-            BackTrack::Empty,
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-        ];
-        let matrix = Matrix::from_vec(container, 4, 4);
-        BackTrack::backtracking(&matrix, 3, 3, f32::NEG_INFINITY);
-    }
-
-    #[test]
-    fn matrix_backtrack_multiple_paths() {
-        let test_cases = [
-            (
-                vec![
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::DT(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                ],
-                HashSet::from([
-                    vec![
-                        [6, 3],
-                        [5, 2],
-                        [4, 1],
-                        [4, 0],
-                        [3, 0],
-                        [2, 0],
-                        [1, 0],
-                        [0, 0],
-                    ],
-                    vec![[6, 3], [5, 2], [4, 2], [3, 2], [2, 2], [1, 1], [0, 0]],
-                ]),
-            ),
-            (
-                vec![
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::DL(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::DL(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                ],
-                HashSet::from([
-                    vec![[6, 3], [5, 2], [4, 1], [3, 1], [2, 1], [1, 0], [0, 0]],
-                    vec![
-                        [6, 3],
-                        [5, 2],
-                        [5, 1],
-                        [4, 0],
-                        [3, 0],
-                        [2, 0],
-                        [1, 0],
-                        [0, 0],
-                    ],
-                ]),
-            ),
-            (
-                vec![
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::TL(0.0),
-                ],
-                HashSet::from([
-                    vec![
-                        [6, 3],
-                        [5, 3],
-                        [4, 3],
-                        [3, 2],
-                        [2, 1],
-                        [2, 0],
-                        [1, 0],
-                        [0, 0],
-                    ],
-                    vec![
-                        [6, 3],
-                        [6, 2],
-                        [5, 1],
-                        [4, 1],
-                        [3, 0],
-                        [2, 0],
-                        [1, 0],
-                        [0, 0],
-                    ],
-                ]),
-            ),
-            (
-                vec![
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::L(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::All(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::T(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                    BackTrack::D(0.0),
-                ],
-                HashSet::from([
-                    vec![[6, 3], [5, 2], [4, 1], [3, 1], [2, 0], [1, 0], [0, 0]],
-                    vec![[6, 3], [5, 2], [4, 2], [3, 2], [2, 2], [1, 1], [0, 0]],
-                    vec![
-                        [6, 3],
-                        [5, 2],
-                        [5, 1],
-                        [4, 0],
-                        [3, 0],
-                        [2, 0],
-                        [1, 0],
-                        [0, 0],
-                    ],
-                ]),
-            ),
-        ];
-
-        let mut counter = 0;
-        for (container, expected_paths) in test_cases {
-            counter += 1;
-            let rows = 7;
-            let cols = 4;
-            let matrix = Matrix::from_vec(container, rows, cols);
-            let [init_row, init_col] = [rows - 1, cols - 1];
-            let actual_paths: HashSet<Vec<[usize; 2]>> =
-                BackTrack::backtracking(&matrix, init_row, init_col, f32::NEG_INFINITY)
-                    .into_iter()
-                    .collect();
-
-            // Didn't missed any path
-            let diff_missing: HashSet<_> =
-                expected_paths.difference(&actual_paths).collect();
-            // Didn't get an invalid path
-            let diff_extra: HashSet<_> =
-                actual_paths.difference(&expected_paths).collect();
-
-            assert!(
-                diff_missing.is_empty() && diff_extra.is_empty(),
-                "Error at test case {}.\n- Extra paths: {:?}.\n- Missing paths: {:?}.",
-                counter,
-                diff_extra,
-                diff_missing
-            );
-        }
-    }
-    #[test]
-    fn matrix_backtrack_nested_multiple_paths() {
-        let container = vec![
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::L(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::D(0.0),
-            BackTrack::D(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::T(0.0),
-            BackTrack::L(0.0),
-            BackTrack::T(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::T(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::DL(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::T(0.0),
-            BackTrack::T(0.0),
-            BackTrack::T(0.0),
-            BackTrack::D(0.0),
-            BackTrack::L(0.0),
-            BackTrack::TL(0.0),
-        ];
-
-        let expected_paths = HashSet::from([
-            vec![[6, 3], [5, 3], [4, 3], [3, 2], [2, 1], [1, 0], [0, 0]],
-            vec![
-                [6, 3],
-                [5, 3],
-                [4, 3],
-                [4, 2],
-                [4, 1],
-                [3, 0],
-                [2, 0],
-                [1, 0],
-                [0, 0],
-            ],
-            vec![
-                [6, 3],
-                [6, 2],
-                [6, 1],
-                [5, 0],
-                [4, 0],
-                [3, 0],
-                [2, 0],
-                [1, 0],
-                [0, 0],
-            ],
-        ]);
-
-        let matrix = Matrix::from_vec(container, 7, 4);
-        let [init_row, init_col] = [6, 3];
-        let actual_path: HashSet<Vec<[usize; 2]>> =
-            BackTrack::backtracking(&matrix, init_row, init_col, f32::NEG_INFINITY)
-                .into_iter()
-                .collect();
-
-        // Didn't missed any path
-        let diff_missing: HashSet<_> = expected_paths.difference(&actual_path).collect();
-        // Didn't get an invalid path
-        let diff_extra: HashSet<_> = actual_path.difference(&expected_paths).collect();
-
-        assert!(
-            diff_missing.is_empty() && diff_extra.is_empty(),
-            "- Extra paths: {:?}.\n- Missing paths: {:?}.",
-            diff_extra,
-            diff_missing
-        );
-    }
-
-    #[test]
-    fn matrix_backtrack_one_path_cutoff() {
-        let container = vec![
-            BackTrack::D(1.0),
-            BackTrack::L(1.0),
-            BackTrack::L(1.0),
-            BackTrack::L(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::D(1.0),
-            BackTrack::L(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::D(1.0),
-            BackTrack::L(1.0),
-            BackTrack::T(0.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::L(1.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::D(10.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::L(1.0),
-            BackTrack::T(1.0),
-            BackTrack::T(1.0),
-        ];
-        let matrix = Matrix::from_vec(container, 7, 4);
-
-        let [init_row, init_col] = [5, 2];
-        let actual_path = BackTrack::backtracking(&matrix, init_row, init_col, 0.0);
-        let expected_path = vec![vec![[5, 2], [4, 1], [3, 0]]];
-
+    fn backtrack_from_unreachable_scores() {
+        let unreachable = f32::NEG_INFINITY;
         assert_eq!(
-            expected_path, actual_path,
-            "Failed with starting cell [{init_row}, {init_col}]."
-        )
-    }
-
-    #[test]
-    fn matrix_backtrack_multiple_paths_cutoff() {
-        let container = vec![
-            BackTrack::D(1.0),
-            BackTrack::L(0.0),
-            BackTrack::L(1.0),
-            BackTrack::L(1.0),
-            BackTrack::T(0.0),
-            BackTrack::D(1.0),
-            BackTrack::D(1.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(10.0),
-            BackTrack::T(0.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::L(1.0),
-            BackTrack::DL(1.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::T(1.0),
-            BackTrack::T(10.0),
-            BackTrack::T(1.0),
-            BackTrack::D(1.0),
-            BackTrack::L(1.0),
-            BackTrack::TL(1.0),
-        ];
-        let matrix = Matrix::from_vec(container, 7, 4);
-
-        let expected_paths = HashSet::from([
-            vec![[5, 3], [4, 3], [3, 2], [2, 1], [1, 0]],
-            vec![[5, 3], [4, 3], [4, 2], [4, 1], [3, 0]],
-            vec![[2, 3], [1, 2], [0, 1]],
-        ]);
-
-        let mut actual_paths: HashSet<Vec<[usize; 2]>> =
-            BackTrack::backtracking(&matrix, 5, 3, 0.0)
-                .into_iter()
-                .collect();
-
-        actual_paths.extend(BackTrack::backtracking(&matrix, 2, 3, 0.0));
-
-        // Didn't missed any path
-        let diff_missing: HashSet<_> = expected_paths.difference(&actual_paths).collect();
-        // Didn't get an invalid path
-        let diff_extra: HashSet<_> = actual_paths.difference(&expected_paths).collect();
-
-        assert!(
-            diff_missing.is_empty() && diff_extra.is_empty(),
-            "- Extra paths: {:?}.\n- Missing paths: {:?}.",
-            diff_extra,
-            diff_missing
+            (BackTrack::Empty, unreachable),
+            BackTrack::make_backtrack(unreachable, unreachable, unreachable)
         );
+        assert_eq!(unreachable, BackTrack::Empty.score());
+        assert_eq!(BackTrack::Empty, BackTrack::Empty.rescore(1.0));
+        assert_eq!(BackTrack::DL(-3.0), BackTrack::DL(2.0).rescore(-3.0));
     }
 
     #[test]
@@ -975,4 +889,61 @@ fn alignment_sequence_top_gap() {
             )
         }
     }
+
+    #[test]
+    fn alignment_result_statistics() {
+        let sequence_left = Protein::new("KVGAHAGEYA").unwrap();
+        let sequence_top = Protein::new("WKIGGHGAEYGA").unwrap();
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, PenaltyKind::Affine(10.0, 1.0))
+                .unwrap();
+        // local alignment of left[1..10] and top[2..12]
+        let backtrack_path = vec![
+            [10, 12],
+            [9, 11],
+            [9, 10],
+            [8, 9],
+            [7, 8],
+            [6, 7],
+            [5, 6],
+            [4, 5],
+            [3, 4],
+            [2, 3],
+            [1, 2],
+        ];
+        let result = AlignmentResult::new(
+            backtrack_path,
+            &sequence_left,
+            &sequence_top,
+            21.0,
+            &scoring_schema,
+        );
+        assert_eq!(21.0, result.score());
+        assert_eq!([2, 3], result.begin());
+        assert_eq!([10, 12], result.end());
+        assert_eq!(10, result.length());
+        // V-I, A-G, A-G and G-A are mismatches; only V-I scores positive
+        assert_eq!(5, result.identities());
+        assert_eq!(6, result.positives());
+        assert_eq!(1, result.gaps());
+        assert_eq!(50.0, result.percent_identity());
+    }
+
+    #[test]
+    fn empty_alignment_result() {
+        let sequence = Protein::new("KVGAHAGEYA").unwrap();
+        let scoring_schema =
+            AaScoringSchema::new(AaScoringKind::Blosum62, PenaltyKind::Linear(1.0))
+                .unwrap();
+        let result = AlignmentResult::new(
+            vec![[0, 0]],
+            &sequence,
+            &sequence,
+            0.0,
+            &scoring_schema,
+        );
+        assert_eq!(([0, 0], [0, 0]), (result.begin(), result.end()));
+        assert_eq!(0, result.length());
+        assert_eq!(0.0, result.percent_identity());
+    }
 }
@@ -6,8 +6,9 @@
     fmt::{self, Debug},
 };
 
-/// IUPAC Amino acid codes. Represents the basic 20 amino acids.
-#[derive(Clone, Copy, PartialEq, Eq)]
+/// IUPAC Amino acid codes. Represents the basic 20 amino acids, the two genetically
+/// encoded non-standard amino acids, the ambiguity codes and the translation stop.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(test, derive(Debug, PartialOrd, Ord))]
 #[repr(u8)]
 pub enum Aac {
@@ -31,6 +32,20 @@ pub enum Aac {
     V,
     W,
     Y,
+    /// Aspartic acid or Asparagine
+    B,
+    /// Glutamic acid or Glutamine
+    Z,
+    /// Isoleucine or Leucine
+    J,
+    /// any amino acid
+    X,
+    /// Selenocysteine
+    U,
+    /// Pyrrolysine
+    O,
+    /// translation stop (`*`)
+    Stop,
 }
 
 impl Aac {
@@ -52,7 +67,8 @@ impl Aac {
     ///     mem::discriminant(&lys),
     ///     mem::discriminant(&Aac::from_char('L').unwrap())
     /// );
-    /// assert!(Aac::from_char('Z').is_err())
+    /// assert!(Aac::from_char('*').is_ok_and(|a| a == Aac::Stop));
+    /// assert!(Aac::from_char('-').is_err())
     /// ```
     pub fn from_char(char_code: char) -> Result<Self, SeqError> {
         if !char_code.is_ascii() {
@@ -86,6 +102,13 @@ fn char_mapping(char_code: char) -> Result<Self, SeqError> {
             'V' => Ok(Self::V),
             'W' => Ok(Self::W),
             'Y' => Ok(Self::Y),
+            'B' => Ok(Self::B),
+            'Z' => Ok(Self::Z),
+            'J' => Ok(Self::J),
+            'X' => Ok(Self::X),
+            'U' => Ok(Self::U),
+            'O' => Ok(Self::O),
+            '*' => Ok(Self::Stop),
             _ => Err(SeqError::new(ErrorKind::InvalidCode)),
         }
     }
@@ -93,6 +116,92 @@ fn char_mapping(char_code: char) -> Result<Self, SeqError> {
 
 impl AlignmentUnit for Aac {}
 
+/// IUPAC nucleotide codes. Represents the DNA (A, C, G, T) and RNA (A, C, G, U) bases, and
+/// the ambiguity codes for incompletely specified bases.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(Debug, PartialOrd, Ord))]
+#[repr(u8)]
+pub enum Nuc {
+    A,
+    C,
+    G,
+    T,
+    U,
+    /// A or G (purine)
+    R,
+    /// C or T (pyrimidine)
+    Y,
+    /// G or C (strong)
+    S,
+    /// A or T (weak)
+    W,
+    /// G or T (keto)
+    K,
+    /// A or C (amino)
+    M,
+    /// not A
+    B,
+    /// not C
+    D,
+    /// not G
+    H,
+    /// not T
+    V,
+    /// any base
+    N,
+}
+
+impl Nuc {
+    /// Creates a Nuc (nucleotide code) from a single character IUPAC code.
+    /// The function is case-insensitive. Returns SeqError if the character is not a valid code.
+    ///
+    /// # Arguments
+    /// + `char_code`: - A char representing a valid IUPAC code
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::*;
+    ///
+    /// assert!(Nuc::from_char('g').is_ok_and(|n| n == Nuc::G));
+    /// assert!(Nuc::from_char('U').is_ok_and(|n| n == Nuc::U));
+    /// assert!(Nuc::from_char('L').is_err())
+    /// ```
+    pub fn from_char(char_code: char) -> Result<Self, SeqError> {
+        if !char_code.is_ascii() {
+            return Err(SeqError::new(ErrorKind::NonAscii));
+        }
+        let char_code = char_code.to_ascii_uppercase();
+
+        Self::char_mapping(char_code)
+    }
+
+    // Contains the map between the valid char values nucleotide code and their enum representation
+    fn char_mapping(char_code: char) -> Result<Self, SeqError> {
+        match char_code {
+            'A' => Ok(Self::A),
+            'C' => Ok(Self::C),
+            'G' => Ok(Self::G),
+            'T' => Ok(Self::T),
+            'U' => Ok(Self::U),
+            'R' => Ok(Self::R),
+            'Y' => Ok(Self::Y),
+            'S' => Ok(Self::S),
+            'W' => Ok(Self::W),
+            'K' => Ok(Self::K),
+            'M' => Ok(Self::M),
+            'B' => Ok(Self::B),
+            'D' => Ok(Self::D),
+            'H' => Ok(Self::H),
+            'V' => Ok(Self::V),
+            'N' => Ok(Self::N),
+            _ => Err(SeqError::new(ErrorKind::InvalidCode)),
+        }
+    }
+}
+
+impl AlignmentUnit for Nuc {}
+
 /// Trait that allows to biological sequences to expose their content.
 pub trait HasSequence<T>
 where
@@ -129,7 +238,8 @@ impl Protein {
     ///         mem::discriminant(&expected_sequence[i])
     ///     );
     /// }
-    /// assert!(Protein::new("pBaGH").is_err())
+    /// assert!(Protein::new("pBaGH").is_ok());
+    /// assert!(Protein::new("p-aGH").is_err())
     /// ```
     pub fn new(string: &str) -> Result<Self, SeqError> {
         if string.is_empty() {
@@ -152,6 +262,94 @@ fn seq(&self) -> &Vec<Aac> {
     }
 }
 
+/// Representation of a DNA molecule.
+pub struct Dna {
+    /// Encodes the DNA primary structure.
+    sequence: Vec<Nuc>,
+}
+
+impl Dna {
+    /// Creates a Dna from a string. The function is case-insensitive.
+    /// Returns SeqError if the string contains non-valid IUPAC codes, including Uracil.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - a text containing valid IUPAC nucleotide code points. Only accepts ASCII characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::*;
+    /// let dna = Dna::new("gaTC").unwrap();
+    /// assert!(dna.seq() == &vec![Nuc::G, Nuc::A, Nuc::T, Nuc::C]);
+    /// assert!(Dna::new("GAUC").is_err())
+    /// ```
+    pub fn new(string: &str) -> Result<Self, SeqError> {
+        let sequence = nucleic_acid_from_str(string, Nuc::U)?;
+        Ok(Self { sequence })
+    }
+}
+
+impl HasSequence<Nuc> for Dna {
+    fn seq(&self) -> &Vec<Nuc> {
+        &self.sequence
+    }
+}
+
+/// Representation of a RNA molecule.
+pub struct Rna {
+    /// Encodes the RNA primary structure.
+    sequence: Vec<Nuc>,
+}
+
+impl Rna {
+    /// Creates a Rna from a string. The function is case-insensitive.
+    /// Returns SeqError if the string contains non-valid IUPAC codes, including Thymine.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - a text containing valid IUPAC nucleotide code points. Only accepts ASCII characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::*;
+    /// let rna = Rna::new("gaUC").unwrap();
+    /// assert!(rna.seq() == &vec![Nuc::G, Nuc::A, Nuc::U, Nuc::C]);
+    /// assert!(Rna::new("GATC").is_err())
+    /// ```
+    pub fn new(string: &str) -> Result<Self, SeqError> {
+        let sequence = nucleic_acid_from_str(string, Nuc::T)?;
+        Ok(Self { sequence })
+    }
+}
+
+impl HasSequence<Nuc> for Rna {
+    fn seq(&self) -> &Vec<Nuc> {
+        &self.sequence
+    }
+}
+
+// Reads a nucleic acid sequence skipping whitespaces. The excluded base is the one that does
+// not belong to the molecule (U for DNA, T for RNA).
+fn nucleic_acid_from_str(string: &str, excluded: Nuc) -> Result<Vec<Nuc>, SeqError> {
+    if string.is_empty() {
+        return Err(SeqError::new(ErrorKind::EmptyString));
+    }
+    let mut sequence: Vec<Nuc> = Vec::new();
+    for c in string.chars() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        let nucleotide = Nuc::from_char(c)?;
+        if nucleotide == excluded {
+            return Err(SeqError::new(ErrorKind::InvalidCode));
+        }
+        sequence.push(nucleotide)
+    }
+    Ok(sequence)
+}
+
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
 /// A list specifying general error categories of SeqError.
@@ -219,6 +417,23 @@ fn creates_protein_from_string() {
         );
     }
 
+    #[test]
+    fn creates_protein_with_non_standard_codes() {
+        assert_eq!(
+            Vec::from([
+                Aac::B,
+                Aac::Z,
+                Aac::J,
+                Aac::X,
+                Aac::U,
+                Aac::O,
+                Aac::M,
+                Aac::Stop,
+            ]),
+            Protein::new("bZjXuOM*").unwrap().sequence
+        );
+    }
+
     #[test]
     fn empty_string_to_protein() {
         assert!(Protein::new("").is_err_and(|e| e.kind == ErrorKind::EmptyString))
@@ -230,10 +445,52 @@ fn bad_string_to_protein() {
         assert!(Protein::new("VTVQＨKKLRT").is_err_and(|e| e.kind == ErrorKind::NonAscii));
         // contains non IUPAC code characters
         assert!(
-            Protein::new("VTVQBKKLRT").is_err_and(|e| e.kind == ErrorKind::InvalidCode)
+            Protein::new("VTVQ-KKLRT").is_err_and(|e| e.kind == ErrorKind::InvalidCode)
         )
     }
 
+    #[test]
+    fn creates_nucleic_acids_from_string() {
+        assert_eq!(
+            vec![Nuc::A, Nuc::C, Nuc::G, Nuc::T, Nuc::T, Nuc::A],
+            Dna::new("AcG\ntTa").unwrap().sequence
+        );
+        assert_eq!(
+            vec![Nuc::A, Nuc::C, Nuc::G, Nuc::U, Nuc::U, Nuc::A],
+            Rna::new("acg UUA").unwrap().sequence
+        );
+        // ambiguity codes
+        assert_eq!(
+            vec![
+                Nuc::R,
+                Nuc::Y,
+                Nuc::S,
+                Nuc::W,
+                Nuc::K,
+                Nuc::M,
+                Nuc::B,
+                Nuc::D,
+                Nuc::H,
+                Nuc::V,
+                Nuc::N
+            ],
+            Dna::new("RYSWKMBDHVn").unwrap().sequence
+        );
+    }
+
+    #[test]
+    fn bad_string_to_nucleic_acids() {
+        assert!(Dna::new("").is_err_and(|e| e.kind == ErrorKind::EmptyString));
+        assert!(Rna::new("").is_err_and(|e| e.kind == ErrorKind::EmptyString));
+        // Uracil is not a DNA base, and Thymine is not an RNA base
+        assert!(Dna::new("ACGU").is_err_and(|e| e.kind == ErrorKind::InvalidCode));
+        assert!(Rna::new("ACGT").is_err_and(|e| e.kind == ErrorKind::InvalidCode));
+        // Amino acid codes
+        assert!(Dna::new("ACGL").is_err_and(|e| e.kind == ErrorKind::InvalidCode));
+        assert!(Rna::new("ACG-U").is_err_and(|e| e.kind == ErrorKind::InvalidCode));
+        assert!(Rna::new("ACＧU").is_err_and(|e| e.kind == ErrorKind::NonAscii));
+    }
+
     #[test]
     fn read_sequence_from_external() {
         let protein: Protein = Protein::new("pVaGH").unwrap();
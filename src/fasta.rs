@@ -0,0 +1,402 @@
+//! Reads biological sequences stored in FASTA format.
+//!
+//! A FASTA text is a list of records. Each record starts with a header line beginning with
+//! '>', followed by the identifier and an optional description, and continues with one or
+//! more lines of residues until the next header. Blank lines and lines starting with ';'
+//! are ignored.
+
+use crate::bioseq::{Dna, Protein, Rna, SeqError};
+use std::io::{BufRead, Lines};
+use std::{error, fmt};
+
+/// A single FASTA record. The residues are kept as text until they are converted into a
+/// sequence type, so errors can point to the line and column of the offending residue.
+pub struct FastaRecord {
+    id: String,
+    description: String,
+    // sequence lines and their 1-based line numbers
+    lines: Vec<(usize, String)>,
+    header_line: usize,
+}
+
+impl FastaRecord {
+    /// The first word of the header.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The rest of the header after the identifier. Empty if the header only has an
+    /// identifier.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// 1-based line number of the record header.
+    pub fn header_line(&self) -> usize {
+        self.header_line
+    }
+
+    /// The residues of the record, without line breaks or whitespaces.
+    pub fn sequence(&self) -> String {
+        self.lines
+            .iter()
+            .flat_map(|(_, line)| line.chars())
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect()
+    }
+
+    /// Creates a Protein from the record residues.
+    /// Returns FastaError with the position of the first invalid residue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::HasSequence;
+    /// use pairwasm_alignment::fasta::FastaReader;
+    ///
+    /// let text = ">sp|P69905|HBA_HUMAN Hemoglobin subunit alpha\nMVLSPADK\nTNVKAAWG\n";
+    /// let record = FastaReader::new(text.as_bytes()).next().unwrap().unwrap();
+    /// assert_eq!("sp|P69905|HBA_HUMAN", record.id());
+    /// assert_eq!(16, record.protein().unwrap().seq().len());
+    /// ```
+    pub fn protein(&self) -> Result<Protein, FastaError> {
+        self.build(Protein::new)
+    }
+
+    /// Creates a Dna from the record residues.
+    /// Returns FastaError with the position of the first invalid residue.
+    pub fn dna(&self) -> Result<Dna, FastaError> {
+        self.build(Dna::new)
+    }
+
+    /// Creates a Rna from the record residues.
+    /// Returns FastaError with the position of the first invalid residue.
+    pub fn rna(&self) -> Result<Rna, FastaError> {
+        self.build(Rna::new)
+    }
+
+    // Builds the whole sequence at once. Only when that fails, looks for the residue that
+    // caused the error, first by line and then by column.
+    fn build<T>(
+        &self,
+        constructor: fn(&str) -> Result<T, SeqError>,
+    ) -> Result<T, FastaError> {
+        let error = match constructor(&self.sequence()) {
+            Ok(sequence) => return Ok(sequence),
+            Err(error) => error,
+        };
+        for (line_number, line) in &self.lines {
+            if constructor(line).is_ok() {
+                continue;
+            }
+            for (column, residue) in line.char_indices() {
+                if residue.is_ascii_whitespace() {
+                    continue;
+                }
+                if let Err(error) =
+                    constructor(&line[column..column + residue.len_utf8()])
+                {
+                    let column = line[..column].chars().count() + 1;
+                    return Err(FastaError::new(
+                        FastaErrorKind::InvalidResidue,
+                        *line_number,
+                        column,
+                        &error.to_string(),
+                    ));
+                }
+            }
+        }
+        Err(FastaError::new(
+            FastaErrorKind::InvalidResidue,
+            self.header_line,
+            0,
+            &error.to_string(),
+        ))
+    }
+}
+
+/// Streaming reader of FASTA records. Yields the records one at a time, so the whole text
+/// does not need to be in memory.
+pub struct FastaReader<R: BufRead> {
+    lines: Lines<R>,
+    line_number: usize,
+    // header line already read, belonging to the next record
+    next_header: Option<(usize, String)>,
+    failed: bool,
+}
+
+impl<R: BufRead> FastaReader<R> {
+    /// Creates a FastaReader over any buffered input: a file, the standard input, or a
+    /// byte slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - the source of the FASTA text.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_number: 0,
+            next_header: None,
+            failed: false,
+        }
+    }
+
+    // Returns the next meaningful line with its line number, skipping blank and comment lines.
+    fn next_line(&mut self) -> Option<Result<(usize, String), FastaError>> {
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => {
+                    return Some(Err(FastaError::new(
+                        FastaErrorKind::Io,
+                        self.line_number,
+                        0,
+                        &error.to_string(),
+                    )))
+                }
+            };
+            if line.trim().is_empty() || line.starts_with(';') {
+                continue;
+            }
+            return Some(Ok((self.line_number, line)));
+        }
+        None
+    }
+
+    fn read_record(&mut self) -> Option<Result<FastaRecord, FastaError>> {
+        let (header_line, header) = match self.next_header.take() {
+            Some(header) => header,
+            None => match self.next_line()? {
+                Ok((line_number, line)) if line.starts_with('>') => (line_number, line),
+                Ok((line_number, _)) => {
+                    return Some(Err(FastaError::new(
+                        FastaErrorKind::MissingHeader,
+                        line_number,
+                        1,
+                        "",
+                    )))
+                }
+                Err(error) => return Some(Err(error)),
+            },
+        };
+        let header = header[1..].trim();
+        let (id, description) = match header.split_once(char::is_whitespace) {
+            Some((id, description)) => (id.to_string(), description.trim().to_string()),
+            None => (header.to_string(), String::new()),
+        };
+
+        let mut lines: Vec<(usize, String)> = Vec::new();
+        while let Some(line) = self.next_line() {
+            match line {
+                Ok((line_number, line)) if line.starts_with('>') => {
+                    self.next_header = Some((line_number, line));
+                    break;
+                }
+                Ok(line) => lines.push(line),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        if lines.is_empty() {
+            return Some(Err(FastaError::new(
+                FastaErrorKind::EmptyRecord,
+                header_line,
+                0,
+                &id,
+            )));
+        }
+
+        Some(Ok(FastaRecord {
+            id,
+            description,
+            lines,
+            header_line,
+        }))
+    }
+}
+
+impl<R: BufRead> Iterator for FastaReader<R> {
+    type Item = Result<FastaRecord, FastaError>;
+
+    /// Reads the next record. After the first error the reader stops yielding records.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let record = self.read_record();
+        self.failed = matches!(record, Some(Err(_)));
+        record
+    }
+}
+
+/// Reads all the records of a FASTA text as proteins, keeping their identifiers.
+///
+/// # Arguments
+///
+/// * `reader` - the source of the FASTA text.
+pub fn read_proteins<R: BufRead>(
+    reader: R,
+) -> Result<Vec<(String, Protein)>, FastaError> {
+    FastaReader::new(reader)
+        .map(|record| {
+            let record = record?;
+            let protein = record.protein()?;
+            Ok((record.id, protein))
+        })
+        .collect()
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of FastaError.
+pub enum FastaErrorKind {
+    EmptyRecord,
+    InvalidResidue,
+    Io,
+    MissingHeader,
+}
+
+#[derive(Debug)]
+/// Error type for FASTA parsing. Keeps the 1-based line and column where the error was
+/// found; the column is 0 when the error concerns the whole line or record.
+pub struct FastaError {
+    kind: FastaErrorKind,
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl FastaError {
+    fn new(kind: FastaErrorKind, line: usize, column: usize, detail: &str) -> Self {
+        let mut message: String = match kind {
+            FastaErrorKind::EmptyRecord => "The record has no sequence.".to_string(),
+            FastaErrorKind::InvalidResidue => {
+                "The sequence contains an invalid residue.".to_string()
+            }
+            FastaErrorKind::Io => "The input could not be read.".to_string(),
+            FastaErrorKind::MissingHeader => {
+                "The sequence must start with a '>' header line.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+
+        Self {
+            kind,
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub fn kind(&self) -> &FastaErrorKind {
+        &self.kind
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for FastaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({:?}) line {}, column {}: {}",
+            self.kind, self.line, self.column, self.message
+        )
+    }
+}
+
+impl error::Error for FastaError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bioseq::{Aac, HasSequence, Nuc};
+
+    const MULTI_RECORD: &str = "\
+; comment line
+>seq1 first test protein
+MNGT
+EGPN
+
+>seq2
+FYVP
+>seq3 a nucleotide
+ACGT
+AC
+";
+
+    #[test]
+    fn reads_multiple_records() {
+        let records: Vec<FastaRecord> = FastaReader::new(MULTI_RECORD.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(3, records.len());
+        assert_eq!("seq1", records[0].id());
+        assert_eq!("first test protein", records[0].description());
+        assert_eq!(2, records[0].header_line());
+        assert_eq!("MNGTEGPN", records[0].sequence());
+        assert_eq!("seq2", records[1].id());
+        assert_eq!("", records[1].description());
+        assert_eq!(
+            &vec![Aac::F, Aac::Y, Aac::V, Aac::P],
+            records[1].protein().unwrap().seq()
+        );
+        assert_eq!(
+            &vec![Nuc::A, Nuc::C, Nuc::G, Nuc::T, Nuc::A, Nuc::C],
+            records[2].dna().unwrap().seq()
+        );
+    }
+
+    #[test]
+    fn reads_windows_line_endings() {
+        let proteins =
+            read_proteins(">a\r\nMN\r\nGT\r\n>b\r\nPV\r\n".as_bytes()).unwrap();
+        assert_eq!(2, proteins.len());
+        assert_eq!("a", proteins[0].0);
+        assert_eq!(&vec![Aac::M, Aac::N, Aac::G, Aac::T], proteins[0].1.seq());
+    }
+
+    #[test]
+    fn reports_invalid_residue_position() {
+        let text = ">seq1\nMNGT\nEG-N\n";
+        let record = FastaReader::new(text.as_bytes()).next().unwrap().unwrap();
+        let error = record.protein().err().unwrap();
+        assert_eq!(&FastaErrorKind::InvalidResidue, error.kind());
+        assert_eq!((3, 3), (error.line(), error.column()));
+
+        // U is a valid code, but not for DNA
+        let record = FastaReader::new(">x\nAC GU\n".as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        let error = record.dna().err().unwrap();
+        assert_eq!((2, 5), (error.line(), error.column()));
+        assert!(record.rna().is_ok());
+    }
+
+    #[test]
+    fn bad_fasta_structure() {
+        let error = FastaReader::new("MNGT\n".as_bytes()).next().unwrap();
+        assert!(
+            error.is_err_and(|e| e.kind == FastaErrorKind::MissingHeader && e.line == 1)
+        );
+
+        let mut reader = FastaReader::new(">a\n>b\nMN\n".as_bytes());
+        let error = reader.next().unwrap();
+        assert!(
+            error.is_err_and(|e| e.kind == FastaErrorKind::EmptyRecord && e.line == 1)
+        );
+        assert!(reader.next().is_none());
+
+        assert!(FastaReader::new("\n\n".as_bytes()).next().is_none());
+    }
+}
@@ -1,7 +1,10 @@
 //! Deals with the output format
 
-use crate::aligner::utils::AlignmentSequence;
-use crate::bioseq::Aac;
+use crate::aligner::progressive::MultipleAlignment;
+use crate::aligner::search::SearchResult;
+use crate::aligner::utils::{AlignmentResult, AlignmentSequence};
+use crate::bioseq::{Aac, Nuc};
+use crate::scoring_schema::profile::ProfileUnit;
 use crate::utils::AlignmentUnit;
 use std::cmp::PartialEq;
 use std::convert::From;
@@ -35,10 +38,97 @@ fn from(val: &Aac) -> Self {
             Aac::V => 'V',
             Aac::W => 'W',
             Aac::Y => 'Y',
+            Aac::B => 'B',
+            Aac::Z => 'Z',
+            Aac::J => 'J',
+            Aac::X => 'X',
+            Aac::U => 'U',
+            Aac::O => 'O',
+            Aac::Stop => '*',
         }
     }
 }
 
+impl From<&Nuc> for char {
+    fn from(val: &Nuc) -> Self {
+        match val {
+            Nuc::A => 'A',
+            Nuc::C => 'C',
+            Nuc::G => 'G',
+            Nuc::T => 'T',
+            Nuc::U => 'U',
+            Nuc::R => 'R',
+            Nuc::Y => 'Y',
+            Nuc::S => 'S',
+            Nuc::W => 'W',
+            Nuc::K => 'K',
+            Nuc::M => 'M',
+            Nuc::B => 'B',
+            Nuc::D => 'D',
+            Nuc::H => 'H',
+            Nuc::V => 'V',
+            Nuc::N => 'N',
+        }
+    }
+}
+
+// A profile column is written as its consensus residue.
+impl From<&ProfileUnit> for char {
+    fn from(val: &ProfileUnit) -> Self {
+        char::from(&val.residue())
+    }
+}
+
+impl<A> AlignmentSequence<A>
+where
+    A: AlignmentUnit,
+    char: for<'a> From<&'a A>,
+{
+    /// Both rows of the alignment as text, [left, top]. Gaps are written as '-'.
+    pub fn aligned_strings(&self) -> [String; 2] {
+        let mut rows = [
+            String::with_capacity(self.read().len()),
+            String::with_capacity(self.read().len()),
+        ];
+        for pair in self.read() {
+            for (row, unit) in rows.iter_mut().zip(pair) {
+                row.push(unit.as_ref().map_or('-', char::from));
+            }
+        }
+        rows
+    }
+
+    /// CIGAR string of the alignment, taking the left sequence as the reference: M for
+    /// aligned pairs (match or mismatch), I for residues only present at the top sequence
+    /// and D for residues only present at the left sequence.
+    pub fn cigar(&self) -> String {
+        let mut cigar = String::new();
+        let mut last: Option<(char, usize)> = None;
+        for pair in self.read() {
+            let operation = match pair {
+                [Some(_), Some(_)] => 'M',
+                [None, Some(_)] => 'I',
+                [Some(_), None] => 'D',
+                [None, None] => continue,
+            };
+            last = match last {
+                Some((previous, count)) if previous == operation => {
+                    Some((operation, count + 1))
+                }
+                Some((previous, count)) => {
+                    cigar.push_str(&format!("{}{}", count, previous));
+                    Some((operation, 1))
+                }
+                None => Some((operation, 1)),
+            };
+        }
+        if let Some((operation, count)) = last {
+            cigar.push_str(&format!("{}{}", count, operation));
+        }
+        cigar
+    }
+}
+
 impl<A> Display for AlignmentSequence<A>
 where
     A: AlignmentUnit + PartialEq,
@@ -88,3 +178,251 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{}\n{}\n{}", line1, line2, line3))
     }
 }
+
+impl<A> Display for AlignmentResult<A>
+where
+    A: AlignmentUnit + PartialEq,
+    char: for<'a> From<&'a A>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let length = self.length();
+        // percentage of the alignment length
+        let percent = |count: usize| {
+            if length == 0 {
+                0.0
+            } else {
+                100.0 * count as f32 / length as f32
+            }
+        };
+        writeln!(f, "Score: {}", self.score())?;
+        if let (Some(bit_score), Some(e_value)) = (self.bit_score(), self.e_value()) {
+            writeln!(f, "Bit score: {:.1}", bit_score)?;
+            writeln!(f, "E-value: {:.2e}", e_value)?;
+        }
+        writeln!(f, "Length: {}", length)?;
+        writeln!(
+            f,
+            "Identity: {}/{} ({:.1}%)",
+            self.identities(),
+            length,
+            self.percent_identity()
+        )?;
+        writeln!(
+            f,
+            "Positives: {}/{} ({:.1}%)",
+            self.positives(),
+            length,
+            percent(self.positives())
+        )?;
+        writeln!(
+            f,
+            "Gaps: {}/{} ({:.1}%)",
+            self.gaps(),
+            length,
+            percent(self.gaps())
+        )?;
+        let [begin_1, begin_2] = self.begin();
+        let [end_1, end_2] = self.end();
+        writeln!(f, "Sequence 1: {}-{}", begin_1, end_1)?;
+        writeln!(f, "Sequence 2: {}-{}", begin_2, end_2)?;
+        if self.touches_band_edge() {
+            writeln!(
+                f,
+                "Band edge: touched, a wider band may find a better alignment"
+            )?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.alignment())
+    }
+}
+
+impl<A> MultipleAlignment<A>
+where
+    A: AlignmentUnit,
+    char: for<'a> From<&'a A>,
+{
+    /// The rows of the alignment as text. Gaps are written as '-'.
+    pub fn aligned_strings(&self) -> Vec<String> {
+        self.rows()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|unit| unit.as_ref().map_or('-', char::from))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The alignment in FASTA format, with 60 columns per line.
+    pub fn to_fasta(&self) -> String {
+        let mut text = String::new();
+        for (id, row) in self.ids().iter().zip(self.aligned_strings()) {
+            text.push('>');
+            text.push_str(id);
+            text.push('\n');
+            let row: Vec<char> = row.chars().collect();
+            for line in row.chunks(60) {
+                text.extend(line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+// Blocks of 50 columns with the identifier before each row, followed by a line that marks
+// with MATCH_STR the columns where every row has the same unit.
+impl<A> Display for MultipleAlignment<A>
+where
+    A: AlignmentUnit,
+    char: for<'a> From<&'a A>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id_width = self
+            .ids()
+            .iter()
+            .map(|id| id.chars().count())
+            .max()
+            .unwrap_or(0);
+        let rows: Vec<Vec<char>> = self
+            .aligned_strings()
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let conserved: Vec<char> = (0..self.width())
+            .map(|column| {
+                let first = self.rows()[0][column];
+                match first.is_some()
+                    && self.rows().iter().all(|row| row[column] == first)
+                {
+                    true => MATCH_STR,
+                    false => SPACE_STR,
+                }
+            })
+            .collect();
+        for start in (0..self.width()).step_by(50) {
+            if start > 0 {
+                writeln!(f)?;
+            }
+            let end = (start + 50).min(self.width());
+            for (id, row) in self.ids().iter().zip(&rows) {
+                let line: String = row[start..end].iter().collect();
+                writeln!(f, "{:id_width$}  {}", id, line)?;
+            }
+            let line: String = conserved[start..end].iter().collect();
+            writeln!(f, "{:id_width$}  {}", "", line)?;
+        }
+        Ok(())
+    }
+}
+
+// A summary of the search and the hit table, followed by the alignment of each hit under
+// its FASTA header.
+impl Display for SearchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Query length: {}", self.query_length())?;
+        writeln!(
+            f,
+            "Library: {} records, {} residues",
+            self.searched_records(),
+            self.searched_residues()
+        )?;
+        writeln!(f, "Hits: {}", self.hits().len())?;
+        write!(f, "\n{}", self.hit_table())?;
+        for hit in self.hits() {
+            writeln!(f)?;
+            match hit.description().is_empty() {
+                true => writeln!(f, ">{}", hit.id())?,
+                false => writeln!(f, ">{} {}", hit.id(), hit.description())?,
+            }
+            writeln!(f, "Length: {}", hit.length())?;
+            writeln!(f, "{}", hit.alignment())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aligner::progressive::ProgressiveAligner;
+    use crate::aligner::search::{DatabaseSearch, SearchSettings};
+    use crate::bioseq::Protein;
+    use crate::scoring_schema::aminoacid_schema::AaScoringKind;
+    use crate::scoring_schema::gap_penalty::PenaltyKind;
+    use crate::tree::TreeMethod;
+
+    #[test]
+    fn aligned_strings_and_cigar() {
+        let sequence_left = Protein::new("KVGAHAGEYA").unwrap();
+        let sequence_top = Protein::new("KIGGHGAEYGA").unwrap();
+        let backtrack_path = vec![
+            [10, 11],
+            [9, 10],
+            [9, 9],
+            [8, 8],
+            [7, 7],
+            [6, 6],
+            [5, 5],
+            [4, 4],
+            [3, 3],
+            [2, 2],
+            [1, 1],
+            [0, 0],
+        ];
+        let alignment =
+            AlignmentSequence::new(backtrack_path, &sequence_left, &sequence_top);
+        assert_eq!(
+            ["KVGAHAGEY-A".to_string(), "KIGGHGAEYGA".to_string()],
+            alignment.aligned_strings()
+        );
+        assert_eq!("9M1I1M", alignment.cigar());
+
+        let backtrack_path = vec![[3, 1], [2, 1], [1, 1], [0, 0]];
+        let alignment =
+            AlignmentSequence::new(backtrack_path, &sequence_left, &sequence_top);
+        assert_eq!("1M2D", alignment.cigar());
+    }
+
+    #[test]
+    fn multiple_alignment_text() {
+        let aligner = ProgressiveAligner::new(
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            TreeMethod::Upgma,
+        )
+        .unwrap();
+        let records = [
+            ("first", Protein::new("HGEYWAKLV").unwrap()),
+            ("second", Protein::new("HGEFWKLV").unwrap()),
+        ];
+        let alignment = aligner.align(&records).unwrap();
+        assert_eq!(
+            ">first\nHGEYWAKLV\n>second\nHGEFW-KLV\n",
+            alignment.to_fasta()
+        );
+        assert_eq!(
+            "first   HGEYWAKLV\nsecond  HGEFW-KLV\n        ||| | |||\n",
+            format!("{}", alignment)
+        );
+    }
+
+    #[test]
+    fn search_result_text() {
+        let search = DatabaseSearch::new(
+            Protein::new("HGEYWAKLV").unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            SearchSettings::default(),
+        )
+        .unwrap();
+        let library = ">noise\nPPPPPPPP\n>exact some protein\nMKTHGEYWAKLVRRA\n";
+        let result = search.run(library.as_bytes()).unwrap();
+        let text = format!("{}", result);
+        assert!(text.starts_with(
+            "Query length: 9\nLibrary: 2 records, 23 residues\nHits: 1\n\nid\tscore"
+        ));
+        assert!(text.contains("\n>exact some protein\nLength: 15\nScore: 54\n"));
+        assert!(text.ends_with(&format!("{}\n", result.hits()[0].alignment())));
+    }
+}
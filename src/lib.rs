@@ -1,21 +1,764 @@
 pub mod aligner;
 pub mod bioseq;
+pub mod fasta;
 pub mod formatter;
 pub mod matrix;
 pub mod scoring_schema;
+pub mod statistics;
+pub mod tree;
 mod utils;
 
 #[cfg(test)]
 pub mod tests;
 
-use aligner::AlignerKind;
-use scoring_schema::{aminoacid_schema::AaScoringKind, gap_penalty::PenaltyKind};
+use aligner::distance::{AllVersusAll, DistanceSettings};
+use aligner::progressive::{MultipleAlignment, ProgressiveAligner};
+use aligner::search::{DatabaseSearch, HitRanking, SearchResult, SearchSettings};
+use aligner::{AlignerKind, Band, EndGaps};
+use scoring_schema::aminoacid_schema::{similarity_builder, AaScoringKind};
+use scoring_schema::diagnostics::MatrixDiagnostics;
+use scoring_schema::gap_penalty::{
+    PenaltyKind, MAX_EXTEND_COST, MAX_OPEN_COST, MIN_EXTEND_COST, MIN_OPEN_COST,
+};
+use scoring_schema::substitution_matrix::SubstitutionMatrix;
+use statistics::ROBINSON_FREQUENCIES;
 use std::{error, fmt};
+use tree::{Tree, TreeMethod};
 use utils::set_panic_hook;
 use wasm_bindgen::prelude::*;
 
-use crate::bioseq::Protein;
+use crate::aligner::utils::{AlignmentResult, AlignmentScore};
+use crate::bioseq::{Aac, Protein};
+use crate::fasta::FastaReader;
 
+/// Substitution matrices available from JS.
+#[wasm_bindgen(js_name = AaScoringKind)]
+#[derive(Clone, Copy)]
+pub enum JsAaScoringKind {
+    Blosum45 = 1,
+    Blosum62 = 2,
+    Pam160 = 3,
+    Blosum30 = 4,
+    Blosum35 = 5,
+    Blosum40 = 6,
+    Blosum50 = 7,
+    Blosum55 = 8,
+    Blosum60 = 9,
+    Blosum65 = 10,
+    Blosum70 = 11,
+    Blosum75 = 12,
+    Blosum80 = 13,
+    Blosum85 = 14,
+    Blosum90 = 15,
+    Blosum100 = 16,
+    Pam30 = 17,
+    Pam70 = 18,
+    Pam120 = 19,
+    Pam250 = 20,
+}
+
+/// Alignment algorithms available from JS.
+#[wasm_bindgen(js_name = AlignerKind)]
+#[derive(Clone, Copy)]
+pub enum JsAlignerKind {
+    SmithWaterman = 1,
+    NeedlemanWunsch = 2,
+    SemiGlobal = 3,
+}
+
+/// Gap penalty models available from JS.
+#[wasm_bindgen(js_name = PenaltyKind)]
+#[derive(Clone, Copy)]
+pub enum JsPenaltyKind {
+    Affine = 1,
+    Linear = 2,
+}
+
+/// Tree methods of multiple_protein_alignment and protein_tree.
+#[wasm_bindgen(js_name = TreeMethod)]
+#[derive(Clone, Copy)]
+pub enum JsTreeMethod {
+    Upgma = 1,
+    NeighborJoining = 2,
+}
+
+impl From<JsTreeMethod> for TreeMethod {
+    fn from(kind: JsTreeMethod) -> Self {
+        match kind {
+            JsTreeMethod::Upgma => TreeMethod::Upgma,
+            JsTreeMethod::NeighborJoining => TreeMethod::NeighborJoining,
+        }
+    }
+}
+
+impl From<JsAaScoringKind> for AaScoringKind {
+    fn from(kind: JsAaScoringKind) -> Self {
+        match kind {
+            JsAaScoringKind::Blosum45 => AaScoringKind::Blosum45,
+            JsAaScoringKind::Blosum62 => AaScoringKind::Blosum62,
+            JsAaScoringKind::Pam160 => AaScoringKind::Pam160,
+            JsAaScoringKind::Blosum30 => AaScoringKind::Blosum30,
+            JsAaScoringKind::Blosum35 => AaScoringKind::Blosum35,
+            JsAaScoringKind::Blosum40 => AaScoringKind::Blosum40,
+            JsAaScoringKind::Blosum50 => AaScoringKind::Blosum50,
+            JsAaScoringKind::Blosum55 => AaScoringKind::Blosum55,
+            JsAaScoringKind::Blosum60 => AaScoringKind::Blosum60,
+            JsAaScoringKind::Blosum65 => AaScoringKind::Blosum65,
+            JsAaScoringKind::Blosum70 => AaScoringKind::Blosum70,
+            JsAaScoringKind::Blosum75 => AaScoringKind::Blosum75,
+            JsAaScoringKind::Blosum80 => AaScoringKind::Blosum80,
+            JsAaScoringKind::Blosum85 => AaScoringKind::Blosum85,
+            JsAaScoringKind::Blosum90 => AaScoringKind::Blosum90,
+            JsAaScoringKind::Blosum100 => AaScoringKind::Blosum100,
+            JsAaScoringKind::Pam30 => AaScoringKind::Pam30,
+            JsAaScoringKind::Pam70 => AaScoringKind::Pam70,
+            JsAaScoringKind::Pam120 => AaScoringKind::Pam120,
+            JsAaScoringKind::Pam250 => AaScoringKind::Pam250,
+        }
+    }
+}
+
+impl JsAlignerKind {
+    fn with_end_gaps(self, free_end_gaps: Option<u8>) -> AlignerKind {
+        match self {
+            JsAlignerKind::SmithWaterman => AlignerKind::SmithWaterman,
+            JsAlignerKind::NeedlemanWunsch => AlignerKind::NeedlemanWunsch,
+            JsAlignerKind::SemiGlobal => {
+                AlignerKind::SemiGlobal(end_gaps_from_flags(free_end_gaps.unwrap_or(0)))
+            }
+        }
+    }
+}
+
+impl JsPenaltyKind {
+    fn with_costs(self, open_cost: f32, extend_cost: f32) -> PenaltyKind {
+        match self {
+            JsPenaltyKind::Affine => PenaltyKind::Affine(open_cost, extend_cost),
+            JsPenaltyKind::Linear => PenaltyKind::Linear(extend_cost),
+        }
+    }
+}
+
+/// An alignment and its statistics, as returned to JS.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Alignment {
+    aligned_sequences: [String; 2],
+    score: f32,
+    begin: [usize; 2],
+    end: [usize; 2],
+    length: usize,
+    identities: usize,
+    positives: usize,
+    gaps: usize,
+    percent_identity: f32,
+    cigar: String,
+    touches_band_edge: bool,
+    bit_score: Option<f64>,
+    e_value: Option<f64>,
+    text: String,
+}
+
+#[wasm_bindgen]
+impl Alignment {
+    /// The first sequence with its gaps written as '-'.
+    #[wasm_bindgen(getter)]
+    pub fn aligned_sequence_1(&self) -> String {
+        self.aligned_sequences[0].clone()
+    }
+
+    /// The second sequence with its gaps written as '-'.
+    #[wasm_bindgen(getter)]
+    pub fn aligned_sequence_2(&self) -> String {
+        self.aligned_sequences[1].clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// 1-based position of the first aligned residue of the first sequence.
+    #[wasm_bindgen(getter)]
+    pub fn begin_1(&self) -> usize {
+        self.begin[0]
+    }
+
+    /// 1-based position of the last aligned residue of the first sequence.
+    #[wasm_bindgen(getter)]
+    pub fn end_1(&self) -> usize {
+        self.end[0]
+    }
+
+    /// 1-based position of the first aligned residue of the second sequence.
+    #[wasm_bindgen(getter)]
+    pub fn begin_2(&self) -> usize {
+        self.begin[1]
+    }
+
+    /// 1-based position of the last aligned residue of the second sequence.
+    #[wasm_bindgen(getter)]
+    pub fn end_2(&self) -> usize {
+        self.end[1]
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn identities(&self) -> usize {
+        self.identities
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn positives(&self) -> usize {
+        self.positives
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn gaps(&self) -> usize {
+        self.gaps
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn percent_identity(&self) -> f32 {
+        self.percent_identity
+    }
+
+    /// CIGAR string taking the first sequence as the reference.
+    #[wasm_bindgen(getter)]
+    pub fn cigar(&self) -> String {
+        self.cigar.clone()
+    }
+
+    /// Whether a banded alignment reached the band edge, see banded_protein_alignment.
+    #[wasm_bindgen(getter)]
+    pub fn touches_band_edge(&self) -> bool {
+        self.touches_band_edge
+    }
+
+    /// Local alignments only, normalised score in bits. It is comparable across
+    /// substitution matrices. Undefined if the matrix and gap costs have no known
+    /// statistical parameters.
+    #[wasm_bindgen(getter)]
+    pub fn bit_score(&self) -> Option<f64> {
+        self.bit_score
+    }
+
+    /// Local alignments only, expected number of alignments with this score between random
+    /// sequences of the same lengths. Undefined as bit_score.
+    #[wasm_bindgen(getter)]
+    pub fn e_value(&self) -> Option<f64> {
+        self.e_value
+    }
+
+    /// The same text returned by do_protein_alignment.
+    pub fn to_text(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl From<AlignmentResult<Aac>> for Alignment {
+    fn from(result: AlignmentResult<Aac>) -> Self {
+        Self {
+            aligned_sequences: result.alignment().aligned_strings(),
+            score: result.score(),
+            begin: result.begin(),
+            end: result.end(),
+            length: result.length(),
+            identities: result.identities(),
+            positives: result.positives(),
+            gaps: result.gaps(),
+            percent_identity: result.percent_identity(),
+            cigar: result.alignment().cigar(),
+            touches_band_edge: result.touches_band_edge(),
+            bit_score: result.bit_score(),
+            e_value: result.e_value(),
+            text: format!("{}", result),
+        }
+    }
+}
+
+/// Aligns two protein sequences.
+///
+/// * `open_cost`: ignored by the linear gap model.
+/// * `free_end_gaps`: only for the semi-global algorithm. Sum of the flags of the terminal
+///   gaps that are not penalised: 1 leading and 2 trailing gaps at the first sequence, 4
+///   leading and 8 trailing gaps at the second sequence. If omitted, none is free.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn protein_alignment(
+    string_1: &str,
+    string_2: &str,
+    algorithm: JsAlignerKind,
+    substitution_matrix: JsAaScoringKind,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    free_end_gaps: Option<u8>,
+) -> Result<Alignment, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let result = align_proteins(
+        Protein::new(string_1)?,
+        Protein::new(string_2)?,
+        algorithm.with_end_gaps(free_end_gaps),
+        substitution_matrix.into(),
+        penalty.with_costs(open_cost, extend_cost),
+        None,
+    )?;
+    Ok(result.into())
+}
+
+/// Substitution matrix read from the NCBI/EMBOSS text format, for
+/// protein_alignment_custom_matrix.
+#[wasm_bindgen(js_name = SubstitutionMatrix)]
+pub struct JsSubstitutionMatrix(SubstitutionMatrix);
+
+#[wasm_bindgen(js_class = SubstitutionMatrix)]
+impl JsSubstitutionMatrix {
+    /// Reads the text of a matrix file, see SubstitutionMatrix::from_ncbi_text. Throws if
+    /// the matrix is incomplete, not symmetric or malformed.
+    #[wasm_bindgen(constructor)]
+    pub fn new(text: &str) -> Result<JsSubstitutionMatrix, JsError> {
+        Ok(Self(SubstitutionMatrix::from_ncbi_text(text)?))
+    }
+
+    /// The matrix in the NCBI text format.
+    pub fn to_text(&self) -> String {
+        self.0.to_ncbi_text("")
+    }
+}
+
+/// Properties of a substitution matrix under the background frequencies of Robinson &
+/// Robinson (1991), as returned to JS by matrix_diagnostics.
+#[wasm_bindgen(js_name = MatrixDiagnostics)]
+pub struct JsMatrixDiagnostics(MatrixDiagnostics);
+
+#[wasm_bindgen(js_class = MatrixDiagnostics)]
+impl JsMatrixDiagnostics {
+    /// The λ that turns a score into its log-odds in nats.
+    #[wasm_bindgen(getter)]
+    pub fn lambda(&self) -> f64 {
+        self.0.lambda()
+    }
+
+    /// Relative entropy, in bits per aligned pair.
+    #[wasm_bindgen(getter)]
+    pub fn relative_entropy(&self) -> f64 {
+        self.0.relative_entropy()
+    }
+
+    /// Expected score of a random pair, in the units of the matrix.
+    #[wasm_bindgen(getter)]
+    pub fn expected_score(&self) -> f64 {
+        self.0.expected_score()
+    }
+
+    /// Units of the matrix per bit.
+    #[wasm_bindgen(getter)]
+    pub fn bit_scale(&self) -> f64 {
+        self.0.bit_scale()
+    }
+
+    /// Target frequency of a pair of residues, undefined if one is not standard. Throws
+    /// if a code is not an amino acid.
+    pub fn target_frequency(
+        &self,
+        residue_1: char,
+        residue_2: char,
+    ) -> Result<Option<f64>, JsError> {
+        Ok(self
+            .0
+            .target_frequency(Aac::from_char(residue_1)?, Aac::from_char(residue_2)?))
+    }
+
+    /// The matrix with its scores in 1/bit_scale bits. Throws if the scale is not
+    /// positive or a score does not fit.
+    pub fn rescaled(&self, bit_scale: f64) -> Result<JsSubstitutionMatrix, JsError> {
+        Ok(JsSubstitutionMatrix(self.0.rescaled(bit_scale)?))
+    }
+}
+
+/// Relative entropy, expected score, λ and target frequencies of a built-in matrix.
+#[wasm_bindgen]
+pub fn matrix_diagnostics(
+    substitution_matrix: JsAaScoringKind,
+) -> Result<JsMatrixDiagnostics, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let similarity = similarity_builder(substitution_matrix.into());
+    let diagnostics = MatrixDiagnostics::new(similarity.as_ref(), &ROBINSON_FREQUENCIES)?;
+    Ok(JsMatrixDiagnostics(diagnostics))
+}
+
+/// Same as matrix_diagnostics, for a matrix read at runtime. Throws if it is not a
+/// log-odds matrix.
+#[wasm_bindgen]
+pub fn custom_matrix_diagnostics(
+    substitution_matrix: &JsSubstitutionMatrix,
+) -> Result<JsMatrixDiagnostics, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let diagnostics =
+        MatrixDiagnostics::new(&substitution_matrix.0, &ROBINSON_FREQUENCIES)?;
+    Ok(JsMatrixDiagnostics(diagnostics))
+}
+
+/// Same as protein_alignment, but scores the substitutions with a matrix read at runtime.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn protein_alignment_custom_matrix(
+    string_1: &str,
+    string_2: &str,
+    algorithm: JsAlignerKind,
+    substitution_matrix: &JsSubstitutionMatrix,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    free_end_gaps: Option<u8>,
+) -> Result<Alignment, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let result = align_proteins(
+        Protein::new(string_1)?,
+        Protein::new(string_2)?,
+        algorithm.with_end_gaps(free_end_gaps),
+        AaScoringKind::Custom(substitution_matrix.0.clone()),
+        penalty.with_costs(open_cost, extend_cost),
+        None,
+    )?;
+    Ok(result.into())
+}
+
+/// Same as protein_alignment, but only computes a band of diagonals around a seed one.
+/// Fast for long and similar sequences; check touches_band_edge on the result, a wider
+/// band may find a better alignment.
+///
+/// * `band_width`: diagonals at each side of the seed diagonal.
+/// * `seed_diagonal`: the seed diagonal j - i, where i and j are positions at the first
+///   and second sequence. If omitted, it is the diagonal with more words shared by both
+///   sequences.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn banded_protein_alignment(
+    string_1: &str,
+    string_2: &str,
+    algorithm: JsAlignerKind,
+    substitution_matrix: JsAaScoringKind,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    free_end_gaps: Option<u8>,
+    band_width: u32,
+    seed_diagonal: Option<i32>,
+) -> Result<Alignment, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let width = band_width as usize;
+    let band = match seed_diagonal {
+        Some(diagonal) => Band::Seeded {
+            diagonal: diagonal as isize,
+            width,
+        },
+        None => Band::Adaptive(width),
+    };
+    let result = align_proteins(
+        Protein::new(string_1)?,
+        Protein::new(string_2)?,
+        algorithm.with_end_gaps(free_end_gaps),
+        substitution_matrix.into(),
+        penalty.with_costs(open_cost, extend_cost),
+        Some(band),
+    )?;
+    Ok(result.into())
+}
+
+/// The optimal score of an alignment, as returned to JS by protein_alignment_score.
+#[wasm_bindgen(js_name = AlignmentScore)]
+pub struct JsAlignmentScore {
+    score: f32,
+    end: Option<[usize; 2]>,
+}
+
+#[wasm_bindgen(js_class = AlignmentScore)]
+impl JsAlignmentScore {
+    #[wasm_bindgen(getter)]
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// Local alignments only, 1-based position of the last aligned residue of the first
+    /// sequence.
+    #[wasm_bindgen(getter)]
+    pub fn end_1(&self) -> Option<usize> {
+        self.end.map(|end| end[0])
+    }
+
+    /// Local alignments only, 1-based position of the last aligned residue of the second
+    /// sequence.
+    #[wasm_bindgen(getter)]
+    pub fn end_2(&self) -> Option<usize> {
+        self.end.map(|end| end[1])
+    }
+}
+
+impl From<AlignmentScore> for JsAlignmentScore {
+    fn from(score: AlignmentScore) -> Self {
+        Self {
+            score: score.score(),
+            end: score.end(),
+        }
+    }
+}
+
+/// Same as protein_alignment, but only computes the optimal score, without the alignment.
+/// Takes memory proportional to the shorter sequence length, so it suits screening many
+/// pairs before aligning the interesting ones.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn protein_alignment_score(
+    string_1: &str,
+    string_2: &str,
+    algorithm: JsAlignerKind,
+    substitution_matrix: JsAaScoringKind,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    free_end_gaps: Option<u8>,
+) -> Result<JsAlignmentScore, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let aligner_instance = aligner::aminoacid_align_builder(
+        algorithm.with_end_gaps(free_end_gaps),
+        Protein::new(string_1)?,
+        Protein::new(string_2)?,
+        substitution_matrix.into(),
+        penalty.with_costs(open_cost, extend_cost),
+    )?;
+    Ok(aligner_instance.score().into())
+}
+
+/// A multiple sequence alignment, as returned to JS by multiple_protein_alignment.
+#[wasm_bindgen(js_name = MultipleAlignment)]
+pub struct JsMultipleAlignment(MultipleAlignment<Aac>);
+
+#[wasm_bindgen(js_class = MultipleAlignment)]
+impl JsMultipleAlignment {
+    /// Identifiers of the sequences, in the order of the FASTA records.
+    #[wasm_bindgen(getter)]
+    pub fn ids(&self) -> Vec<String> {
+        self.0.ids().to_vec()
+    }
+
+    /// The sequences with their gaps written as '-', in the order of ids.
+    #[wasm_bindgen(getter)]
+    pub fn aligned_sequences(&self) -> Vec<String> {
+        self.0.aligned_strings()
+    }
+
+    /// Number of columns.
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    /// The alignment in FASTA format.
+    pub fn to_fasta(&self) -> String {
+        self.0.to_fasta()
+    }
+
+    /// The alignment in blocks of 50 columns, marking the conserved ones.
+    pub fn to_text(&self) -> String {
+        format!("{}", self.0)
+    }
+}
+
+/// Progressive multiple alignment of the proteins of a FASTA text, with a guide tree
+/// built from their pairwise Needleman-Wunsch alignments. Meant for a handful of
+/// proteins: it aligns every pair. Throws if there are less than two records.
+///
+/// * `open_cost`: ignored by the linear gap model.
+#[wasm_bindgen]
+pub fn multiple_protein_alignment(
+    fasta: &str,
+    substitution_matrix: JsAaScoringKind,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    guide_tree: JsTreeMethod,
+) -> Result<JsMultipleAlignment, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let records = read_protein_records(fasta)?;
+    let aligner = ProgressiveAligner::new(
+        substitution_matrix.into(),
+        penalty.with_costs(open_cost, extend_cost),
+        guide_tree.into(),
+    )?;
+    Ok(JsMultipleAlignment(aligner.align(&records)?))
+}
+
+/// Tree of the proteins of a FASTA text, in the Newick format, with the record
+/// identifiers as leaf labels. The distances are the Kimura-corrected distances of the
+/// global alignments of every pair. Throws if there are less than two records.
+///
+/// * `open_cost`: ignored by the linear gap model.
+#[wasm_bindgen]
+pub fn protein_tree(
+    fasta: &str,
+    substitution_matrix: JsAaScoringKind,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    method: JsTreeMethod,
+) -> Result<String, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let records = read_protein_records(fasta)?;
+    let distances = AllVersusAll::new(
+        substitution_matrix.into(),
+        penalty.with_costs(open_cost, extend_cost),
+        DistanceSettings::default(),
+    )?
+    .distances(&records)?;
+    Ok(Tree::from_distances(&distances, method.into())?.to_newick())
+}
+
+/// Hits of a database search, as returned to JS by protein_database_search.
+#[wasm_bindgen(js_name = SearchResult)]
+pub struct JsSearchResult {
+    ids: Vec<String>,
+    alignments: Vec<Alignment>,
+    searched_records: usize,
+    hit_table: String,
+    text: String,
+}
+
+#[wasm_bindgen(js_class = SearchResult)]
+impl JsSearchResult {
+    /// Number of hits.
+    #[wasm_bindgen(getter)]
+    pub fn hit_count(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Number of records in the library.
+    #[wasm_bindgen(getter)]
+    pub fn searched_records(&self) -> usize {
+        self.searched_records
+    }
+
+    /// Identifiers of the hits, best first.
+    #[wasm_bindgen(getter)]
+    pub fn ids(&self) -> Vec<String> {
+        self.ids.clone()
+    }
+
+    /// Alignment of the query, the first sequence, with the hit at `index`, in the order
+    /// of ids. Its E-value accounts for the size of the library. Undefined if there is no
+    /// such hit.
+    pub fn alignment(&self, index: usize) -> Option<Alignment> {
+        self.alignments.get(index).cloned()
+    }
+
+    /// The hits as tab-separated values, with a header line.
+    pub fn hit_table(&self) -> String {
+        self.hit_table.clone()
+    }
+
+    /// The hit table followed by the alignment of every hit.
+    pub fn to_text(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl From<SearchResult> for JsSearchResult {
+    fn from(result: SearchResult) -> Self {
+        let hit_table = result.hit_table();
+        let text = format!("{}", result);
+        let searched_records = result.searched_records();
+        let (ids, alignments) = result
+            .into_hits()
+            .into_iter()
+            .map(|hit| (hit.id().to_string(), hit.into_alignment().into()))
+            .unzip();
+        Self {
+            ids,
+            alignments,
+            searched_records,
+            hit_table,
+            text,
+        }
+    }
+}
+
+/// Searches a protein query against every record of a FASTA library with Smith-Waterman,
+/// and keeps the best `max_hits` hits, at least 1. The records that do not score above 0
+/// are not hits. Throws if the query or a record is not a valid protein.
+///
+/// * `open_cost`: ignored by the linear gap model.
+/// * `rank_by_e_value`: ranks the hits by E-value instead of score, so short records
+///   rank higher. Only for the matrices and gap costs with known statistical parameters.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn protein_database_search(
+    query: &str,
+    library: &str,
+    substitution_matrix: JsAaScoringKind,
+    penalty: JsPenaltyKind,
+    open_cost: f32,
+    extend_cost: f32,
+    max_hits: usize,
+    rank_by_e_value: bool,
+) -> Result<JsSearchResult, JsError> {
+    // set panic_hook
+    set_panic_hook();
+
+    let settings = SearchSettings {
+        max_hits,
+        ranking: match rank_by_e_value {
+            true => HitRanking::EValue,
+            false => HitRanking::Score,
+        },
+    };
+    let search = DatabaseSearch::new(
+        Protein::new(query)?,
+        substitution_matrix.into(),
+        penalty.with_costs(open_cost, extend_cost),
+        settings,
+    )?;
+    Ok(search.run(library.as_bytes())?.into())
+}
+
+// Reads every record of a FASTA text as a protein, with its identifier.
+fn read_protein_records(fasta: &str) -> Result<Vec<(String, Protein)>, JsError> {
+    FastaReader::new(fasta.as_bytes())
+        .map(|record| {
+            let record = record?;
+            Ok((record.id().to_string(), record.protein()?))
+        })
+        .collect()
+}
+
+/// Aligns two protein sequences and returns the formatted alignment.
+///
+/// * `substitution_matrix`: 1 BLOSUM45, 2 BLOSUM62, 3 PAM160, 4 BLOSUM30, 5 BLOSUM35,
+///   6 BLOSUM40, 7 BLOSUM50, 8 BLOSUM55, 9 BLOSUM60, 10 BLOSUM65, 11 BLOSUM70, 12 BLOSUM75,
+///   13 BLOSUM80, 14 BLOSUM85, 15 BLOSUM90, 16 BLOSUM100, 17 PAM30, 18 PAM70, 19 PAM120,
+///   20 PAM250. The same values as AaScoringKind.
+/// * `algorithm`: 1 Smith-Waterman, 2 Needleman-Wunsch, 3 semi-global.
+/// * `free_end_gaps`: see protein_alignment.
 #[wasm_bindgen]
 pub fn do_protein_alignment(
     string_1: &str,
@@ -24,6 +767,7 @@ pub fn do_protein_alignment(
     extend_cost: f32,
     substitution_matrix: u8,
     algorithm: u8,
+    free_end_gaps: Option<u8>,
 ) -> Result<String, JsError> {
     // set panic_hook
     set_panic_hook();
@@ -32,33 +776,120 @@ pub fn do_protein_alignment(
     let sequence_1 = Protein::new(string_1)?;
     let sequence_2 = Protein::new(string_2)?;
 
-    let penalty_kind = PenaltyKind::Affine(open_cost, extend_cost);
+    let result = align_proteins(
+        sequence_1,
+        sequence_2,
+        aligner_kind_from_code(algorithm, free_end_gaps)?,
+        score_kind_from_code(substitution_matrix)?,
+        PenaltyKind::Affine(open_cost, extend_cost),
+        None,
+    )?;
+    Ok(format!("{}", result))
+}
 
-    let score_kind = match substitution_matrix {
-        b'\x01' => AaScoringKind::Blosum45,
-        b'\x02' => AaScoringKind::Blosum62,
-        b'\x03' => AaScoringKind::Pam160,
-        _ => Err(InputError::new(InputErrorKind::ScoringMatrixNotExist))?,
-    };
+/// Same as do_protein_alignment, but reads both sequences from a FASTA text. Aligns the
+/// first two records; the remaining ones are ignored.
+#[wasm_bindgen]
+pub fn do_fasta_protein_alignment(
+    fasta: &str,
+    open_cost: f32,
+    extend_cost: f32,
+    substitution_matrix: u8,
+    algorithm: u8,
+    free_end_gaps: Option<u8>,
+) -> Result<String, JsError> {
+    // set panic_hook
+    set_panic_hook();
 
-    let aligner_kind = match algorithm {
-        b'\x01' => AlignerKind::SmithWaterman,
-        b'\x02' => AlignerKind::NeedlemanWunsch,
-        _ => Err(InputError::new(InputErrorKind::AlignerNotExist))?,
+    let mut records = FastaReader::new(fasta.as_bytes());
+    let mut next_protein = || -> Result<Protein, JsError> {
+        match records.next() {
+            Some(record) => Ok(record?.protein()?),
+            None => Err(InputError::new(InputErrorKind::NotEnoughSequences, ""))?,
+        }
     };
+    let sequence_1 = next_protein()?;
+    let sequence_2 = next_protein()?;
 
+    let result = align_proteins(
+        sequence_1,
+        sequence_2,
+        aligner_kind_from_code(algorithm, free_end_gaps)?,
+        score_kind_from_code(substitution_matrix)?,
+        PenaltyKind::Affine(open_cost, extend_cost),
+        None,
+    )?;
+    Ok(format!("{}", result))
+}
+
+fn align_proteins(
+    sequence_1: Protein,
+    sequence_2: Protein,
+    aligner_kind: AlignerKind,
+    score_kind: AaScoringKind,
+    penalty_kind: PenaltyKind,
+    band: Option<Band>,
+) -> Result<AlignmentResult<Aac>, InputError> {
     let mut aligner_instance = aligner::aminoacid_align_builder(
         aligner_kind,
         sequence_1,
         sequence_2,
         score_kind,
         penalty_kind,
-    );
+    )?;
+    if let Some(band) = band {
+        aligner_instance.set_band(band);
+    }
+    Ok(aligner_instance.run().remove(0))
+}
+
+fn score_kind_from_code(code: u8) -> Result<AaScoringKind, InputError> {
+    match code {
+        b'\x01' => Ok(AaScoringKind::Blosum45),
+        b'\x02' => Ok(AaScoringKind::Blosum62),
+        b'\x03' => Ok(AaScoringKind::Pam160),
+        b'\x04' => Ok(AaScoringKind::Blosum30),
+        b'\x05' => Ok(AaScoringKind::Blosum35),
+        b'\x06' => Ok(AaScoringKind::Blosum40),
+        b'\x07' => Ok(AaScoringKind::Blosum50),
+        b'\x08' => Ok(AaScoringKind::Blosum55),
+        b'\x09' => Ok(AaScoringKind::Blosum60),
+        b'\x0a' => Ok(AaScoringKind::Blosum65),
+        b'\x0b' => Ok(AaScoringKind::Blosum70),
+        b'\x0c' => Ok(AaScoringKind::Blosum75),
+        b'\x0d' => Ok(AaScoringKind::Blosum80),
+        b'\x0e' => Ok(AaScoringKind::Blosum85),
+        b'\x0f' => Ok(AaScoringKind::Blosum90),
+        b'\x10' => Ok(AaScoringKind::Blosum100),
+        b'\x11' => Ok(AaScoringKind::Pam30),
+        b'\x12' => Ok(AaScoringKind::Pam70),
+        b'\x13' => Ok(AaScoringKind::Pam120),
+        b'\x14' => Ok(AaScoringKind::Pam250),
+        _ => Err(InputError::new(InputErrorKind::ScoringMatrixNotExist, "")),
+    }
+}
 
-    Ok(aligner_instance
-        .run()
-        .into_iter()
-        .fold(String::new(), |acc, e| acc + &format!("{}", e)))
+fn aligner_kind_from_code(
+    code: u8,
+    free_end_gaps: Option<u8>,
+) -> Result<AlignerKind, InputError> {
+    match code {
+        b'\x01' => Ok(JsAlignerKind::SmithWaterman),
+        b'\x02' => Ok(JsAlignerKind::NeedlemanWunsch),
+        b'\x03' => Ok(JsAlignerKind::SemiGlobal),
+        _ => Err(InputError::new(InputErrorKind::AlignerNotExist, "")),
+    }
+    .map(|kind| kind.with_end_gaps(free_end_gaps))
+}
+
+// Reads the free_end_gaps flags of protein_alignment.
+fn end_gaps_from_flags(flags: u8) -> EndGaps {
+    EndGaps {
+        leading_left: flags & 0b0001 != 0,
+        trailing_left: flags & 0b0010 != 0,
+        leading_top: flags & 0b0100 != 0,
+        trailing_top: flags & 0b1000 != 0,
+    }
 }
 
 #[derive(Debug)]
@@ -74,11 +905,14 @@ pub struct InputError {
 pub enum InputErrorKind {
     AlignerNotExist,
     GapModelNotExist,
+    InvalidExtendCost,
+    InvalidOpenCost,
+    NotEnoughSequences,
     ScoringMatrixNotExist,
 }
 
 impl InputError {
-    fn new(kind: InputErrorKind) -> Self {
+    fn new(kind: InputErrorKind, detail: &str) -> Self {
         let mut message: String = match kind {
             InputErrorKind::AlignerNotExist => {
                 "The chosen aligner algorithm does not exist.".to_string()
@@ -86,10 +920,25 @@ fn new(kind: InputErrorKind) -> Self {
             InputErrorKind::GapModelNotExist => {
                 "The chosen gap model does not exist.".to_string()
             }
+            InputErrorKind::InvalidExtendCost => format!(
+                "The gap extend cost must be in the closed interval [{}, {}].",
+                MIN_EXTEND_COST, MAX_EXTEND_COST
+            ),
+            InputErrorKind::InvalidOpenCost => format!(
+                "The gap open cost must be in the closed interval [{}, {}].",
+                MIN_OPEN_COST, MAX_OPEN_COST
+            ),
+            InputErrorKind::NotEnoughSequences => {
+                "The input must contain at least two sequences.".to_string()
+            }
             InputErrorKind::ScoringMatrixNotExist => {
                 "The chosen scoring matrix does not exist.".to_string()
             }
         };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
 
         message.push_str(" Please check the documentation for more information.");
 
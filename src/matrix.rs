@@ -2,11 +2,13 @@
 
 use std::error;
 use std::fmt::{self, Debug};
-use std::ops::{Index, IndexMut};
+use std::ops::{Index, IndexMut, Range};
 
 /// Representation of a Matrix (aᵢⱼ), 0 ≤ i < rows , 0 ≤ j < cols.
 ///
-/// Internally it has a linear container of length i⨯j.
+/// Internally it has a linear container of length i⨯j. A band-compressed matrix only stores
+/// the entries of a band of diagonals, lower ≤ j - i ≤ upper, in a container of length
+/// i⨯(upper - lower + 1); every entry outside the band has the same read-only value.
 pub struct Matrix<T> {
     /// number of rows.
     rows: usize,
@@ -14,6 +16,8 @@ pub struct Matrix<T> {
     cols: usize,
     /// matrix's elements collection.
     container: Vec<T>,
+    /// stored diagonals [lower, upper] and the value outside them, if band-compressed.
+    band: Option<([isize; 2], T)>,
 }
 
 impl<T> Matrix<T> {
@@ -21,6 +25,32 @@ impl<T> Matrix<T> {
     pub fn dim(&self) -> [usize; 2] {
         [self.rows, self.cols]
     }
+
+    /// Returns the stored diagonals [lower, upper] of a band-compressed matrix.
+    pub fn band(&self) -> Option<[isize; 2]> {
+        self.band.as_ref().map(|(diagonals, _)| *diagonals)
+    }
+
+    /// Returns the columns of the row that are stored: all of them, or the ones inside
+    /// the band.
+    pub fn row_range(&self, row: usize) -> Range<usize> {
+        match self.band {
+            None => 0..self.cols,
+            Some(([lower, upper], _)) => {
+                let start = (row as isize + lower).clamp(0, self.cols as isize);
+                let end = (row as isize + upper + 1).clamp(start, self.cols as isize);
+                start as usize..end as usize
+            }
+        }
+    }
+
+    // Length of the stored part of a row.
+    fn row_length(&self) -> usize {
+        match self.band {
+            None => self.cols,
+            Some(([lower, upper], _)) => (upper - lower + 1) as usize,
+        }
+    }
 }
 
 impl<T: std::clone::Clone> Matrix<T> {
@@ -51,6 +81,7 @@ pub fn empty(rows: usize, cols: usize) -> Self {
             rows,
             cols,
             container,
+            band: None,
         }
     }
 
@@ -75,9 +106,58 @@ pub fn full(value: T, rows: usize, cols: usize) -> Self {
             rows,
             cols,
             container,
+            band: None,
+        }
+    }
+
+    /// Creates a band-compressed matrix of dimension rows * cols. The entries on the
+    /// diagonals lower ≤ j - i ≤ upper are filled with a constant value: T. The entries
+    /// outside the band always read as `outside` and cannot be modified.
+    ///
+    /// # Arguments
+    /// * `value` - initial value of the band entries.
+    /// * `outside` - value of the entries outside the band.
+    /// * `rows` - matrix rows number.
+    /// * `cols` - matrix columns number.
+    /// * `diagonals` - the [lower, upper] band diagonals. They are clipped to the
+    ///   matrix diagonals, -(rows - 1) and cols - 1.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use pairwasm_alignment::matrix::*;
+    /// let mut matrix: Matrix<i8> = Matrix::banded(0, -1, 4, 4, [-1, 1]);
+    /// matrix[[2, 3]] = 5;
+    /// assert_eq!(5, matrix[[2, 3]]);
+    /// assert_eq!(-1, matrix[[0, 3]]);
+    /// assert!(matrix.get_mut(0, 3).is_err());
+    /// assert_eq!(1..4, matrix.row_range(2));
+    /// ```
+    pub fn banded(
+        value: T,
+        outside: T,
+        rows: usize,
+        cols: usize,
+        [lower, upper]: [isize; 2],
+    ) -> Self {
+        let lower = lower.max(1 - rows as isize);
+        let upper = upper.min(cols as isize - 1).max(lower - 1);
+        let length = (upper - lower + 1) as usize;
+        let container: Vec<T> = vec![value; rows * length];
+        Self {
+            rows,
+            cols,
+            container,
+            band: Some(([lower, upper], outside)),
         }
     }
 
+    /// Returns whether the matrix i,j entry is stored: it belongs to the matrix bounds
+    /// and, for a band-compressed matrix, to the band.
+    pub fn in_band(&self, row: usize, col: usize) -> bool {
+        self.map_2dim_to_1dim_index(row, col).is_ok()
+    }
+
     /// Returns a reference to the matrix i,j entry.
     /// If the entry is empty, it returns a MatError.
     ///
@@ -97,7 +177,13 @@ pub fn full(value: T, rows: usize, cols: usize) -> Self {
     /// assert!(matrix.get(2, 3).is_err());
     /// ```
     pub fn get(&self, row: usize, col: usize) -> Result<&T, MatError> {
-        let index = self.map_2dim_to_1dim_index(row, col)?;
+        let index = match self.map_2dim_to_1dim_index(row, col) {
+            Err(MatError {
+                kind: ErrorKind::OutOfBand(_),
+                ..
+            }) => return Ok(&self[[row, col]]),
+            index => index?,
+        };
         if index >= self.container.len() {
             return Err(MatError::new(ErrorKind::EmptyAtIndex((
                 [row, col],
@@ -108,7 +194,7 @@ pub fn get(&self, row: usize, col: usize) -> Result<&T, MatError> {
     }
 
     /// Returns a mutable reference to the matrix i,j entry.
-    /// If the entry is empty, it returns a MatError.
+    /// If the entry is empty or outside the band, it returns a MatError.
     ///
     /// # Arguments
     /// * `row` - matrix row i,  0 ≤ i < self.rows ,
@@ -144,7 +230,19 @@ fn map_2dim_to_1dim_index(&self, row: usize, col: usize) -> Result<usize, MatErr
                 [self.rows, self.cols],
             ))));
         }
-        Ok(row * self.cols + col)
+        match &self.band {
+            None => Ok(row * self.cols + col),
+            Some(([lower, upper], _)) => {
+                let diagonal = col as isize - row as isize;
+                if diagonal < *lower || diagonal > *upper {
+                    return Err(MatError::new(ErrorKind::OutOfBand((
+                        [row, col],
+                        [*lower, *upper],
+                    ))));
+                }
+                Ok(row * self.row_length() + (diagonal - lower) as usize)
+            }
+        }
     }
 
     /// Appends an element to the end of the matrix if there is sufficient spare
@@ -202,10 +300,14 @@ pub fn last_entry_indices(&self) -> Option<(usize, usize)> {
             return None;
         }
         let current_index = self.container.len() - 1;
-        Some((
-            current_index.div_euclid(self.cols),
-            current_index.rem_euclid(self.cols),
-        ))
+        let row = current_index.div_euclid(self.row_length());
+        let offset = current_index.rem_euclid(self.row_length());
+        match self.band {
+            None => Some((row, offset)),
+            Some(([lower, _], _)) => {
+                Some((row, (row as isize + lower) as usize + offset))
+            }
+        }
     }
 }
 
@@ -214,6 +316,10 @@ pub fn last_entry_indices(&self) -> Option<(usize, usize)> {
     fn index(&self, index: [usize; 2]) -> &Self::Output {
         let i = match self.map_2dim_to_1dim_index(index[0], index[1]) {
             Ok(i) => i,
+            Err(MatError {
+                kind: ErrorKind::OutOfBand(_),
+                ..
+            }) => return &self.band.as_ref().unwrap().1,
             Err(e) => panic!("{:?}", e),
         };
         &self.container[i]
@@ -240,8 +346,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         //f.write_fmt(format_args!(""))
         let mut formatted = String::new();
         for i in 0..self.rows {
-            let start = i * self.cols;
-            let end = (i + 1) * self.cols;
+            let start = i * self.row_length();
+            let end = (i + 1) * self.row_length();
             formatted.push_str(&format!("{:?}\n", &self.container[start..end]))
         }
         f.write_fmt(format_args!("[{}]", formatted))
@@ -263,6 +369,7 @@ pub fn from_vec(container: Vec<T>, rows: usize, cols: usize) -> Self {
             rows,
             cols,
             container,
+            band: None,
         }
     }
 }
@@ -284,6 +391,8 @@ pub enum ErrorKind {
     EmptyAtIndex(([usize; 2], [usize; 2])),
     // Dimension.
     Filled([usize; 2]),
+    // (AttemptedIndex, BandDiagonals).
+    OutOfBand(([usize; 2], [isize; 2])),
 }
 
 impl MatError {
@@ -299,6 +408,10 @@ fn new(kind: ErrorKind) -> Self {
             ),
             ErrorKind::Filled([rows, cols]) => format!(
                 "Cannot add more entries, the matrix (dim {rows}⨯{cols}) is at full capacity."),
+            ErrorKind::OutOfBand((attempted, diagonals)) => format!(
+                "The index '{attempted:?}' is out of the Matrix band. \
+                 The stored diagonals are '{diagonals:?}'."
+            ),
         };
 
         Self { kind, message }
@@ -407,4 +520,44 @@ fn get_last_i_j() {
             assert_eq!(Some(expected), matrix.last_entry_indices())
         }
     }
+
+    #[test]
+    fn band_compressed_matrix() {
+        let mut matrix: Matrix<u8> = Matrix::banded(0, 9, 4, 6, [-1, 2]);
+        assert_eq!(Some([-1, 2]), matrix.band());
+        assert_eq!(16, matrix.container.len());
+        for i in 0..4 {
+            for j in matrix.row_range(i) {
+                matrix[[i, j]] = (10 * i + j) as u8;
+            }
+        }
+        assert_eq!(
+            [0..3, 0..4, 1..5, 2..6],
+            [0, 1, 2, 3].map(|i| matrix.row_range(i))
+        );
+        assert_eq!(23, matrix[[2, 3]]);
+        assert_eq!(9, matrix[[3, 0]]);
+        assert!(matrix.get(0, 5).is_ok_and(|v| *v == 9));
+        assert!(!matrix.in_band(0, 5));
+        assert!(matrix
+            .get_mut(3, 0)
+            .is_err_and(|e| e.kind == ErrorKind::OutOfBand(([3, 0], [-1, 2]))
+                && e.message
+                    .contains("band. The stored diagonals are '[-1, 2]'.")));
+        assert!(matrix
+            .get(4, 0)
+            .is_err_and(|e| e.kind == ErrorKind::OutOfDimension(([4, 0], [4, 6]))));
+
+        // the band is clipped to the matrix diagonals
+        let matrix: Matrix<u8> = Matrix::banded(0, 9, 2, 3, [-5, 7]);
+        assert_eq!(Some([-1, 2]), matrix.band());
+        assert_eq!(0..3, matrix.row_range(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_write_outside_band() {
+        let mut matrix: Matrix<u8> = Matrix::banded(0, 9, 3, 3, [0, 0]);
+        matrix[[1, 2]] = 1;
+    }
 }
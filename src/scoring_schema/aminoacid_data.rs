@@ -2,1102 +2,715 @@
 
 use crate::bioseq::Aac;
 
-const fn map_aminoacid_to_index(code_1: Aac, code_2: Aac) -> u8 {
-    match code_1 {
-        Aac::A => match code_2 {
-            Aac::A => b'\x00',
-            Aac::C => b'\x01',
-            Aac::D => b'\x02',
-            Aac::E => b'\x03',
-            Aac::F => b'\x04',
-            Aac::G => b'\x05',
-            Aac::H => b'\x06',
-            Aac::I => b'\x07',
-            Aac::K => b'\x08',
-            Aac::L => b'\t',
-            Aac::M => b'\n',
-            Aac::N => b'\x0b',
-            Aac::P => b'\x0c',
-            Aac::Q => b'\r',
-            Aac::R => b'\x0e',
-            Aac::S => b'\x0f',
-            Aac::T => b'\x10',
-            Aac::V => b'\x11',
-            Aac::W => b'\x12',
-            Aac::Y => b'\x13',
-        },
-        Aac::C => match code_2 {
-            Aac::A => b'\x01',
-            Aac::C => b'\x14',
-            Aac::D => b'\x15',
-            Aac::E => b'\x16',
-            Aac::F => b'\x17',
-            Aac::G => b'\x18',
-            Aac::H => b'\x19',
-            Aac::I => b'\x1a',
-            Aac::K => b'\x1b',
-            Aac::L => b'\x1c',
-            Aac::M => b'\x1d',
-            Aac::N => b'\x1e',
-            Aac::P => b'\x1f',
-            Aac::Q => b' ',
-            Aac::R => b'!',
-            Aac::S => b'"',
-            Aac::T => b'#',
-            Aac::V => b'$',
-            Aac::W => b'%',
-            Aac::Y => b'&',
-        },
-        Aac::D => match code_2 {
-            Aac::A => b'\x02',
-            Aac::C => b'\x15',
-            Aac::D => b'\'',
-            Aac::E => b'(',
-            Aac::F => b')',
-            Aac::G => b'*',
-            Aac::H => b'+',
-            Aac::I => b',',
-            Aac::K => b'-',
-            Aac::L => b'.',
-            Aac::M => b'/',
-            Aac::N => b'0',
-            Aac::P => b'1',
-            Aac::Q => b'2',
-            Aac::R => b'3',
-            Aac::S => b'4',
-            Aac::T => b'5',
-            Aac::V => b'6',
-            Aac::W => b'7',
-            Aac::Y => b'8',
-        },
-        Aac::E => match code_2 {
-            Aac::A => b'\x03',
-            Aac::C => b'\x16',
-            Aac::D => b'(',
-            Aac::E => b'9',
-            Aac::F => b':',
-            Aac::G => b';',
-            Aac::H => b'<',
-            Aac::I => b'=',
-            Aac::K => b'>',
-            Aac::L => b'?',
-            Aac::M => b'@',
-            Aac::N => b'A',
-            Aac::P => b'B',
-            Aac::Q => b'C',
-            Aac::R => b'D',
-            Aac::S => b'E',
-            Aac::T => b'F',
-            Aac::V => b'G',
-            Aac::W => b'H',
-            Aac::Y => b'I',
-        },
-        Aac::F => match code_2 {
-            Aac::A => b'\x04',
-            Aac::C => b'\x17',
-            Aac::D => b')',
-            Aac::E => b':',
-            Aac::F => b'J',
-            Aac::G => b'K',
-            Aac::H => b'L',
-            Aac::I => b'M',
-            Aac::K => b'N',
-            Aac::L => b'O',
-            Aac::M => b'P',
-            Aac::N => b'Q',
-            Aac::P => b'R',
-            Aac::Q => b'S',
-            Aac::R => b'T',
-            Aac::S => b'U',
-            Aac::T => b'V',
-            Aac::V => b'W',
-            Aac::W => b'X',
-            Aac::Y => b'Y',
-        },
-        Aac::G => match code_2 {
-            Aac::A => b'\x05',
-            Aac::C => b'\x18',
-            Aac::D => b'*',
-            Aac::E => b';',
-            Aac::F => b'K',
-            Aac::G => b'Z',
-            Aac::H => b'[',
-            Aac::I => b'\\',
-            Aac::K => b']',
-            Aac::L => b'^',
-            Aac::M => b'_',
-            Aac::N => b'`',
-            Aac::P => b'a',
-            Aac::Q => b'b',
-            Aac::R => b'c',
-            Aac::S => b'd',
-            Aac::T => b'e',
-            Aac::V => b'f',
-            Aac::W => b'g',
-            Aac::Y => b'h',
-        },
-        Aac::H => match code_2 {
-            Aac::A => b'\x06',
-            Aac::C => b'\x19',
-            Aac::D => b'+',
-            Aac::E => b'<',
-            Aac::F => b'L',
-            Aac::G => b'[',
-            Aac::H => b'i',
-            Aac::I => b'j',
-            Aac::K => b'k',
-            Aac::L => b'l',
-            Aac::M => b'm',
-            Aac::N => b'n',
-            Aac::P => b'o',
-            Aac::Q => b'p',
-            Aac::R => b'q',
-            Aac::S => b'r',
-            Aac::T => b's',
-            Aac::V => b't',
-            Aac::W => b'u',
-            Aac::Y => b'v',
-        },
-        Aac::I => match code_2 {
-            Aac::A => b'\x07',
-            Aac::C => b'\x1a',
-            Aac::D => b',',
-            Aac::E => b'=',
-            Aac::F => b'M',
-            Aac::G => b'\\',
-            Aac::H => b'j',
-            Aac::I => b'w',
-            Aac::K => b'x',
-            Aac::L => b'y',
-            Aac::M => b'z',
-            Aac::N => b'{',
-            Aac::P => b'|',
-            Aac::Q => b'}',
-            Aac::R => b'~',
-            Aac::S => b'\x7f',
-            Aac::T => b'\x80',
-            Aac::V => b'\x81',
-            Aac::W => b'\x82',
-            Aac::Y => b'\x83',
-        },
-        Aac::K => match code_2 {
-            Aac::A => b'\x08',
-            Aac::C => b'\x1b',
-            Aac::D => b'-',
-            Aac::E => b'>',
-            Aac::F => b'N',
-            Aac::G => b']',
-            Aac::H => b'k',
-            Aac::I => b'x',
-            Aac::K => b'\x84',
-            Aac::L => b'\x85',
-            Aac::M => b'\x86',
-            Aac::N => b'\x87',
-            Aac::P => b'\x88',
-            Aac::Q => b'\x89',
-            Aac::R => b'\x8a',
-            Aac::S => b'\x8b',
-            Aac::T => b'\x8c',
-            Aac::V => b'\x8d',
-            Aac::W => b'\x8e',
-            Aac::Y => b'\x8f',
-        },
-        Aac::L => match code_2 {
-            Aac::A => b'\t',
-            Aac::C => b'\x1c',
-            Aac::D => b'.',
-            Aac::E => b'?',
-            Aac::F => b'O',
-            Aac::G => b'^',
-            Aac::H => b'l',
-            Aac::I => b'y',
-            Aac::K => b'\x85',
-            Aac::L => b'\x90',
-            Aac::M => b'\x91',
-            Aac::N => b'\x92',
-            Aac::P => b'\x93',
-            Aac::Q => b'\x94',
-            Aac::R => b'\x95',
-            Aac::S => b'\x96',
-            Aac::T => b'\x97',
-            Aac::V => b'\x98',
-            Aac::W => b'\x99',
-            Aac::Y => b'\x9a',
-        },
-        Aac::M => match code_2 {
-            Aac::A => b'\n',
-            Aac::C => b'\x1d',
-            Aac::D => b'/',
-            Aac::E => b'@',
-            Aac::F => b'P',
-            Aac::G => b'_',
-            Aac::H => b'm',
-            Aac::I => b'z',
-            Aac::K => b'\x86',
-            Aac::L => b'\x91',
-            Aac::M => b'\x9b',
-            Aac::N => b'\x9c',
-            Aac::P => b'\x9d',
-            Aac::Q => b'\x9e',
-            Aac::R => b'\x9f',
-            Aac::S => b'\xa0',
-            Aac::T => b'\xa1',
-            Aac::V => b'\xa2',
-            Aac::W => b'\xa3',
-            Aac::Y => b'\xa4',
-        },
-        Aac::N => match code_2 {
-            Aac::A => b'\x0b',
-            Aac::C => b'\x1e',
-            Aac::D => b'0',
-            Aac::E => b'A',
-            Aac::F => b'Q',
-            Aac::G => b'`',
-            Aac::H => b'n',
-            Aac::I => b'{',
-            Aac::K => b'\x87',
-            Aac::L => b'\x92',
-            Aac::M => b'\x9c',
-            Aac::N => b'\xa5',
-            Aac::P => b'\xa6',
-            Aac::Q => b'\xa7',
-            Aac::R => b'\xa8',
-            Aac::S => b'\xa9',
-            Aac::T => b'\xaa',
-            Aac::V => b'\xab',
-            Aac::W => b'\xac',
-            Aac::Y => b'\xad',
-        },
-        Aac::P => match code_2 {
-            Aac::A => b'\x0c',
-            Aac::C => b'\x1f',
-            Aac::D => b'1',
-            Aac::E => b'B',
-            Aac::F => b'R',
-            Aac::G => b'a',
-            Aac::H => b'o',
-            Aac::I => b'|',
-            Aac::K => b'\x88',
-            Aac::L => b'\x93',
-            Aac::M => b'\x9d',
-            Aac::N => b'\xa6',
-            Aac::P => b'\xae',
-            Aac::Q => b'\xaf',
-            Aac::R => b'\xb0',
-            Aac::S => b'\xb1',
-            Aac::T => b'\xb2',
-            Aac::V => b'\xb3',
-            Aac::W => b'\xb4',
-            Aac::Y => b'\xb5',
-        },
-        Aac::Q => match code_2 {
-            Aac::A => b'\r',
-            Aac::C => b' ',
-            Aac::D => b'2',
-            Aac::E => b'C',
-            Aac::F => b'S',
-            Aac::G => b'b',
-            Aac::H => b'p',
-            Aac::I => b'}',
-            Aac::K => b'\x89',
-            Aac::L => b'\x94',
-            Aac::M => b'\x9e',
-            Aac::N => b'\xa7',
-            Aac::P => b'\xaf',
-            Aac::Q => b'\xb6',
-            Aac::R => b'\xb7',
-            Aac::S => b'\xb8',
-            Aac::T => b'\xb9',
-            Aac::V => b'\xba',
-            Aac::W => b'\xbb',
-            Aac::Y => b'\xbc',
-        },
-        Aac::R => match code_2 {
-            Aac::A => b'\x0e',
-            Aac::C => b'!',
-            Aac::D => b'3',
-            Aac::E => b'D',
-            Aac::F => b'T',
-            Aac::G => b'c',
-            Aac::H => b'q',
-            Aac::I => b'~',
-            Aac::K => b'\x8a',
-            Aac::L => b'\x95',
-            Aac::M => b'\x9f',
-            Aac::N => b'\xa8',
-            Aac::P => b'\xb0',
-            Aac::Q => b'\xb7',
-            Aac::R => b'\xbd',
-            Aac::S => b'\xbe',
-            Aac::T => b'\xbf',
-            Aac::V => b'\xc0',
-            Aac::W => b'\xc1',
-            Aac::Y => b'\xc2',
-        },
-        Aac::S => match code_2 {
-            Aac::A => b'\x0f',
-            Aac::C => b'"',
-            Aac::D => b'4',
-            Aac::E => b'E',
-            Aac::F => b'U',
-            Aac::G => b'd',
-            Aac::H => b'r',
-            Aac::I => b'\x7f',
-            Aac::K => b'\x8b',
-            Aac::L => b'\x96',
-            Aac::M => b'\xa0',
-            Aac::N => b'\xa9',
-            Aac::P => b'\xb1',
-            Aac::Q => b'\xb8',
-            Aac::R => b'\xbe',
-            Aac::S => b'\xc3',
-            Aac::T => b'\xc4',
-            Aac::V => b'\xc5',
-            Aac::W => b'\xc6',
-            Aac::Y => b'\xc7',
-        },
-        Aac::T => match code_2 {
-            Aac::A => b'\x10',
-            Aac::C => b'#',
-            Aac::D => b'5',
-            Aac::E => b'F',
-            Aac::F => b'V',
-            Aac::G => b'e',
-            Aac::H => b's',
-            Aac::I => b'\x80',
-            Aac::K => b'\x8c',
-            Aac::L => b'\x97',
-            Aac::M => b'\xa1',
-            Aac::N => b'\xaa',
-            Aac::P => b'\xb2',
-            Aac::Q => b'\xb9',
-            Aac::R => b'\xbf',
-            Aac::S => b'\xc4',
-            Aac::T => b'\xc8',
-            Aac::V => b'\xc9',
-            Aac::W => b'\xca',
-            Aac::Y => b'\xcb',
-        },
-        Aac::V => match code_2 {
-            Aac::A => b'\x11',
-            Aac::C => b'$',
-            Aac::D => b'6',
-            Aac::E => b'G',
-            Aac::F => b'W',
-            Aac::G => b'f',
-            Aac::H => b't',
-            Aac::I => b'\x81',
-            Aac::K => b'\x8d',
-            Aac::L => b'\x98',
-            Aac::M => b'\xa2',
-            Aac::N => b'\xab',
-            Aac::P => b'\xb3',
-            Aac::Q => b'\xba',
-            Aac::R => b'\xc0',
-            Aac::S => b'\xc5',
-            Aac::T => b'\xc9',
-            Aac::V => b'\xcc',
-            Aac::W => b'\xcd',
-            Aac::Y => b'\xce',
-        },
-        Aac::W => match code_2 {
-            Aac::A => b'\x12',
-            Aac::C => b'%',
-            Aac::D => b'7',
-            Aac::E => b'H',
-            Aac::F => b'X',
-            Aac::G => b'g',
-            Aac::H => b'u',
-            Aac::I => b'\x82',
-            Aac::K => b'\x8e',
-            Aac::L => b'\x99',
-            Aac::M => b'\xa3',
-            Aac::N => b'\xac',
-            Aac::P => b'\xb4',
-            Aac::Q => b'\xbb',
-            Aac::R => b'\xc1',
-            Aac::S => b'\xc6',
-            Aac::T => b'\xca',
-            Aac::V => b'\xcd',
-            Aac::W => b'\xcf',
-            Aac::Y => b'\xd0',
-        },
-        Aac::Y => match code_2 {
-            Aac::A => b'\x13',
-            Aac::C => b'&',
-            Aac::D => b'8',
-            Aac::E => b'I',
-            Aac::F => b'Y',
-            Aac::G => b'h',
-            Aac::H => b'v',
-            Aac::I => b'\x83',
-            Aac::K => b'\x8f',
-            Aac::L => b'\x9a',
-            Aac::M => b'\xa4',
-            Aac::N => b'\xad',
-            Aac::P => b'\xb5',
-            Aac::Q => b'\xbc',
-            Aac::R => b'\xc2',
-            Aac::S => b'\xc7',
-            Aac::T => b'\xcb',
-            Aac::V => b'\xce',
-            Aac::W => b'\xd0',
-            Aac::Y => b'\xd1',
-        },
+/// A substitution matrix, indexed with map_aminoacid_to_index.
+pub(super) type ScoreTable = [[i8; 25]; 25];
+
+/// Rows and columns of the tables, in order. The first STANDARD ones are the standard amino
+/// acids.
+pub(super) const TABLE_CODES: [Aac; 25] = [
+    Aac::A,
+    Aac::R,
+    Aac::N,
+    Aac::D,
+    Aac::C,
+    Aac::Q,
+    Aac::E,
+    Aac::G,
+    Aac::H,
+    Aac::I,
+    Aac::L,
+    Aac::K,
+    Aac::M,
+    Aac::F,
+    Aac::P,
+    Aac::S,
+    Aac::T,
+    Aac::W,
+    Aac::Y,
+    Aac::V,
+    Aac::B,
+    Aac::J,
+    Aac::Z,
+    Aac::X,
+    Aac::Stop,
+];
+
+/// Number of standard amino acids.
+pub(super) const STANDARD: usize = 20;
+
+/// The standard amino acids, in the order of the tables.
+pub(super) const STANDARD_CODES: [Aac; STANDARD] = *TABLE_CODES.first_chunk().unwrap();
+
+// Rows and columns order of the tables, as in the NCBI files:
+// A R N D C Q E G H I L K M F P S T W Y V B J Z X *.
+// The NCBI files have no rows for Selenocysteine and Pyrrolysine; they share the rows of
+// the residues they derive from (Cysteine and Lysine). The J (Isoleucine or Leucine) row of
+// BLOSUM62 is the one of the NCBI file. The other tables come from NCBI files without a J
+// row; theirs is the rounded mean of the I and L rows, which may differ by 1 from the J
+// scores of other sources.
+pub(super) const fn map_aminoacid_to_index(code: Aac) -> usize {
+    match code {
+        Aac::A => 0,
+        Aac::R => 1,
+        Aac::N => 2,
+        Aac::D => 3,
+        Aac::C | Aac::U => 4,
+        Aac::Q => 5,
+        Aac::E => 6,
+        Aac::G => 7,
+        Aac::H => 8,
+        Aac::I => 9,
+        Aac::L => 10,
+        Aac::K | Aac::O => 11,
+        Aac::M => 12,
+        Aac::F => 13,
+        Aac::P => 14,
+        Aac::S => 15,
+        Aac::T => 16,
+        Aac::W => 17,
+        Aac::Y => 18,
+        Aac::V => 19,
+        Aac::B => 20,
+        Aac::J => 21,
+        Aac::Z => 22,
+        Aac::X => 23,
+        Aac::Stop => 24,
     }
 }
 
-/// Get BLOSUM 62 scores
-pub const fn read_blosum62(code_1: Aac, code_2: Aac) -> i8 {
-    let index = map_aminoacid_to_index(code_1, code_2);
-    match index {
-        b'\x00' => 4,
-        b'\x01' => 0,
-        b'\x02' => -2,
-        b'\x03' => -1,
-        b'\x04' => -2,
-        b'\x05' => 0,
-        b'\x06' => -2,
-        b'\x07' => -1,
-        b'\x08' => -1,
-        b'\t' => -1,
-        b'\n' => -1,
-        b'\x0b' => -2,
-        b'\x0c' => -1,
-        b'\r' => -1,
-        b'\x0e' => -1,
-        b'\x0f' => 1,
-        b'\x10' => 0,
-        b'\x11' => 0,
-        b'\x12' => -3,
-        b'\x13' => -2,
-        b'\x14' => 9,
-        b'\x15' => -3,
-        b'\x16' => -4,
-        b'\x17' => -2,
-        b'\x18' => -3,
-        b'\x19' => -3,
-        b'\x1a' => -1,
-        b'\x1b' => -3,
-        b'\x1c' => -1,
-        b'\x1d' => -1,
-        b'\x1e' => -3,
-        b'\x1f' => -3,
-        b' ' => -3,
-        b'!' => -3,
-        b'"' => -1,
-        b'#' => -1,
-        b'$' => -1,
-        b'%' => -2,
-        b'&' => -2,
-        b'\'' => 6,
-        b'(' => 2,
-        b')' => -3,
-        b'*' => -1,
-        b'+' => -1,
-        b',' => -3,
-        b'-' => -1,
-        b'.' => -4,
-        b'/' => -3,
-        b'0' => 1,
-        b'1' => -1,
-        b'2' => 0,
-        b'3' => -2,
-        b'4' => 0,
-        b'5' => -1,
-        b'6' => -3,
-        b'7' => -4,
-        b'8' => -3,
-        b'9' => 5,
-        b':' => -3,
-        b';' => -2,
-        b'<' => 0,
-        b'=' => -3,
-        b'>' => 1,
-        b'?' => -3,
-        b'@' => -2,
-        b'A' => 0,
-        b'B' => -1,
-        b'C' => 2,
-        b'D' => 0,
-        b'E' => 0,
-        b'F' => -1,
-        b'G' => -2,
-        b'H' => -3,
-        b'I' => -2,
-        b'J' => 6,
-        b'K' => -3,
-        b'L' => -1,
-        b'M' => 0,
-        b'N' => -3,
-        b'O' => 0,
-        b'P' => 0,
-        b'Q' => -3,
-        b'R' => -4,
-        b'S' => -3,
-        b'T' => -3,
-        b'U' => -2,
-        b'V' => -2,
-        b'W' => -1,
-        b'X' => 1,
-        b'Y' => 3,
-        b'Z' => 6,
-        b'[' => -2,
-        b'\\' => -4,
-        b']' => -2,
-        b'^' => -4,
-        b'_' => -3,
-        b'`' => 0,
-        b'a' => -2,
-        b'b' => -2,
-        b'c' => -2,
-        b'd' => 0,
-        b'e' => -2,
-        b'f' => -3,
-        b'g' => -2,
-        b'h' => -3,
-        b'i' => 8,
-        b'j' => -3,
-        b'k' => -1,
-        b'l' => -3,
-        b'm' => -2,
-        b'n' => 1,
-        b'o' => -2,
-        b'p' => 0,
-        b'q' => 0,
-        b'r' => -1,
-        b's' => -2,
-        b't' => -3,
-        b'u' => -2,
-        b'v' => 2,
-        b'w' => 4,
-        b'x' => -3,
-        b'y' => 2,
-        b'z' => 1,
-        b'{' => -3,
-        b'|' => -3,
-        b'}' => -3,
-        b'~' => -3,
-        b'\x7f' => -2,
-        b'\x80' => -1,
-        b'\x81' => 3,
-        b'\x82' => -3,
-        b'\x83' => -1,
-        b'\x84' => 5,
-        b'\x85' => -2,
-        b'\x86' => -1,
-        b'\x87' => 0,
-        b'\x88' => -1,
-        b'\x89' => 1,
-        b'\x8a' => 2,
-        b'\x8b' => 0,
-        b'\x8c' => -1,
-        b'\x8d' => -2,
-        b'\x8e' => -3,
-        b'\x8f' => -2,
-        b'\x90' => 4,
-        b'\x91' => 2,
-        b'\x92' => -3,
-        b'\x93' => -3,
-        b'\x94' => -2,
-        b'\x95' => -2,
-        b'\x96' => -2,
-        b'\x97' => -1,
-        b'\x98' => 1,
-        b'\x99' => -2,
-        b'\x9a' => -1,
-        b'\x9b' => 5,
-        b'\x9c' => -2,
-        b'\x9d' => -2,
-        b'\x9e' => 0,
-        b'\x9f' => -1,
-        b'\xa0' => -1,
-        b'\xa1' => -1,
-        b'\xa2' => 1,
-        b'\xa3' => -1,
-        b'\xa4' => -1,
-        b'\xa5' => 6,
-        b'\xa6' => -2,
-        b'\xa7' => 0,
-        b'\xa8' => 0,
-        b'\xa9' => 1,
-        b'\xaa' => 0,
-        b'\xab' => -3,
-        b'\xac' => -4,
-        b'\xad' => -2,
-        b'\xae' => 7,
-        b'\xaf' => -1,
-        b'\xb0' => -2,
-        b'\xb1' => -1,
-        b'\xb2' => -1,
-        b'\xb3' => -2,
-        b'\xb4' => -4,
-        b'\xb5' => -3,
-        b'\xb6' => 5,
-        b'\xb7' => 1,
-        b'\xb8' => 0,
-        b'\xb9' => -1,
-        b'\xba' => -2,
-        b'\xbb' => -2,
-        b'\xbc' => -1,
-        b'\xbd' => 5,
-        b'\xbe' => -1,
-        b'\xbf' => -1,
-        b'\xc0' => -3,
-        b'\xc1' => -3,
-        b'\xc2' => -2,
-        b'\xc3' => 4,
-        b'\xc4' => 1,
-        b'\xc5' => -2,
-        b'\xc6' => -3,
-        b'\xc7' => -2,
-        b'\xc8' => 5,
-        b'\xc9' => 0,
-        b'\xca' => -2,
-        b'\xcb' => -2,
-        b'\xcc' => 4,
-        b'\xcd' => -3,
-        b'\xce' => -1,
-        b'\xcf' => 11,
-        b'\xd0' => 2,
-        b'\xd1' => 7,
-        _ => panic!("Code not found. The table may be incomplete."),
+/// Index of a standard amino acid in STANDARD_CODES. None for the other codes, including
+/// Selenocysteine and Pyrrolysine, which only share the rows of standard ones.
+pub(super) const fn standard_index(code: Aac) -> Option<usize> {
+    match code {
+        Aac::U | Aac::O => None,
+        _ => match map_aminoacid_to_index(code) {
+            index if index < STANDARD => Some(index),
+            _ => None,
+        },
     }
 }
 
-/// Get BLOSUM 45 scores
-pub const fn read_blosum45(code_1: Aac, code_2: Aac) -> i8 {
-    let index = map_aminoacid_to_index(code_1, code_2);
+/// NCBI BLOSUM 45.
+#[rustfmt::skip]
+pub(super) const BLOSUM45: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -1, -2, -1, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -2, -2,  0, -1, -1, -1,  0, -5], // A
+    [-2,  7,  0, -1, -3,  1,  0, -2,  0, -3, -2,  3, -1, -2, -2, -1, -1, -2, -1, -2, -1, -3,  0, -1, -5], // R
+    [-1,  0,  6,  2, -2,  0,  0,  0,  1, -2, -3,  0, -2, -2, -2,  1,  0, -4, -2, -3,  4, -3,  0, -1, -5], // N
+    [-2, -1,  2,  7, -3,  0,  2, -1,  0, -4, -3,  0, -3, -4, -1,  0, -1, -4, -2, -3,  5, -4,  1, -1, -5], // D
+    [-1, -3, -2, -3, 12, -3, -3, -3, -3, -3, -2, -3, -2, -2, -4, -1, -1, -5, -3, -1, -2, -3, -3, -2, -5], // C
+    [-1,  1,  0,  0, -3,  6,  2, -2,  1, -2, -2,  1,  0, -4, -1,  0, -1, -2, -1, -3,  0, -2,  4, -1, -5], // Q
+    [-1,  0,  0,  2, -3,  2,  6, -2,  0, -3, -2,  1, -2, -3,  0,  0, -1, -3, -2, -3,  1, -3,  4, -1, -5], // E
+    [ 0, -2,  0, -1, -3, -2, -2,  7, -2, -4, -3, -2, -2, -3, -2,  0, -2, -2, -3, -3, -1, -4, -2, -1, -5], // G
+    [-2,  0,  1,  0, -3,  1,  0, -2, 10, -3, -2, -1,  0, -2, -2, -1, -2, -3,  2, -3,  0, -3,  0, -1, -5], // H
+    [-1, -3, -2, -4, -3, -2, -3, -4, -3,  5,  2, -3,  2,  0, -2, -2, -1, -2,  0,  3, -3,  4, -3, -1, -5], // I
+    [-1, -2, -3, -3, -2, -2, -2, -3, -2,  2,  5, -3,  2,  1, -3, -3, -1, -2,  0,  1, -3,  4, -2, -1, -5], // L
+    [-1,  3,  0,  0, -3,  1,  1, -2, -1, -3, -3,  5, -1, -3, -1, -1, -1, -2, -1, -2,  0, -3,  1, -1, -5], // K
+    [-1, -1, -2, -3, -2,  0, -2, -2,  0,  2,  2, -1,  6,  0, -2, -2, -1, -2,  0,  1, -2,  2, -1, -1, -5], // M
+    [-2, -2, -2, -4, -2, -4, -3, -3, -2,  0,  1, -3,  0,  8, -3, -2, -1,  1,  3,  0, -3,  1, -3, -1, -5], // F
+    [-1, -2, -2, -1, -4, -1,  0, -2, -2, -2, -3, -1, -2, -3,  9, -1, -1, -3, -3, -3, -2, -3, -1, -1, -5], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -3, -1, -2, -2, -1,  4,  2, -4, -2, -1,  0, -3,  0,  0, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -1, -1,  2,  5, -3, -1,  0,  0, -1, -1,  0, -5], // T
+    [-2, -2, -4, -4, -5, -2, -3, -2, -3, -2, -2, -2, -2,  1, -3, -4, -3, 15,  3, -3, -4, -2, -2, -2, -5], // W
+    [-2, -1, -2, -2, -3, -1, -2, -3,  2,  0,  0, -1,  0,  3, -3, -2, -1,  3,  8, -1, -2,  0, -2, -1, -5], // Y
+    [ 0, -2, -3, -3, -1, -3, -3, -3, -3,  3,  1, -2,  1,  0, -3, -1,  0, -3, -1,  5, -3,  2, -3, -1, -5], // V
+    [-1, -1,  4,  5, -2,  0,  1, -1,  0, -3, -3,  0, -2, -3, -2,  0,  0, -4, -2, -3,  4, -3,  2, -1, -5], // B
+    [-1, -3, -3, -4, -3, -2, -3, -4, -3,  4,  4, -3,  2,  1, -3, -3, -1, -2,  0,  2, -3,  4, -3, -1, -5], // J
+    [-1,  0,  0,  1, -3,  4,  4, -2,  0, -3, -2,  1, -1, -3, -1,  0, -1, -2, -2, -3,  2, -3,  4, -1, -5], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,  0,  0, -2, -1, -1, -1, -1, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
 
-    match index {
-        b'\x00' => 5,
-        b'\x01' => -1,
-        b'\x02' => -2,
-        b'\x03' => -1,
-        b'\x04' => -2,
-        b'\x05' => 0,
-        b'\x06' => -2,
-        b'\x07' => -1,
-        b'\x08' => -1,
-        b'\t' => -1,
-        b'\n' => -1,
-        b'\x0b' => -1,
-        b'\x0c' => -1,
-        b'\r' => -1,
-        b'\x0e' => -2,
-        b'\x0f' => 1,
-        b'\x10' => 0,
-        b'\x11' => 0,
-        b'\x12' => -2,
-        b'\x13' => -2,
-        b'\x14' => 12,
-        b'\x15' => -3,
-        b'\x16' => -3,
-        b'\x17' => -2,
-        b'\x18' => -3,
-        b'\x19' => -3,
-        b'\x1a' => -3,
-        b'\x1b' => -3,
-        b'\x1c' => -2,
-        b'\x1d' => -2,
-        b'\x1e' => -2,
-        b'\x1f' => -4,
-        b' ' => -3,
-        b'!' => -3,
-        b'"' => -1,
-        b'#' => -1,
-        b'$' => -1,
-        b'%' => -5,
-        b'&' => -3,
-        b'\'' => 7,
-        b'(' => 2,
-        b')' => -4,
-        b'*' => -1,
-        b'+' => 0,
-        b',' => -4,
-        b'-' => 0,
-        b'.' => -3,
-        b'/' => -3,
-        b'0' => 2,
-        b'1' => -1,
-        b'2' => 0,
-        b'3' => -1,
-        b'4' => 0,
-        b'5' => -1,
-        b'6' => -3,
-        b'7' => -4,
-        b'8' => -2,
-        b'9' => 6,
-        b':' => -3,
-        b';' => -2,
-        b'<' => 0,
-        b'=' => -3,
-        b'>' => 1,
-        b'?' => -2,
-        b'@' => -2,
-        b'A' => 0,
-        b'B' => 0,
-        b'C' => 2,
-        b'D' => 0,
-        b'E' => 0,
-        b'F' => -1,
-        b'G' => -3,
-        b'H' => -3,
-        b'I' => -2,
-        b'J' => 8,
-        b'K' => -3,
-        b'L' => -2,
-        b'M' => 0,
-        b'N' => -3,
-        b'O' => 1,
-        b'P' => 0,
-        b'Q' => -2,
-        b'R' => -3,
-        b'S' => -4,
-        b'T' => -2,
-        b'U' => -2,
-        b'V' => -1,
-        b'W' => 0,
-        b'X' => 1,
-        b'Y' => 3,
-        b'Z' => 7,
-        b'[' => -2,
-        b'\\' => -4,
-        b']' => -2,
-        b'^' => -3,
-        b'_' => -2,
-        b'`' => 0,
-        b'a' => -2,
-        b'b' => -2,
-        b'c' => -2,
-        b'd' => 0,
-        b'e' => -2,
-        b'f' => -3,
-        b'g' => -2,
-        b'h' => -3,
-        b'i' => 10,
-        b'j' => -3,
-        b'k' => -1,
-        b'l' => -2,
-        b'm' => 0,
-        b'n' => 1,
-        b'o' => -2,
-        b'p' => 1,
-        b'q' => 0,
-        b'r' => -1,
-        b's' => -2,
-        b't' => -3,
-        b'u' => -3,
-        b'v' => 2,
-        b'w' => 5,
-        b'x' => -3,
-        b'y' => 2,
-        b'z' => 2,
-        b'{' => -2,
-        b'|' => -2,
-        b'}' => -2,
-        b'~' => -3,
-        b'\x7f' => -2,
-        b'\x80' => -1,
-        b'\x81' => 3,
-        b'\x82' => -2,
-        b'\x83' => 0,
-        b'\x84' => 5,
-        b'\x85' => -3,
-        b'\x86' => -1,
-        b'\x87' => 0,
-        b'\x88' => -1,
-        b'\x89' => 1,
-        b'\x8a' => 3,
-        b'\x8b' => -1,
-        b'\x8c' => -1,
-        b'\x8d' => -2,
-        b'\x8e' => -2,
-        b'\x8f' => -1,
-        b'\x90' => 5,
-        b'\x91' => 2,
-        b'\x92' => -3,
-        b'\x93' => -3,
-        b'\x94' => -2,
-        b'\x95' => -2,
-        b'\x96' => -3,
-        b'\x97' => -1,
-        b'\x98' => 1,
-        b'\x99' => -2,
-        b'\x9a' => 0,
-        b'\x9b' => 6,
-        b'\x9c' => -2,
-        b'\x9d' => -2,
-        b'\x9e' => 0,
-        b'\x9f' => -1,
-        b'\xa0' => -2,
-        b'\xa1' => -1,
-        b'\xa2' => 1,
-        b'\xa3' => -2,
-        b'\xa4' => 0,
-        b'\xa5' => 6,
-        b'\xa6' => -2,
-        b'\xa7' => 0,
-        b'\xa8' => 0,
-        b'\xa9' => 1,
-        b'\xaa' => 0,
-        b'\xab' => -3,
-        b'\xac' => -4,
-        b'\xad' => -2,
-        b'\xae' => 9,
-        b'\xaf' => -1,
-        b'\xb0' => -2,
-        b'\xb1' => -1,
-        b'\xb2' => -1,
-        b'\xb3' => -3,
-        b'\xb4' => -3,
-        b'\xb5' => -3,
-        b'\xb6' => 6,
-        b'\xb7' => 1,
-        b'\xb8' => 0,
-        b'\xb9' => -1,
-        b'\xba' => -3,
-        b'\xbb' => -2,
-        b'\xbc' => -1,
-        b'\xbd' => 7,
-        b'\xbe' => -1,
-        b'\xbf' => -1,
-        b'\xc0' => -2,
-        b'\xc1' => -2,
-        b'\xc2' => -1,
-        b'\xc3' => 4,
-        b'\xc4' => 2,
-        b'\xc5' => -1,
-        b'\xc6' => -4,
-        b'\xc7' => -2,
-        b'\xc8' => 5,
-        b'\xc9' => 0,
-        b'\xca' => -3,
-        b'\xcb' => -1,
-        b'\xcc' => 5,
-        b'\xcd' => -3,
-        b'\xce' => -1,
-        b'\xcf' => 15,
-        b'\xd0' => 3,
-        b'\xd1' => 8,
-        _ => panic!("Code not found. The table may be incomplete."),
-    }
-}
+/// NCBI BLOSUM 62.
+#[rustfmt::skip]
+pub(super) const BLOSUM62: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 4, -1, -2, -2,  0, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -3, -2,  0, -2, -1, -1,  0, -4], // A
+    [-1,  5,  0, -2, -3,  1,  0, -2,  0, -3, -2,  2, -1, -3, -2, -1, -1, -3, -2, -3, -1, -2,  0, -1, -4], // R
+    [-2,  0,  6,  1, -3,  0,  0,  0,  1, -3, -3,  0, -2, -3, -2,  1,  0, -4, -2, -3,  3, -3,  0, -1, -4], // N
+    [-2, -2,  1,  6, -3,  0,  2, -1, -1, -3, -4, -1, -3, -3, -1,  0, -1, -4, -3, -3,  4, -3,  1, -1, -4], // D
+    [ 0, -3, -3, -3,  9, -3, -4, -3, -3, -1, -1, -3, -1, -2, -3, -1, -1, -2, -2, -1, -3, -1, -3, -2, -4], // C
+    [-1,  1,  0,  0, -3,  5,  2, -2,  0, -3, -2,  1,  0, -3, -1,  0, -1, -2, -1, -2,  0, -2,  3, -1, -4], // Q
+    [-1,  0,  0,  2, -4,  2,  5, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -2,  1, -3,  4, -1, -4], // E
+    [ 0, -2,  0, -1, -3, -2, -2,  6, -2, -4, -4, -2, -3, -3, -2,  0, -2, -2, -3, -3, -1, -4, -2, -1, -4], // G
+    [-2,  0,  1, -1, -3,  0,  0, -2,  8, -3, -3, -1, -2, -1, -2, -1, -2, -2,  2, -3,  0, -3,  0, -1, -4], // H
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  4,  2, -3,  1,  0, -3, -2, -1, -3, -1,  3, -3,  3, -3, -1, -4], // I
+    [-1, -2, -3, -4, -1, -2, -3, -4, -3,  2,  4, -2,  2,  0, -3, -2, -1, -2, -1,  1, -4,  3, -3, -1, -4], // L
+    [-1,  2,  0, -1, -3,  1,  1, -2, -1, -3, -2,  5, -1, -3, -1,  0, -1, -3, -2, -2,  0, -3,  1, -1, -4], // K
+    [-1, -1, -2, -3, -1,  0, -2, -3, -2,  1,  2, -1,  5,  0, -2, -1, -1, -1, -1,  1, -3,  2, -1, -1, -4], // M
+    [-2, -3, -3, -3, -2, -3, -3, -3, -1,  0,  0, -3,  0,  6, -4, -2, -2,  1,  3, -1, -3,  0, -3, -1, -4], // F
+    [-1, -2, -2, -1, -3, -1, -1, -2, -2, -3, -3, -1, -2, -4,  7, -1, -1, -4, -3, -2, -2, -3, -1, -2, -4], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -2,  0, -1, -2, -1,  4,  1, -3, -2, -2,  0, -2,  0,  0, -4], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  1,  5, -2, -2,  0, -1, -1, -1,  0, -4], // T
+    [-3, -3, -4, -4, -2, -2, -3, -2, -2, -3, -2, -3, -1,  1, -4, -3, -2, 11,  2, -3, -4, -2, -3, -2, -4], // W
+    [-2, -2, -2, -3, -2, -1, -2, -3,  2, -1, -1, -2, -1,  3, -3, -2, -2,  2,  7, -1, -3, -1, -2, -1, -4], // Y
+    [ 0, -3, -3, -3, -1, -2, -2, -3, -3,  3,  1, -2,  1, -1, -2, -2,  0, -3, -1,  4, -3,  2, -2, -1, -4], // V
+    [-2, -1,  3,  4, -3,  0,  1, -1,  0, -3, -4,  0, -3, -3, -2,  0, -1, -4, -3, -3,  4, -3,  1, -1, -4], // B
+    [-1, -2, -3, -3, -1, -2, -3, -4, -3,  3,  3, -3,  2,  0, -3, -2, -1, -2, -1,  2, -3,  3, -3, -1, -4], // J
+    [-1,  0,  0,  1, -3,  3,  4, -2,  0, -3, -3,  1, -1, -3, -1,  0, -1, -3, -2, -2,  1, -3,  4, -1, -4], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -2,  0,  0, -2, -1, -1, -1, -1, -1, -1, -4], // X
+    [-4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,  1], // *
+];
 
-/// Get PAM 160 scores
-pub const fn read_pam160(code_1: Aac, code_2: Aac) -> i8 {
-    let index = map_aminoacid_to_index(code_1, code_2);
-    match index {
-        b'\x00' => 2,
-        b'\x01' => -2,
-        b'\x02' => 0,
-        b'\x03' => 0,
-        b'\x04' => -3,
-        b'\x05' => 1,
-        b'\x06' => -2,
-        b'\x07' => -1,
-        b'\x08' => -2,
-        b'\t' => -2,
-        b'\n' => -1,
-        b'\x0b' => 0,
-        b'\x0c' => 1,
-        b'\r' => -1,
-        b'\x0e' => -2,
-        b'\x0f' => 1,
-        b'\x10' => 1,
-        b'\x11' => 0,
-        b'\x12' => -5,
-        b'\x13' => -3,
-        b'\x14' => 9,
-        b'\x15' => -5,
-        b'\x16' => -5,
-        b'\x17' => -5,
-        b'\x18' => -3,
-        b'\x19' => -3,
-        b'\x1a' => -2,
-        b'\x1b' => -5,
-        b'\x1c' => -6,
-        b'\x1d' => -5,
-        b'\x1e' => -4,
-        b'\x1f' => -3,
-        b' ' => -5,
-        b'!' => -3,
-        b'"' => 0,
-        b'#' => -2,
-        b'$' => -2,
-        b'%' => -7,
-        b'&' => 0,
-        b'\'' => 4,
-        b'(' => 3,
-        b')' => -6,
-        b'*' => 0,
-        b'+' => 0,
-        b',' => -3,
-        b'-' => 0,
-        b'.' => -4,
-        b'/' => -3,
-        b'0' => 2,
-        b'1' => -2,
-        b'2' => 1,
-        b'3' => -2,
-        b'4' => 0,
-        b'5' => -1,
-        b'6' => -3,
-        b'7' => -6,
-        b'8' => -4,
-        b'9' => 4,
-        b':' => -5,
-        b';' => 0,
-        b'<' => 0,
-        b'=' => -2,
-        b'>' => -1,
-        b'?' => -3,
-        b'@' => -2,
-        b'A' => 1,
-        b'B' => -1,
-        b'C' => 2,
-        b'D' => -2,
-        b'E' => 0,
-        b'F' => -1,
-        b'G' => -2,
-        b'H' => -7,
-        b'I' => -4,
-        b'J' => 7,
-        b'K' => -4,
-        b'L' => -2,
-        b'M' => 0,
-        b'N' => -5,
-        b'O' => 1,
-        b'P' => 0,
-        b'Q' => -3,
-        b'R' => -4,
-        b'S' => -5,
-        b'T' => -4,
-        b'U' => -3,
-        b'V' => -3,
-        b'W' => -2,
-        b'X' => -1,
-        b'Y' => 5,
-        b'Z' => 4,
-        b'[' => -3,
-        b'\\' => -3,
-        b']' => -2,
-        b'^' => -4,
-        b'_' => -3,
-        b'`' => 0,
-        b'a' => -1,
-        b'b' => -2,
-        b'c' => -3,
-        b'd' => 1,
-        b'e' => -1,
-        b'f' => -2,
-        b'g' => -7,
-        b'h' => -5,
-        b'i' => 6,
-        b'j' => -3,
-        b'k' => -1,
-        b'l' => -2,
-        b'm' => -3,
-        b'n' => 2,
-        b'o' => -1,
-        b'p' => 2,
-        b'q' => 1,
-        b'r' => -1,
-        b's' => -2,
-        b't' => -2,
-        b'u' => -3,
-        b'v' => 0,
-        b'w' => 5,
-        b'x' => -2,
-        b'y' => 2,
-        b'z' => 2,
-        b'{' => -2,
-        b'|' => -2,
-        b'}' => -2,
-        b'~' => -2,
-        b'\x7f' => -2,
-        b'\x80' => 0,
-        b'\x81' => 3,
-        b'\x82' => -5,
-        b'\x83' => -2,
-        b'\x84' => 4,
-        b'\x85' => -3,
-        b'\x86' => 0,
-        b'\x87' => 1,
-        b'\x88' => -2,
-        b'\x89' => 0,
-        b'\x8a' => 3,
-        b'\x8b' => -1,
-        b'\x8c' => 0,
-        b'\x8d' => -3,
-        b'\x8e' => -4,
-        b'\x8f' => -4,
-        b'\x90' => 5,
-        b'\x91' => 3,
-        b'\x92' => -3,
-        b'\x93' => -3,
-        b'\x94' => -2,
-        b'\x95' => -3,
-        b'\x96' => -3,
-        b'\x97' => -2,
-        b'\x98' => 1,
-        b'\x99' => -2,
-        b'\x9a' => -2,
-        b'\x9b' => 7,
-        b'\x9c' => -2,
-        b'\x9d' => -2,
-        b'\x9e' => -1,
-        b'\x9f' => -1,
-        b'\xa0' => -2,
-        b'\xa1' => -1,
-        b'\xa2' => 1,
-        b'\xa3' => -4,
-        b'\xa4' => -3,
-        b'\xa5' => 3,
-        b'\xa6' => -1,
-        b'\xa7' => 0,
-        b'\xa8' => -1,
-        b'\xa9' => 1,
-        b'\xaa' => 0,
-        b'\xab' => -2,
-        b'\xac' => -4,
-        b'\xad' => -2,
-        b'\xae' => 5,
-        b'\xaf' => 0,
-        b'\xb0' => -1,
-        b'\xb1' => 1,
-        b'\xb2' => 0,
-        b'\xb3' => -2,
-        b'\xb4' => -5,
-        b'\xb5' => -5,
-        b'\xb6' => 5,
-        b'\xb7' => 1,
-        b'\xb8' => -1,
-        b'\xb9' => -1,
-        b'\xba' => -2,
-        b'\xbb' => -5,
-        b'\xbc' => -4,
-        b'\xbd' => 6,
-        b'\xbe' => -1,
-        b'\xbf' => -1,
-        b'\xc0' => -3,
-        b'\xc1' => 1,
-        b'\xc2' => -4,
-        b'\xc3' => 2,
-        b'\xc4' => 1,
-        b'\xc5' => -1,
-        b'\xc6' => -2,
-        b'\xc7' => -3,
-        b'\xc8' => 3,
-        b'\xc9' => 0,
-        b'\xca' => -6,
-        b'\xcb' => -3,
-        b'\xcc' => 4,
-        b'\xcd' => -6,
-        b'\xce' => -3,
-        b'\xcf' => 12,
-        b'\xd0' => -1,
-        b'\xd1' => 8,
-        _ => panic!("Code not found. The table may be incomplete."),
-    }
+/// NCBI PAM 160.
+#[rustfmt::skip]
+pub(super) const PAM160: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 2, -2,  0,  0, -2, -1,  0,  1, -2, -1, -2, -2, -1, -3,  1,  1,  1, -5, -3,  0,  0, -2,  0,  0, -7], // A
+    [-2,  6, -1, -2, -3,  1, -2, -3,  1, -2, -3,  3, -1, -4, -1, -1, -1,  1, -4, -3, -1, -3,  0, -1, -7], // R
+    [ 0, -1,  3,  2, -4,  0,  1,  0,  2, -2, -3,  1, -2, -3, -1,  1,  0, -4, -2, -2,  2, -3,  1,  0, -7], // N
+    [ 0, -2,  2,  4, -5,  1,  3,  0,  0, -3, -4,  0, -3, -6, -2,  0, -1, -6, -4, -3,  3, -4,  2, -1, -7], // D
+    [-2, -3, -4, -5,  9, -5, -5, -3, -3, -2, -6, -5, -5, -5, -3,  0, -2, -7,  0, -2, -4, -4, -5, -3, -7], // C
+    [-1,  1,  0,  1, -5,  5,  2, -2,  2, -2, -2,  0, -1, -5,  0, -1, -1, -5, -4, -2,  1, -2,  3, -1, -7], // Q
+    [ 0, -2,  1,  3, -5,  2,  4,  0,  0, -2, -3, -1, -2, -5, -1,  0, -1, -7, -4, -2,  2, -3,  3, -1, -7], // E
+    [ 1, -3,  0,  0, -3, -2,  0,  4, -3, -3, -4, -2, -3, -4, -1,  1, -1, -7, -5, -2,  0, -4, -1, -1, -7], // G
+    [-2,  1,  2,  0, -3,  2,  0, -3,  6, -3, -2, -1, -3, -2, -1, -1, -2, -3,  0, -2,  1, -3,  1, -1, -7], // H
+    [-1, -2, -2, -3, -2, -2, -2, -3, -3,  5,  2, -2,  2,  0, -2, -2,  0, -5, -2,  3, -2,  4, -2, -1, -7], // I
+    [-2, -3, -3, -4, -6, -2, -3, -4, -2,  2,  5, -3,  3,  1, -3, -3, -2, -2, -2,  1, -3,  4, -3, -2, -7], // L
+    [-2,  3,  1,  0, -5,  0, -1, -2, -1, -2, -3,  4,  0, -5, -2, -1,  0, -4, -4, -3,  0, -3,  0, -1, -7], // K
+    [-1, -1, -2, -3, -5, -1, -2, -3, -3,  2,  3,  0,  7,  0, -2, -2, -1, -4, -3,  1, -2,  3, -1, -1, -7], // M
+    [-3, -4, -3, -6, -5, -5, -5, -4, -2,  0,  1, -5,  0,  7, -4, -3, -3, -1,  5, -2, -4,  1, -5, -2, -7], // F
+    [ 1, -1, -1, -2, -3,  0, -1, -1, -1, -2, -3, -2, -2, -4,  5,  1,  0, -5, -5, -2, -1, -3, -1, -1, -7], // P
+    [ 1, -1,  1,  0,  0, -1,  0,  1, -1, -2, -3, -1, -2, -3,  1,  2,  1, -2, -3, -1,  0, -3, -1,  0, -7], // S
+    [ 1, -1,  0, -1, -2, -1, -1, -1, -2,  0, -2,  0, -1, -3,  0,  1,  3, -6, -3,  0,  0, -1, -1, -1, -7], // T
+    [-5,  1, -4, -6, -7, -5, -7, -7, -3, -5, -2, -4, -4, -1, -5, -2, -6, 12, -1, -6, -5, -4, -6, -5, -7], // W
+    [-3, -4, -2, -4,  0, -4, -4, -5,  0, -2, -2, -4, -3,  5, -5, -3, -3, -1,  8, -3, -3, -2, -4, -3, -7], // Y
+    [ 0, -3, -2, -3, -2, -2, -2, -2, -2,  3,  1, -3,  1, -2, -2, -1,  0, -6, -3,  4, -2,  2, -2, -1, -7], // V
+    [ 0, -1,  2,  3, -4,  1,  2,  0,  1, -2, -3,  0, -2, -4, -1,  0,  0, -5, -3, -2,  3, -3,  2, -1, -7], // B
+    [-2, -3, -3, -4, -4, -2, -3, -4, -3,  4,  4, -3,  3,  1, -3, -3, -1, -4, -2,  2, -3,  4, -3, -2, -7], // J
+    [ 0,  0,  1,  2, -5,  3,  3, -1,  1, -2, -3,  0, -1, -5, -1, -1, -1, -6, -4, -2,  2, -3,  3, -1, -7], // Z
+    [ 0, -1,  0, -1, -3, -1, -1, -1, -1, -1, -2, -1, -1, -2, -1,  0, -1, -5, -3, -1, -1, -2, -1, -1, -7], // X
+    [-7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7,  1], // *
+];
+
+/// NCBI BLOSUM 30.
+#[rustfmt::skip]
+pub(super) const BLOSUM30: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 4, -1,  0,  0, -3,  1,  0,  0, -2,  0, -1,  0,  1, -2, -1,  1,  1, -5, -4,  1,  0, -1,  0,  0, -7], // A
+    [-1,  8, -2, -1, -2,  3, -1, -2, -1, -3, -2,  1,  0, -1, -1, -1, -3,  0,  0, -1, -2, -3,  0, -1, -7], // R
+    [ 0, -2,  8,  1, -1, -1, -1,  0, -1,  0, -2,  0,  0, -1, -3,  0,  1, -7, -4, -2,  4, -1, -1,  0, -7], // N
+    [ 0, -1,  1,  9, -3, -1,  1, -1, -2, -4, -1,  0, -3, -5, -1,  0, -1, -4, -1, -2,  5, -3,  0, -1, -7], // D
+    [-3, -2, -1, -3, 17, -2,  1, -4, -5, -2,  0, -3, -2, -3, -3, -2, -2, -2, -6, -2, -2, -1,  0, -2, -7], // C
+    [ 1,  3, -1, -1, -2,  8,  2, -2,  0, -2, -2,  0, -1, -3,  0, -1,  0, -1, -1, -3, -1, -2,  4,  0, -7], // Q
+    [ 0, -1, -1,  1,  1,  2,  6, -2,  0, -3, -1,  2, -1, -4,  1,  0, -2, -1, -2, -3,  0, -2,  5, -1, -7], // E
+    [ 0, -2,  0, -1, -4, -2, -2,  8, -3, -1, -2, -1, -2, -3, -1,  0, -2,  1, -3, -3,  0, -2, -2, -1, -7], // G
+    [-2, -1, -1, -2, -5,  0,  0, -3, 14, -2, -1, -2,  2, -3,  1, -1, -2, -5,  0, -3, -2, -2,  0, -1, -7], // H
+    [ 0, -3,  0, -4, -2, -2, -3, -1, -2,  6,  2, -2,  1,  0, -3, -1,  0, -3, -1,  4, -2,  4, -3,  0, -7], // I
+    [-1, -2, -2, -1,  0, -2, -1, -2, -1,  2,  4, -2,  2,  2, -3, -2,  0, -2,  3,  1, -1,  3, -1,  0, -7], // L
+    [ 0,  1,  0,  0, -3,  0,  2, -1, -2, -2, -2,  4,  2, -1,  1,  0, -1, -2, -1, -2,  0, -2,  1,  0, -7], // K
+    [ 1,  0,  0, -3, -2, -1, -1, -2,  2,  1,  2,  2,  6, -2, -4, -2,  0, -3, -1,  0, -2,  2, -1,  0, -7], // M
+    [-2, -1, -1, -5, -3, -3, -4, -3, -3,  0,  2, -1, -2, 10, -4, -1, -2,  1,  3,  1, -3,  1, -4, -1, -7], // F
+    [-1, -1, -3, -1, -3,  0,  1, -1,  1, -3, -3,  1, -4, -4, 11, -1,  0, -3, -2, -4, -2, -3,  0, -1, -7], // P
+    [ 1, -1,  0,  0, -2, -1,  0,  0, -1, -1, -2,  0, -2, -1, -1,  4,  2, -3, -2, -1,  0, -2, -1,  0, -7], // S
+    [ 1, -3,  1, -1, -2,  0, -2, -2, -2,  0,  0, -1,  0, -2,  0,  2,  5, -5, -1,  1,  0,  0, -1,  0, -7], // T
+    [-5,  0, -7, -4, -2, -1, -1,  1, -5, -3, -2, -2, -3,  1, -3, -3, -5, 20,  5, -3, -5, -3, -1, -2, -7], // W
+    [-4,  0, -4, -1, -6, -1, -2, -3,  0, -1,  3, -1, -1,  3, -2, -2, -1,  5,  9,  1, -3,  1, -2, -1, -7], // Y
+    [ 1, -1, -2, -2, -2, -3, -3, -3, -3,  4,  1, -2,  0,  1, -4, -1,  1, -3,  1,  5, -2,  3, -3,  0, -7], // V
+    [ 0, -2,  4,  5, -2, -1,  0,  0, -2, -2, -1,  0, -2, -3, -2,  0,  0, -5, -3, -2,  5, -2,  0, -1, -7], // B
+    [-1, -3, -1, -3, -1, -2, -2, -2, -2,  4,  3, -2,  2,  1, -3, -2,  0, -3,  1,  3, -2,  4, -2,  0, -7], // J
+    [ 0,  0, -1,  0,  0,  4,  5, -2,  0, -3, -1,  1, -1, -4,  0, -1, -1, -1, -2, -3,  0, -2,  4,  0, -7], // Z
+    [ 0, -1,  0, -1, -2,  0, -1, -1, -1,  0,  0,  0,  0, -1, -1,  0,  0, -2, -1,  0, -1,  0,  0, -1, -7], // X
+    [-7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7, -7,  1], // *
+];
+
+/// NCBI BLOSUM 35.
+#[rustfmt::skip]
+pub(super) const BLOSUM35: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -1, -1, -1, -2,  0, -1,  0, -2, -1, -2,  0,  0, -2, -2,  1,  0, -2, -1,  0, -1, -2, -1,  0, -5], // A
+    [-1,  8, -1, -1, -3,  2, -1, -2, -1, -3, -2,  2,  0, -1, -2, -1, -2,  0,  0, -1, -1, -3,  0, -1, -5], // R
+    [-1, -1,  7,  1, -1,  1, -1,  1,  1, -1, -2,  0, -1, -1, -2,  0,  0, -2, -2, -2,  4, -2,  0,  0, -5], // N
+    [-1, -1,  1,  8, -3, -1,  2, -2,  0, -3, -2, -1, -3, -3, -1, -1, -1, -3, -2, -2,  5, -3,  1, -1, -5], // D
+    [-2, -3, -1, -3, 15, -3, -1, -3, -4, -4, -2, -2, -4, -4, -4, -3, -1, -5, -5, -2, -2, -3, -2, -2, -5], // C
+    [ 0,  2,  1, -1, -3,  7,  2, -2, -1, -2, -2,  0, -1, -4,  0,  0,  0, -1,  0, -3,  0, -2,  4, -1, -5], // Q
+    [-1, -1, -1,  2, -1,  2,  6, -2, -1, -3, -1,  1, -2, -3,  0,  0, -1, -1, -1, -2,  0, -2,  5, -1, -5], // E
+    [ 0, -2,  1, -2, -3, -2, -2,  7, -2, -3, -3, -1, -1, -3, -2,  1, -2, -1, -2, -3,  0, -3, -2, -1, -5], // G
+    [-2, -1,  1,  0, -4, -1, -1, -2, 12, -3, -2, -2,  1, -3, -1, -1, -2, -4,  0, -4,  0, -3, -1, -1, -5], // H
+    [-1, -3, -1, -3, -4, -2, -3, -3, -3,  5,  2, -2,  1,  1, -1, -2, -1, -1,  0,  4, -2,  4, -3,  0, -5], // I
+    [-2, -2, -2, -2, -2, -2, -1, -3, -2,  2,  5, -2,  3,  2, -3, -2,  0,  0,  0,  2, -2,  4, -2,  0, -5], // L
+    [ 0,  2,  0, -1, -2,  0,  1, -1, -2, -2, -2,  5,  0, -1,  0,  0,  0,  0, -1, -2,  0, -2,  1,  0, -5], // K
+    [ 0,  0, -1, -3, -4, -1, -2, -1,  1,  1,  3,  0,  6,  0, -3, -1,  0,  1,  0,  1, -2,  2, -2,  0, -5], // M
+    [-2, -1, -1, -3, -4, -4, -3, -3, -3,  1,  2, -1,  0,  8, -4, -1, -1,  1,  3,  1, -2,  2, -3, -1, -5], // F
+    [-2, -2, -2, -1, -4,  0,  0, -2, -1, -1, -3,  0, -3, -4, 10, -2,  0, -4, -3, -3, -1, -2,  0, -1, -5], // P
+    [ 1, -1,  0, -1, -3,  0,  0,  1, -1, -2, -2,  0, -1, -1, -2,  4,  2, -2, -1, -1,  0, -2,  0,  0, -5], // S
+    [ 0, -2,  0, -1, -1,  0, -1, -2, -2, -1,  0,  0,  0, -1,  0,  2,  5, -2, -2,  1, -1, -1, -1,  0, -5], // T
+    [-2,  0, -2, -3, -5, -1, -1, -1, -4, -1,  0,  0,  1,  1, -4, -2, -2, 16,  3, -2, -3, -1, -1, -1, -5], // W
+    [-1,  0, -2, -2, -5,  0, -1, -2,  0,  0,  0, -1,  0,  3, -3, -1, -2,  3,  8,  0, -2,  0, -1, -1, -5], // Y
+    [ 0, -1, -2, -2, -2, -3, -2, -3, -4,  4,  2, -2,  1,  1, -3, -1,  1, -2,  0,  5, -2,  3, -2,  0, -5], // V
+    [-1, -1,  4,  5, -2,  0,  0,  0,  0, -2, -2,  0, -2, -2, -1,  0, -1, -3, -2, -2,  5, -2,  0, -1, -5], // B
+    [-2, -3, -2, -3, -3, -2, -2, -3, -3,  4,  4, -2,  2,  2, -2, -2, -1, -1,  0,  3, -2,  4, -3,  0, -5], // J
+    [-1,  0,  0,  1, -2,  4,  5, -2, -1, -3, -2,  1, -2, -3,  0,  0, -1, -1, -1, -2,  0, -3,  4,  0, -5], // Z
+    [ 0, -1,  0, -1, -2, -1, -1, -1, -1,  0,  0,  0,  0, -1, -1,  0,  0, -1, -1,  0, -1,  0,  0, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// NCBI BLOSUM 40.
+#[rustfmt::skip]
+pub(super) const BLOSUM40: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -1, -1, -2,  0, -1,  1, -2, -1, -2, -1, -1, -3, -2,  1,  0, -3, -2,  0, -1, -2, -1,  0, -6], // A
+    [-2,  9,  0, -1, -3,  2, -1, -3,  0, -3, -2,  3, -1, -2, -3, -1, -2, -2, -1, -2, -1, -3,  0, -1, -6], // R
+    [-1,  0,  8,  2, -2,  1, -1,  0,  1, -2, -3,  0, -2, -3, -2,  1,  0, -4, -2, -3,  4, -3,  0, -1, -6], // N
+    [-1, -1,  2,  9, -2, -1,  2, -2,  0, -4, -3,  0, -3, -4, -2,  0, -1, -5, -3, -3,  6, -4,  1, -1, -6], // D
+    [-2, -3, -2, -2, 16, -4, -2, -3, -4, -4, -2, -3, -3, -2, -5, -1, -1, -6, -4, -2, -2, -3, -3, -2, -6], // C
+    [ 0,  2,  1, -1, -4,  8,  2, -2,  0, -3, -2,  1, -1, -4, -2,  1, -1, -1, -1, -3,  0, -3,  4, -1, -6], // Q
+    [-1, -1, -1,  2, -2,  2,  7, -3,  0, -4, -2,  1, -2, -3,  0,  0, -1, -2, -2, -3,  1, -3,  5, -1, -6], // E
+    [ 1, -3,  0, -2, -3, -2, -3,  8, -2, -4, -4, -2, -2, -3, -1,  0, -2, -2, -3, -4, -1, -4, -2, -1, -6], // G
+    [-2,  0,  1,  0, -4,  0,  0, -2, 13, -3, -2, -1,  1, -2, -2, -1, -2, -5,  2, -4,  0, -3,  0, -1, -6], // H
+    [-1, -3, -2, -4, -4, -3, -4, -4, -3,  6,  2, -3,  1,  1, -2, -2, -1, -3,  0,  4, -3,  4, -4, -1, -6], // I
+    [-2, -2, -3, -3, -2, -2, -2, -4, -2,  2,  6, -2,  3,  2, -4, -3, -1, -1,  0,  2, -3,  4, -2, -1, -6], // L
+    [-1,  3,  0,  0, -3,  1,  1, -2, -1, -3, -2,  6, -1, -3, -1,  0,  0, -2, -1, -2,  0, -3,  1, -1, -6], // K
+    [-1, -1, -2, -3, -3, -1, -2, -2,  1,  1,  3, -1,  7,  0, -2, -2, -1, -2,  1,  1, -3,  2, -2,  0, -6], // M
+    [-3, -2, -3, -4, -2, -4, -3, -3, -2,  1,  2, -3,  0,  9, -4, -2, -1,  1,  4,  0, -3,  2, -4, -1, -6], // F
+    [-2, -3, -2, -2, -5, -2,  0, -1, -2, -2, -4, -1, -2, -4, 11, -1,  0, -4, -3, -3, -2, -3, -1, -2, -6], // P
+    [ 1, -1,  1,  0, -1,  1,  0,  0, -1, -2, -3,  0, -2, -2, -1,  5,  2, -5, -2, -1,  0, -3,  0,  0, -6], // S
+    [ 0, -2,  0, -1, -1, -1, -1, -2, -2, -1, -1,  0, -1, -1,  0,  2,  6, -4, -1,  1,  0, -1, -1,  0, -6], // T
+    [-3, -2, -4, -5, -6, -1, -2, -2, -5, -3, -1, -2, -2,  1, -4, -5, -4, 19,  3, -3, -4, -2, -2, -2, -6], // W
+    [-2, -1, -2, -3, -4, -1, -2, -3,  2,  0,  0, -1,  1,  4, -3, -2, -1,  3,  9, -1, -3,  0, -2, -1, -6], // Y
+    [ 0, -2, -3, -3, -2, -3, -3, -4, -4,  4,  2, -2,  1,  0, -3, -1,  1, -3, -1,  5, -3,  3, -3, -1, -6], // V
+    [-1, -1,  4,  6, -2,  0,  1, -1,  0, -3, -3,  0, -3, -3, -2,  0,  0, -4, -3, -3,  5, -3,  2, -1, -6], // B
+    [-2, -3, -3, -4, -3, -3, -3, -4, -3,  4,  4, -3,  2,  2, -3, -3, -1, -2,  0,  3, -3,  4, -3, -1, -6], // J
+    [-1,  0,  0,  1, -3,  4,  5, -2,  0, -4, -2,  1, -2, -4, -1,  0, -1, -2, -2, -3,  2, -3,  5, -1, -6], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1,  0, -1, -2,  0,  0, -2, -1, -1, -1, -1, -1, -1, -6], // X
+    [-6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6,  1], // *
+];
+
+/// NCBI BLOSUM 50.
+#[rustfmt::skip]
+pub(super) const BLOSUM50: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -1, -2, -1, -1, -1,  0, -2, -1, -2, -1, -1, -3, -1,  1,  0, -3, -2,  0, -2, -2, -1, -1, -5], // A
+    [-2,  7, -1, -2, -4,  1,  0, -3,  0, -4, -3,  3, -2, -3, -3, -1, -1, -3, -1, -3, -1, -4,  0, -1, -5], // R
+    [-1, -1,  7,  2, -2,  0,  0,  0,  1, -3, -4,  0, -2, -4, -2,  1,  0, -4, -2, -3,  4, -4,  0, -1, -5], // N
+    [-2, -2,  2,  8, -4,  0,  2, -1, -1, -4, -4, -1, -4, -5, -1,  0, -1, -5, -3, -4,  5, -4,  1, -1, -5], // D
+    [-1, -4, -2, -4, 13, -3, -3, -3, -3, -2, -2, -3, -2, -2, -4, -1, -1, -5, -3, -1, -3, -2, -3, -2, -5], // C
+    [-1,  1,  0,  0, -3,  7,  2, -2,  1, -3, -2,  2,  0, -4, -1,  0, -1, -1, -1, -3,  0, -3,  4, -1, -5], // Q
+    [-1,  0,  0,  2, -3,  2,  6, -3,  0, -4, -3,  1, -2, -3, -1, -1, -1, -3, -2, -3,  1, -4,  5, -1, -5], // E
+    [ 0, -3,  0, -1, -3, -2, -3,  8, -2, -4, -4, -2, -3, -4, -2,  0, -2, -3, -3, -4, -1, -4, -2, -2, -5], // G
+    [-2,  0,  1, -1, -3,  1,  0, -2, 10, -4, -3,  0, -1, -1, -2, -1, -2, -3,  2, -4,  0, -4,  0, -1, -5], // H
+    [-1, -4, -3, -4, -2, -3, -4, -4, -4,  5,  2, -3,  2,  0, -3, -3, -1, -3, -1,  4, -4,  4, -3, -1, -5], // I
+    [-2, -3, -4, -4, -2, -2, -3, -4, -3,  2,  5, -3,  3,  1, -4, -3, -1, -2, -1,  1, -4,  4, -3, -1, -5], // L
+    [-1,  3,  0, -1, -3,  2,  1, -2,  0, -3, -3,  6, -2, -4, -1,  0, -1, -3, -2, -3,  0, -3,  1, -1, -5], // K
+    [-1, -2, -2, -4, -2,  0, -2, -3, -1,  2,  3, -2,  7,  0, -3, -2, -1, -1,  0,  1, -3,  3, -1, -1, -5], // M
+    [-3, -3, -4, -5, -2, -4, -3, -4, -1,  0,  1, -4,  0,  8, -4, -3, -2,  1,  4, -1, -4,  1, -4, -2, -5], // F
+    [-1, -3, -2, -1, -4, -1, -1, -2, -2, -3, -4, -1, -3, -4, 10, -1, -1, -4, -3, -3, -2, -4, -1, -2, -5], // P
+    [ 1, -1,  1,  0, -1,  0, -1,  0, -1, -3, -3,  0, -2, -3, -1,  5,  2, -4, -2, -2,  0, -3,  0, -1, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  2,  5, -3, -2,  0,  0, -1, -1,  0, -5], // T
+    [-3, -3, -4, -5, -5, -1, -3, -3, -3, -3, -2, -3, -1,  1, -4, -4, -3, 15,  2, -3, -5, -3, -2, -3, -5], // W
+    [-2, -1, -2, -3, -3, -1, -2, -3,  2, -1, -1, -2,  0,  4, -3, -2, -2,  2,  8, -1, -3, -1, -2, -1, -5], // Y
+    [ 0, -3, -3, -4, -1, -3, -3, -4, -4,  4,  1, -3,  1, -1, -3, -2,  0, -3, -1,  5, -4,  3, -3, -1, -5], // V
+    [-2, -1,  4,  5, -3,  0,  1, -1,  0, -4, -4,  0, -3, -4, -2,  0,  0, -5, -3, -4,  5, -4,  2, -1, -5], // B
+    [-2, -4, -4, -4, -2, -3, -4, -4, -4,  4,  4, -3,  3,  1, -4, -3, -1, -3, -1,  3, -4,  4, -3, -1, -5], // J
+    [-1,  0,  0,  1, -3,  4,  5, -2,  0, -3, -3,  1, -1, -4, -1,  0, -1, -2, -2, -3,  2, -3,  5, -1, -5], // Z
+    [-1, -1, -1, -1, -2, -1, -1, -2, -1, -1, -1, -1, -1, -2, -2, -1,  0, -3, -1, -1, -1, -1, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// NCBI BLOSUM 55.
+#[rustfmt::skip]
+pub(super) const BLOSUM55: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -2, -2,  0, -1, -1,  0, -2, -2, -2, -1, -1, -3, -1,  2,  0, -4, -2,  0, -2, -2, -1, -1, -5], // A
+    [-2,  8, -1, -2, -4,  1,  0, -3,  0, -4, -3,  3, -2, -3, -3, -1, -1, -3, -2, -3, -1, -4,  0, -1, -5], // R
+    [-2, -1,  8,  2, -3,  0,  0,  0,  1, -4, -4,  0, -3, -4, -2,  1,  0, -5, -2, -4,  4, -4,  0, -1, -5], // N
+    [-2, -2,  2,  8, -4,  0,  2, -2, -1, -4, -5, -1, -4, -5, -2,  0, -1, -5, -3, -4,  5, -5,  1, -2, -5], // D
+    [ 0, -4, -3, -4, 13, -4, -4, -3, -4, -2, -2, -4, -2, -3, -3, -1, -1, -4, -3, -1, -4, -2, -4, -2, -5], // C
+    [-1,  1,  0,  0, -4,  7,  2, -2,  1, -4, -3,  2,  0, -4, -2,  0, -1, -3, -1, -3,  0, -4,  4, -1, -5], // Q
+    [-1,  0,  0,  2, -4,  2,  7, -3, -1, -4, -3,  1, -3, -4, -1,  0, -1, -3, -2, -3,  1, -4,  5, -1, -5], // E
+    [ 0, -3,  0, -2, -3, -2, -3,  8, -2, -5, -5, -2, -3, -4, -3,  0, -2, -3, -4, -4, -1, -5, -3, -2, -5], // G
+    [-2,  0,  1, -1, -4,  1, -1, -2, 11, -4, -3, -1, -2, -1, -3, -1, -2, -3,  2, -4,  0, -4,  0, -1, -5], // H
+    [-2, -4, -4, -4, -2, -4, -4, -5, -4,  6,  2, -4,  2,  0, -3, -3, -1, -3, -1,  4, -4,  4, -4, -1, -5], // I
+    [-2, -3, -4, -5, -2, -3, -3, -5, -3,  2,  6, -3,  3,  1, -4, -3, -2, -3, -1,  1, -4,  4, -3, -1, -5], // L
+    [-1,  3,  0, -1, -4,  2,  1, -2, -1, -4, -3,  6, -2, -4, -1,  0, -1, -4, -2, -3,  0, -4,  1, -1, -5], // K
+    [-1, -2, -3, -4, -2,  0, -3, -3, -2,  2,  3, -2,  8,  0, -3, -2, -1, -2, -1,  1, -3,  3, -2, -1, -5], // M
+    [-3, -3, -4, -5, -3, -4, -4, -4, -1,  0,  1, -4,  0,  9, -5, -3, -3,  2,  4, -1, -5,  1, -4, -2, -5], // F
+    [-1, -3, -2, -2, -3, -2, -1, -3, -3, -3, -4, -1, -3, -5, 10, -1, -1, -5, -4, -3, -2, -4, -1, -2, -5], // P
+    [ 2, -1,  1,  0, -1,  0,  0,  0, -1, -3, -3,  0, -2, -3, -1,  5,  2, -4, -2, -2,  0, -3,  0, -1, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -2, -1, -1, -3, -1,  2,  6, -3, -2,  0, -1, -2, -1, -1, -5], // T
+    [-4, -3, -5, -5, -4, -3, -3, -3, -3, -3, -3, -4, -2,  2, -5, -4, -3, 15,  3, -4, -5, -3, -3, -3, -5], // W
+    [-2, -2, -2, -3, -3, -1, -2, -4,  2, -1, -1, -2, -1,  4, -4, -2, -2,  3,  9, -2, -3, -1, -2, -1, -5], // Y
+    [ 0, -3, -4, -4, -1, -3, -3, -4, -4,  4,  1, -3,  1, -1, -3, -2,  0, -4, -2,  5, -4,  3, -3, -1, -5], // V
+    [-2, -1,  4,  5, -4,  0,  1, -1,  0, -4, -4,  0, -3, -5, -2,  0, -1, -5, -3, -4,  5, -4,  2, -1, -5], // B
+    [-2, -4, -4, -5, -2, -4, -4, -5, -4,  4,  4, -4,  3,  1, -4, -3, -2, -3, -1,  3, -4,  4, -4, -1, -5], // J
+    [-1,  0,  0,  1, -4,  4,  5, -3,  0, -4, -3,  1, -2, -4, -1,  0, -1, -3, -2, -3,  2, -4,  5, -1, -5], // Z
+    [-1, -1, -1, -2, -2, -1, -1, -2, -1, -1, -1, -1, -1, -2, -2, -1, -1, -3, -1, -1, -1, -1, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// NCBI BLOSUM 60.
+#[rustfmt::skip]
+pub(super) const BLOSUM60: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 4, -1, -1, -2,  0, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -3, -2,  0, -2, -1, -1,  0, -4], // A
+    [-1,  5,  0, -1, -3,  1,  0, -2,  0, -3, -2,  2, -1, -3, -2, -1, -1, -3, -2, -2, -1, -3,  0, -1, -4], // R
+    [-1,  0,  6,  1, -2,  0,  0,  0,  1, -3, -3,  0, -2, -3, -2,  1,  0, -4, -2, -3,  3, -3,  0, -1, -4], // N
+    [-2, -1,  1,  6, -3,  0,  2, -1, -1, -3, -3, -1, -3, -3, -1,  0, -1, -4, -3, -3,  4, -3,  1, -1, -4], // D
+    [ 0, -3, -2, -3,  9, -3, -3, -2, -3, -1, -1, -3, -1, -2, -3, -1, -1, -2, -2, -1, -3, -1, -3, -2, -4], // C
+    [-1,  1,  0,  0, -3,  5,  2, -2,  1, -3, -2,  1,  0, -3, -1,  0, -1, -2, -1, -2,  0, -3,  3, -1, -4], // Q
+    [-1,  0,  0,  2, -3,  2,  5, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -2,  1, -3,  4, -1, -4], // E
+    [ 0, -2,  0, -1, -2, -2, -2,  6, -2, -4, -4, -1, -2, -3, -2,  0, -2, -2, -3, -3, -1, -4, -2, -1, -4], // G
+    [-2,  0,  1, -1, -3,  1,  0, -2,  7, -3, -3, -1, -1, -1, -2, -1, -2, -2,  2, -3,  0, -3,  0, -1, -4], // H
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  4,  2, -3,  1,  0, -3, -2, -1, -2, -1,  3, -3,  3, -3, -1, -4], // I
+    [-1, -2, -3, -3, -1, -2, -3, -4, -3,  2,  4, -2,  2,  0, -3, -2, -1, -2, -1,  1, -3,  3, -2, -1, -4], // L
+    [-1,  2,  0, -1, -3,  1,  1, -1, -1, -3, -2,  4, -1, -3, -1,  0, -1, -3, -2, -2,  0, -3,  1, -1, -4], // K
+    [-1, -1, -2, -3, -1,  0, -2, -2, -1,  1,  2, -1,  5,  0, -2, -1, -1, -1, -1,  1, -2,  2, -1, -1, -4], // M
+    [-2, -3, -3, -3, -2, -3, -3, -3, -1,  0,  0, -3,  0,  6, -3, -2, -2,  1,  3,  0, -3,  0, -3, -1, -4], // F
+    [-1, -2, -2, -1, -3, -1, -1, -2, -2, -3, -3, -1, -2, -3,  7, -1, -1, -3, -3, -2, -2, -3, -1, -2, -4], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -2,  0, -1, -2, -1,  4,  1, -3, -2, -1,  0, -2,  0,  0, -4], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  1,  4, -2, -2,  0, -1, -1, -1,  0, -4], // T
+    [-3, -3, -4, -4, -2, -2, -3, -2, -2, -2, -2, -3, -1,  1, -3, -3, -2, 10,  2, -3, -4, -2, -2, -2, -4], // W
+    [-2, -2, -2, -3, -2, -1, -2, -3,  2, -1, -1, -2, -1,  3, -3, -2, -2,  2,  6, -1, -2, -1, -2, -1, -4], // Y
+    [ 0, -2, -3, -3, -1, -2, -2, -3, -3,  3,  1, -2,  1,  0, -2, -1,  0, -3, -1,  4, -3,  2, -2, -1, -4], // V
+    [-2, -1,  3,  4, -3,  0,  1, -1,  0, -3, -3,  0, -2, -3, -2,  0, -1, -4, -2, -3,  4, -3,  1, -1, -4], // B
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  3,  3, -3,  2,  0, -3, -2, -1, -2, -1,  2, -3,  3, -3, -1, -4], // J
+    [-1,  0,  0,  1, -3,  3,  4, -2,  0, -3, -2,  1, -1, -3, -1,  0, -1, -2, -2, -2,  1, -3,  3, -1, -4], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -2,  0,  0, -2, -1, -1, -1, -1, -1, -1, -4], // X
+    [-4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,  1], // *
+];
+
+/// NCBI BLOSUM 65.
+#[rustfmt::skip]
+pub(super) const BLOSUM65: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 4, -1, -2, -2,  0, -1, -1,  0, -2, -1, -2, -1, -1, -2, -1,  1,  0, -3, -2,  0, -2, -2, -1, -1, -5], // A
+    [-1,  6,  0, -2, -4,  1,  0, -2,  0, -3, -2,  2, -2, -3, -2, -1, -1, -3, -2, -3, -1, -3,  0, -1, -5], // R
+    [-2,  0,  6,  1, -3,  0,  0, -1,  1, -3, -4,  0, -2, -3, -2,  1,  0, -4, -2, -3,  3, -4,  0, -1, -5], // N
+    [-2, -2,  1,  6, -4,  0,  2, -1, -1, -3, -4, -1, -3, -4, -2,  0, -1, -5, -3, -3,  4, -4,  1, -1, -5], // D
+    [ 0, -4, -3, -4,  9, -3, -4, -3, -3, -1, -1, -3, -2, -2, -3, -1, -1, -2, -2, -1, -3, -1, -4, -2, -5], // C
+    [-1,  1,  0,  0, -3,  6,  2, -2,  1, -3, -2,  1,  0, -3, -1,  0, -1, -2, -2, -2,  0, -3,  3, -1, -5], // Q
+    [-1,  0,  0,  2, -4,  2,  5, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -3,  1, -3,  4, -1, -5], // E
+    [ 0, -2, -1, -1, -3, -2, -2,  6, -2, -4, -4, -2, -3, -3, -2,  0, -2, -3, -3, -3, -1, -4, -2, -2, -5], // G
+    [-2,  0,  1, -1, -3,  1,  0, -2,  8, -3, -3, -1, -2, -1, -2, -1, -2, -2,  2, -3,  0, -3,  0, -1, -5], // H
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  4,  2, -3,  1,  0, -3, -2, -1, -2, -1,  3, -3,  3, -3, -1, -5], // I
+    [-2, -2, -4, -4, -1, -2, -3, -4, -3,  2,  4, -3,  2,  0, -3, -3, -1, -2, -1,  1, -4,  3, -3, -1, -5], // L
+    [-1,  2,  0, -1, -3,  1,  1, -2, -1, -3, -3,  5, -2, -3, -1,  0, -1, -3, -2, -2, -1, -3,  1, -1, -5], // K
+    [-1, -2, -2, -3, -2,  0, -2, -3, -2,  1,  2, -2,  6,  0, -3, -2, -1, -2, -1,  1, -3,  2, -2, -1, -5], // M
+    [-2, -3, -3, -4, -2, -3, -3, -3, -1,  0,  0, -3,  0,  6, -4, -2, -2,  1,  3, -1, -3,  0, -3, -2, -5], // F
+    [-1, -2, -2, -2, -3, -1, -1, -2, -2, -3, -3, -1, -3, -4,  8, -1, -1, -4, -3, -2, -2, -3, -1, -2, -5], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -3,  0, -2, -2, -1,  4,  1, -3, -2, -2,  0, -3,  0, -1, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  1,  5, -3, -2,  0, -1, -1, -1, -1, -5], // T
+    [-3, -3, -4, -5, -2, -2, -3, -3, -2, -2, -2, -3, -2,  1, -4, -3, -3, 10,  2, -3, -4, -2, -3, -2, -5], // W
+    [-2, -2, -2, -3, -2, -2, -2, -3,  2, -1, -1, -2, -1,  3, -3, -2, -2,  2,  7, -1, -3, -1, -2, -1, -5], // Y
+    [ 0, -3, -3, -3, -1, -2, -3, -3, -3,  3,  1, -2,  1, -1, -2, -2,  0, -3, -1,  4, -3,  2, -2, -1, -5], // V
+    [-2, -1,  3,  4, -3,  0,  1, -1,  0, -3, -4, -1, -3, -3, -2,  0, -1, -4, -3, -3,  4, -4,  1, -1, -5], // B
+    [-2, -3, -4, -4, -1, -3, -3, -4, -3,  3,  3, -3,  2,  0, -3, -3, -1, -2, -1,  2, -4,  3, -3, -1, -5], // J
+    [-1,  0,  0,  1, -4,  3,  4, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -2,  1, -3,  4, -1, -5], // Z
+    [-1, -1, -1, -1, -2, -1, -1, -2, -1, -1, -1, -1, -1, -2, -2, -1, -1, -2, -1, -1, -1, -1, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// NCBI BLOSUM 70.
+#[rustfmt::skip]
+pub(super) const BLOSUM70: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 4, -2, -2, -2, -1, -1, -1,  0, -2, -2, -2, -1, -1, -2, -1,  1,  0, -3, -2,  0, -2, -2, -1, -1, -5], // A
+    [-2,  6, -1, -2, -4,  1, -1, -3,  0, -3, -3,  2, -2, -3, -2, -1, -1, -3, -2, -3, -1, -3,  0, -1, -5], // R
+    [-2, -1,  6,  1, -3,  0,  0, -1,  0, -4, -4,  0, -2, -3, -2,  0,  0, -4, -2, -3,  3, -4,  0, -1, -5], // N
+    [-2, -2,  1,  6, -4, -1,  1, -2, -1, -4, -4, -1, -3, -4, -2,  0, -1, -5, -4, -4,  4, -4,  1, -2, -5], // D
+    [-1, -4, -3, -4,  9, -3, -4, -3, -4, -1, -2, -4, -2, -2, -3, -1, -1, -3, -3, -1, -4, -2, -4, -2, -5], // C
+    [-1,  1,  0, -1, -3,  6,  2, -2,  1, -3, -2,  1,  0, -3, -2,  0, -1, -2, -2, -2,  0, -3,  3, -1, -5], // Q
+    [-1, -1,  0,  1, -4,  2,  5, -2,  0, -4, -3,  1, -2, -4, -1,  0, -1, -4, -3, -3,  1, -4,  4, -1, -5], // E
+    [ 0, -3, -1, -2, -3, -2, -2,  6, -2, -4, -4, -2, -3, -4, -3, -1, -2, -3, -4, -4, -1, -4, -2, -2, -5], // G
+    [-2,  0,  0, -1, -4,  1,  0, -2,  8, -4, -3, -1, -2, -1, -2, -1, -2, -2,  2, -3, -1, -4,  0, -1, -5], // H
+    [-2, -3, -4, -4, -1, -3, -4, -4, -4,  4,  2, -3,  1,  0, -3, -3, -1, -3, -1,  3, -4,  3, -3, -1, -5], // I
+    [-2, -3, -4, -4, -2, -2, -3, -4, -3,  2,  4, -3,  2,  0, -3, -3, -2, -2, -1,  1, -4,  3, -3, -1, -5], // L
+    [-1,  2,  0, -1, -4,  1,  1, -2, -1, -3, -3,  5, -2, -3, -1,  0, -1, -3, -2, -3, -1, -3,  1, -1, -5], // K
+    [-1, -2, -2, -3, -2,  0, -2, -3, -2,  1,  2, -2,  6,  0, -3, -2, -1, -2, -2,  1, -3,  2, -2, -1, -5], // M
+    [-2, -3, -3, -4, -2, -3, -4, -4, -1,  0,  0, -3,  0,  6, -4, -3, -2,  1,  3, -1, -4,  0, -4, -2, -5], // F
+    [-1, -2, -2, -2, -3, -2, -1, -3, -2, -3, -3, -1, -3, -4,  8, -1, -1, -4, -3, -3, -2, -3, -1, -2, -5], // P
+    [ 1, -1,  0,  0, -1,  0,  0, -1, -1, -3, -3,  0, -2, -3, -1,  4,  1, -3, -2, -2,  0, -3,  0, -1, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -2, -1, -1, -2, -1,  1,  5, -3, -2,  0, -1, -2, -1, -1, -5], // T
+    [-3, -3, -4, -5, -3, -2, -4, -3, -2, -3, -2, -3, -2,  1, -4, -3, -3, 11,  2, -3, -4, -3, -3, -3, -5], // W
+    [-2, -2, -2, -4, -3, -2, -3, -4,  2, -1, -1, -2, -2,  3, -3, -2, -2,  2,  7, -2, -3, -1, -2, -1, -5], // Y
+    [ 0, -3, -3, -4, -1, -2, -3, -4, -3,  3,  1, -3,  1, -1, -3, -2,  0, -3, -2,  4, -3,  2, -3, -1, -5], // V
+    [-2, -1,  3,  4, -4,  0,  1, -1, -1, -4, -4, -1, -3, -4, -2,  0, -1, -4, -3, -3,  4, -4,  0, -1, -5], // B
+    [-2, -3, -4, -4, -2, -3, -4, -4, -4,  3,  3, -3,  2,  0, -3, -3, -2, -3, -1,  2, -4,  3, -3, -1, -5], // J
+    [-1,  0,  0,  1, -4,  3,  4, -2,  0, -3, -3,  1, -2, -4, -1,  0, -1, -3, -2, -3,  0, -3,  4, -1, -5], // Z
+    [-1, -1, -1, -2, -2, -1, -1, -2, -1, -1, -1, -1, -1, -2, -2, -1, -1, -3, -1, -1, -1, -1, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// NCBI BLOSUM 75.
+#[rustfmt::skip]
+pub(super) const BLOSUM75: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 4, -2, -2, -2, -1, -1, -1,  0, -2, -2, -2, -1, -1, -3, -1,  1,  0, -3, -2,  0, -2, -2, -1, -1, -5], // A
+    [-2,  6, -1, -2, -4,  1,  0, -3,  0, -3, -3,  2, -2, -3, -2, -1, -1, -3, -2, -3, -1, -3,  0, -1, -5], // R
+    [-2, -1,  6,  1, -3,  0, -1, -1,  0, -4, -4,  0, -3, -4, -3,  0,  0, -4, -3, -3,  3, -4,  0, -1, -5], // N
+    [-2, -2,  1,  6, -4, -1,  1, -2, -1, -4, -4, -1, -4, -4, -2, -1, -1, -5, -4, -4,  4, -4,  1, -2, -5], // D
+    [-1, -4, -3, -4,  9, -3, -5, -3, -4, -1, -2, -4, -2, -2, -4, -1, -1, -3, -3, -1, -4, -2, -4, -2, -5], // C
+    [-1,  1,  0, -1, -3,  6,  2, -2,  1, -3, -3,  1,  0, -4, -2,  0, -1, -2, -2, -2,  0, -3,  3, -1, -5], // Q
+    [-1,  0, -1,  1, -5,  2,  5, -3,  0, -4, -4,  1, -2, -4, -1,  0, -1, -4, -3, -3,  1, -4,  4, -1, -5], // E
+    [ 0, -3, -1, -2, -3, -2, -3,  6, -2, -5, -4, -2, -3, -4, -3, -1, -2, -3, -4, -4, -1, -5, -2, -2, -5], // G
+    [-2,  0,  0, -1, -4,  1,  0, -2,  8, -4, -3, -1, -2, -2, -2, -1, -2, -2,  2, -4, -1, -4,  0, -1, -5], // H
+    [-2, -3, -4, -4, -1, -3, -4, -5, -4,  4,  1, -3,  1,  0, -3, -3, -1, -3, -2,  3, -4,  3, -4, -2, -5], // I
+    [-2, -3, -4, -4, -2, -3, -4, -4, -3,  1,  4, -3,  2,  0, -3, -3, -2, -2, -1,  1, -4,  3, -3, -1, -5], // L
+    [-1,  2,  0, -1, -4,  1,  1, -2, -1, -3, -3,  5, -2, -4, -1,  0, -1, -4, -2, -3, -1, -3,  1, -1, -5], // K
+    [-1, -2, -3, -4, -2,  0, -2, -3, -2,  1,  2, -2,  6,  0, -3, -2, -1, -2, -2,  1, -3,  2, -2, -1, -5], // M
+    [-3, -3, -4, -4, -2, -4, -4, -4, -2,  0,  0, -4,  0,  6, -4, -3, -2,  1,  3, -1, -4,  0, -4, -2, -5], // F
+    [-1, -2, -3, -2, -4, -2, -1, -3, -2, -3, -3, -1, -3, -4,  8, -1, -1, -5, -4, -3, -2, -3, -2, -2, -5], // P
+    [ 1, -1,  0, -1, -1,  0,  0, -1, -1, -3, -3,  0, -2, -3, -1,  5,  1, -3, -2, -2,  0, -3,  0, -1, -5], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -2, -1, -1, -2, -1,  1,  5, -3, -2,  0, -1, -2, -1, -1, -5], // T
+    [-3, -3, -4, -5, -3, -2, -4, -3, -2, -3, -2, -4, -2,  1, -5, -3, -3, 11,  2, -3, -5, -3, -3, -3, -5], // W
+    [-2, -2, -3, -4, -3, -2, -3, -4,  2, -2, -1, -2, -2,  3, -4, -2, -2,  2,  7, -2, -3, -2, -3, -2, -5], // Y
+    [ 0, -3, -3, -4, -1, -2, -3, -4, -4,  3,  1, -3,  1, -1, -3, -2,  0, -3, -2,  4, -4,  2, -3, -1, -5], // V
+    [-2, -1,  3,  4, -4,  0,  1, -1, -1, -4, -4, -1, -3, -4, -2,  0, -1, -5, -3, -4,  4, -4,  0, -2, -5], // B
+    [-2, -3, -4, -4, -2, -3, -4, -5, -4,  3,  3, -3,  2,  0, -3, -3, -2, -3, -2,  2, -4,  3, -4, -2, -5], // J
+    [-1,  0,  0,  1, -4,  3,  4, -2,  0, -4, -3,  1, -2, -4, -2,  0, -1, -3, -3, -3,  0, -4,  4, -1, -5], // Z
+    [-1, -1, -1, -2, -2, -1, -1, -2, -1, -2, -1, -1, -1, -2, -2, -1, -1, -3, -2, -1, -2, -2, -1, -1, -5], // X
+    [-5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5, -5,  1], // *
+];
+
+/// NCBI BLOSUM 80.
+#[rustfmt::skip]
+pub(super) const BLOSUM80: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -2, -2, -1, -1, -1,  0, -2, -2, -2, -1, -1, -3, -1,  1,  0, -3, -2,  0, -2, -2, -1, -1, -6], // A
+    [-2,  6, -1, -2, -4,  1, -1, -3,  0, -3, -3,  2, -2, -4, -2, -1, -1, -4, -3, -3, -1, -3,  0, -1, -6], // R
+    [-2, -1,  6,  1, -3,  0, -1, -1,  0, -4, -4,  0, -3, -4, -3,  0,  0, -4, -3, -4,  5, -4,  0, -1, -6], // N
+    [-2, -2,  1,  6, -4, -1,  1, -2, -2, -4, -5, -1, -4, -4, -2, -1, -1, -6, -4, -4,  5, -5,  1, -1, -6], // D
+    [-1, -4, -3, -4,  9, -4, -5, -4, -4, -2, -2, -4, -2, -3, -4, -2, -1, -3, -3, -1, -4, -2, -4, -1, -6], // C
+    [-1,  1,  0, -1, -4,  6,  2, -2,  1, -3, -3,  1,  0, -4, -2,  0, -1, -3, -2, -3,  0, -3,  3, -1, -6], // Q
+    [-1, -1, -1,  1, -5,  2,  6, -3,  0, -4, -4,  1, -2, -4, -2,  0, -1, -4, -3, -3,  1, -4,  4, -1, -6], // E
+    [ 0, -3, -1, -2, -4, -2, -3,  6, -3, -5, -4, -2, -4, -4, -3, -1, -2, -4, -4, -4, -1, -5, -3, -1, -6], // G
+    [-2,  0,  0, -2, -4,  1,  0, -3,  8, -4, -3, -1, -2, -2, -3, -1, -2, -3,  2, -4, -1, -4,  0, -1, -6], // H
+    [-2, -3, -4, -4, -2, -3, -4, -5, -4,  5,  1, -3,  1, -1, -4, -3, -1, -3, -2,  3, -4,  3, -4, -1, -6], // I
+    [-2, -3, -4, -5, -2, -3, -4, -4, -3,  1,  4, -3,  2,  0, -3, -3, -2, -2, -2,  1, -4,  3, -3, -1, -6], // L
+    [-1,  2,  0, -1, -4,  1,  1, -2, -1, -3, -3,  5, -2, -4, -1, -1, -1, -4, -3, -3, -1, -3,  1, -1, -6], // K
+    [-1, -2, -3, -4, -2,  0, -2, -4, -2,  1,  2, -2,  6,  0, -3, -2, -1, -2, -2,  1, -3,  2, -2, -1, -6], // M
+    [-3, -4, -4, -4, -3, -4, -4, -4, -2, -1,  0, -4,  0,  6, -4, -3, -2,  0,  3, -1, -4, -1, -4, -1, -6], // F
+    [-1, -2, -3, -2, -4, -2, -2, -3, -3, -4, -3, -1, -3, -4,  8, -1, -2, -5, -4, -3, -2, -4, -2, -1, -6], // P
+    [ 1, -1,  0, -1, -2,  0,  0, -1, -1, -3, -3, -1, -2, -3, -1,  5,  1, -4, -2, -2,  0, -3,  0, -1, -6], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -2, -1, -1, -2, -2,  1,  5, -4, -2,  0, -1, -2, -1, -1, -6], // T
+    [-3, -4, -4, -6, -3, -3, -4, -4, -3, -3, -2, -4, -2,  0, -5, -4, -4, 11,  2, -3, -5, -3, -4, -1, -6], // W
+    [-2, -3, -3, -4, -3, -2, -3, -4,  2, -2, -2, -3, -2,  3, -4, -2, -2,  2,  7, -2, -3, -2, -3, -1, -6], // Y
+    [ 0, -3, -4, -4, -1, -3, -3, -4, -4,  3,  1, -3,  1, -1, -3, -2,  0, -3, -2,  4, -4,  2, -3, -1, -6], // V
+    [-2, -1,  5,  5, -4,  0,  1, -1, -1, -4, -4, -1, -3, -4, -2,  0, -1, -5, -3, -4,  5, -4,  0, -1, -6], // B
+    [-2, -3, -4, -5, -2, -3, -4, -5, -4,  3,  3, -3,  2, -1, -4, -3, -2, -3, -2,  2, -4,  3, -4, -1, -6], // J
+    [-1,  0,  0,  1, -4,  3,  4, -3,  0, -4, -3,  1, -2, -4, -2,  0, -1, -4, -3, -3,  0, -4,  4, -1, -6], // Z
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -6], // X
+    [-6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6,  1], // *
+];
+
+/// NCBI BLOSUM 85.
+#[rustfmt::skip]
+pub(super) const BLOSUM85: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -2, -2, -1, -1, -1,  0, -2, -2, -2, -1, -2, -3, -1,  1,  0, -3, -3, -1, -2, -2, -1, -1, -6], // A
+    [-2,  6, -1, -2, -4,  1, -1, -3,  0, -4, -3,  2, -2, -4, -2, -1, -2, -4, -3, -3, -2, -4,  0, -2, -6], // R
+    [-2, -1,  7,  1, -4,  0, -1, -1,  0, -4, -4,  0, -3, -4, -3,  0,  0, -5, -3, -4,  4, -4, -1, -2, -6], // N
+    [-2, -2,  1,  7, -5, -1,  1, -2, -2, -5, -5, -1, -4, -4, -2, -1, -2, -6, -4, -4,  4, -5,  1, -2, -6], // D
+    [-1, -4, -4, -5,  9, -4, -5, -4, -5, -2, -2, -4, -2, -3, -4, -2, -2, -4, -3, -1, -4, -2, -5, -3, -6], // C
+    [-1,  1,  0, -1, -4,  6,  2, -3,  1, -4, -3,  1,  0, -4, -2, -1, -1, -3, -2, -3, -1, -4,  4, -1, -6], // Q
+    [-1, -1, -1,  1, -5,  2,  6, -3, -1, -4, -4,  0, -3, -4, -2, -1, -1, -4, -4, -3,  0, -4,  4, -1, -6], // E
+    [ 0, -3, -1, -2, -4, -3, -3,  6, -3, -5, -5, -2, -4, -4, -3, -1, -2, -4, -5, -4, -1, -5, -3, -2, -6], // G
+    [-2,  0,  0, -2, -5,  1, -1, -3,  8, -4, -3, -1, -3, -2, -3, -1, -2, -3,  2, -4, -1, -4,  0, -2, -6], // H
+    [-2, -4, -4, -5, -2, -4, -4, -5, -4,  5,  1, -3,  1, -1, -4, -3, -1, -3, -2,  3, -5,  3, -4, -2, -6], // I
+    [-2, -3, -4, -5, -2, -3, -4, -5, -3,  1,  4, -3,  2,  0, -4, -3, -2, -3, -2,  0, -5,  3, -4, -2, -6], // L
+    [-1,  2,  0, -1, -4,  1,  0, -2, -1, -3, -3,  6, -2, -4, -2, -1, -1, -5, -3, -3, -1, -3,  1, -1, -6], // K
+    [-2, -2, -3, -4, -2,  0, -3, -4, -3,  1,  2, -2,  7, -1, -3, -2, -1, -2, -2,  0, -4,  2, -2, -1, -6], // M
+    [-3, -4, -4, -4, -3, -4, -4, -4, -2, -1,  0, -4, -1,  7, -4, -3, -3,  0,  3, -1, -4, -1, -4, -2, -6], // F
+    [-1, -2, -3, -2, -4, -2, -2, -3, -3, -4, -4, -2, -3, -4,  8, -1, -2, -5, -4, -3, -3, -4, -2, -2, -6], // P
+    [ 1, -1,  0, -1, -2, -1, -1, -1, -1, -3, -3, -1, -2, -3, -1,  5,  1, -4, -2, -2,  0, -3, -1, -1, -6], // S
+    [ 0, -2,  0, -2, -2, -1, -1, -2, -2, -1, -2, -1, -1, -3, -2,  1,  5, -4, -2,  0, -1, -2, -1, -1, -6], // T
+    [-3, -4, -5, -6, -4, -3, -4, -4, -3, -3, -3, -5, -2,  0, -5, -4, -4, 11,  2, -3, -5, -3, -4, -3, -6], // W
+    [-3, -3, -3, -4, -3, -2, -4, -5,  2, -2, -2, -3, -2,  3, -4, -2, -2,  2,  7, -2, -4, -2, -3, -2, -6], // Y
+    [-1, -3, -4, -4, -1, -3, -3, -4, -4,  3,  0, -3,  0, -1, -3, -2,  0, -3, -2,  5, -4,  2, -3, -1, -6], // V
+    [-2, -2,  4,  4, -4, -1,  0, -1, -1, -5, -5, -1, -4, -4, -3,  0, -1, -5, -4, -4,  4, -5,  0, -2, -6], // B
+    [-2, -4, -4, -5, -2, -4, -4, -5, -4,  3,  3, -3,  2, -1, -4, -3, -2, -3, -2,  2, -5,  3, -4, -2, -6], // J
+    [-1,  0, -1,  1, -5,  4,  4, -3,  0, -4, -4,  1, -2, -4, -2, -1, -1, -4, -3, -3,  0, -4,  4, -1, -6], // Z
+    [-1, -2, -2, -2, -3, -1, -1, -2, -2, -2, -2, -1, -1, -2, -2, -1, -1, -3, -2, -1, -2, -2, -1, -2, -6], // X
+    [-6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6,  1], // *
+];
+
+/// NCBI BLOSUM 90.
+#[rustfmt::skip]
+pub(super) const BLOSUM90: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 5, -2, -2, -3, -1, -1, -1,  0, -2, -2, -2, -1, -2, -3, -1,  1,  0, -4, -3, -1, -2, -2, -1, -1, -6], // A
+    [-2,  6, -1, -3, -5,  1, -1, -3,  0, -4, -3,  2, -2, -4, -3, -1, -2, -4, -3, -3, -2, -4,  0, -2, -6], // R
+    [-2, -1,  7,  1, -4,  0, -1, -1,  0, -4, -4,  0, -3, -4, -3,  0,  0, -5, -3, -4,  4, -4, -1, -2, -6], // N
+    [-3, -3,  1,  7, -5, -1,  1, -2, -2, -5, -5, -1, -4, -5, -3, -1, -2, -6, -4, -5,  4, -5,  0, -2, -6], // D
+    [-1, -5, -4, -5,  9, -4, -6, -4, -5, -2, -2, -4, -2, -3, -4, -2, -2, -4, -4, -2, -4, -2, -5, -3, -6], // C
+    [-1,  1,  0, -1, -4,  7,  2, -3,  1, -4, -3,  1,  0, -4, -2, -1, -1, -3, -3, -3, -1, -4,  4, -1, -6], // Q
+    [-1, -1, -1,  1, -6,  2,  6, -3, -1, -4, -4,  0, -3, -5, -2, -1, -1, -5, -4, -3,  0, -4,  4, -2, -6], // E
+    [ 0, -3, -1, -2, -4, -3, -3,  6, -3, -5, -5, -2, -4, -5, -3, -1, -3, -4, -5, -5, -2, -5, -3, -2, -6], // G
+    [-2,  0,  0, -2, -5,  1, -1, -3,  8, -4, -4, -1, -3, -2, -3, -2, -2, -3,  1, -4, -1, -4,  0, -2, -6], // H
+    [-2, -4, -4, -5, -2, -4, -4, -5, -4,  5,  1, -4,  1, -1, -4, -3, -1, -4, -2,  3, -5,  3, -4, -2, -6], // I
+    [-2, -3, -4, -5, -2, -3, -4, -5, -4,  1,  5, -3,  2,  0, -4, -3, -2, -3, -2,  0, -5,  3, -4, -2, -6], // L
+    [-1,  2,  0, -1, -4,  1,  0, -2, -1, -4, -3,  6, -2, -4, -2, -1, -1, -5, -3, -3, -1, -4,  1, -1, -6], // K
+    [-2, -2, -3, -4, -2,  0, -3, -4, -3,  1,  2, -2,  7, -1, -3, -2, -1, -2, -2,  0, -4,  2, -2, -1, -6], // M
+    [-3, -4, -4, -5, -3, -4, -5, -5, -2, -1,  0, -4, -1,  7, -4, -3, -3,  0,  3, -2, -4, -1, -4, -2, -6], // F
+    [-1, -3, -3, -3, -4, -2, -2, -3, -3, -4, -4, -2, -3, -4,  8, -2, -2, -5, -4, -3, -3, -4, -2, -2, -6], // P
+    [ 1, -1,  0, -1, -2, -1, -1, -1, -2, -3, -3, -1, -2, -3, -2,  5,  1, -4, -3, -2,  0, -3, -1, -1, -6], // S
+    [ 0, -2,  0, -2, -2, -1, -1, -3, -2, -1, -2, -1, -1, -3, -2,  1,  6, -4, -2, -1, -1, -2, -1, -1, -6], // T
+    [-4, -4, -5, -6, -4, -3, -5, -4, -3, -4, -3, -5, -2,  0, -5, -4, -4, 11,  2, -3, -6, -4, -4, -3, -6], // W
+    [-3, -3, -3, -4, -4, -3, -4, -5,  1, -2, -2, -3, -2,  3, -4, -3, -2,  2,  8, -3, -4, -2, -3, -2, -6], // Y
+    [-1, -3, -4, -5, -2, -3, -3, -5, -4,  3,  0, -3,  0, -2, -3, -2, -1, -3, -3,  5, -4,  2, -3, -2, -6], // V
+    [-2, -2,  4,  4, -4, -1,  0, -2, -1, -5, -5, -1, -4, -4, -3,  0, -1, -6, -4, -4,  4, -5,  0, -2, -6], // B
+    [-2, -4, -4, -5, -2, -4, -4, -5, -4,  3,  3, -4,  2, -1, -4, -3, -2, -4, -2,  2, -5,  3, -4, -2, -6], // J
+    [-1,  0, -1,  0, -5,  4,  4, -3,  0, -4, -4,  1, -2, -4, -2, -1, -1, -4, -3, -3,  0, -4,  4, -1, -6], // Z
+    [-1, -2, -2, -2, -3, -1, -2, -2, -2, -2, -2, -1, -1, -2, -2, -1, -1, -3, -2, -2, -2, -2, -1, -2, -6], // X
+    [-6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6, -6,  1], // *
+];
+
+/// NCBI BLOSUM 100.
+#[rustfmt::skip]
+pub(super) const BLOSUM100: ScoreTable = [
+    //  A    R    N    D    C    Q    E    G    H    I    L    K    M    F    P    S    T    W    Y    V    B    J    Z    X    *
+    [  8,  -3,  -4,  -5,  -2,  -2,  -3,  -1,  -4,  -4,  -4,  -2,  -3,  -5,  -2,   1,  -1,  -6,  -5,  -2,  -4,  -4,  -2,  -2, -10], // A
+    [ -3,  10,  -2,  -5,  -8,   0,  -2,  -6,  -1,  -7,  -6,   3,  -4,  -6,  -5,  -3,  -3,  -7,  -5,  -6,  -4,  -7,  -1,  -3, -10], // R
+    [ -4,  -2,  11,   1,  -5,  -1,  -2,  -2,   0,  -7,  -7,  -1,  -5,  -7,  -5,   0,  -1,  -8,  -5,  -7,   5,  -7,  -2,  -3, -10], // N
+    [ -5,  -5,   1,  10,  -8,  -2,   2,  -4,  -3,  -8,  -8,  -3,  -8,  -8,  -5,  -2,  -4, -10,  -7,  -8,   6,  -8,   0,  -4, -10], // D
+    [ -2,  -8,  -5,  -8,  14,  -7,  -9,  -7,  -8,  -3,  -5,  -8,  -4,  -4,  -8,  -3,  -3,  -7,  -6,  -3,  -7,  -4,  -8,  -5, -10], // C
+    [ -2,   0,  -1,  -2,  -7,  11,   2,  -5,   1,  -6,  -5,   2,  -2,  -6,  -4,  -2,  -3,  -5,  -4,  -5,  -2,  -6,   5,  -2, -10], // Q
+    [ -3,  -2,  -2,   2,  -9,   2,  10,  -6,  -2,  -7,  -7,   0,  -5,  -8,  -4,  -2,  -3,  -8,  -7,  -5,   0,  -7,   7,  -3, -10], // E
+    [ -1,  -6,  -2,  -4,  -7,  -5,  -6,   9,  -6,  -9,  -8,  -5,  -7,  -8,  -6,  -2,  -5,  -7,  -8,  -8,  -3,  -9,  -5,  -4, -10], // G
+    [ -4,  -1,   0,  -3,  -8,   1,  -2,  -6,  13,  -7,  -6,  -3,  -5,  -4,  -5,  -3,  -4,  -5,   1,  -7,  -2,  -7,  -1,  -4, -10], // H
+    [ -4,  -7,  -7,  -8,  -3,  -6,  -7,  -9,  -7,   8,   2,  -6,   1,  -2,  -7,  -5,  -3,  -6,  -4,   4,  -8,   5,  -7,  -3, -10], // I
+    [ -4,  -6,  -7,  -8,  -5,  -5,  -7,  -8,  -6,   2,   8,  -6,   3,   0,  -7,  -6,  -4,  -5,  -4,   0,  -8,   5,  -6,  -3, -10], // L
+    [ -2,   3,  -1,  -3,  -8,   2,   0,  -5,  -3,  -6,  -6,  10,  -4,  -6,  -3,  -2,  -3,  -8,  -5,  -5,  -2,  -6,   0,  -2, -10], // K
+    [ -3,  -4,  -5,  -8,  -4,  -2,  -5,  -7,  -5,   1,   3,  -4,  12,  -1,  -5,  -4,  -2,  -4,  -5,   0,  -7,   2,  -4,  -3, -10], // M
+    [ -5,  -6,  -7,  -8,  -4,  -6,  -8,  -8,  -4,  -2,   0,  -6,  -1,  11,  -7,  -5,  -5,   0,   4,  -3,  -7,  -1,  -7,  -4, -10], // F
+    [ -2,  -5,  -5,  -5,  -8,  -4,  -4,  -6,  -5,  -7,  -7,  -3,  -5,  -7,  12,  -3,  -4,  -8,  -7,  -6,  -5,  -7,  -4,  -4, -10], // P
+    [  1,  -3,   0,  -2,  -3,  -2,  -2,  -2,  -3,  -5,  -6,  -2,  -4,  -5,  -3,   9,   2,  -7,  -5,  -4,  -1,  -6,  -2,  -2, -10], // S
+    [ -1,  -3,  -1,  -4,  -3,  -3,  -3,  -5,  -4,  -3,  -4,  -3,  -2,  -5,  -4,   2,   9,  -7,  -5,  -1,  -2,  -4,  -3,  -2, -10], // T
+    [ -6,  -7,  -8, -10,  -7,  -5,  -8,  -7,  -5,  -6,  -5,  -8,  -4,   0,  -8,  -7,  -7,  17,   2,  -5,  -9,  -6,  -7,  -6, -10], // W
+    [ -5,  -5,  -5,  -7,  -6,  -4,  -7,  -8,   1,  -4,  -4,  -5,  -5,   4,  -7,  -5,  -5,   2,  12,  -5,  -6,  -4,  -6,  -4, -10], // Y
+    [ -2,  -6,  -7,  -8,  -3,  -5,  -5,  -8,  -7,   4,   0,  -5,   0,  -3,  -6,  -4,  -1,  -5,  -5,   8,  -7,   2,  -5,  -3, -10], // V
+    [ -4,  -4,   5,   6,  -7,  -2,   0,  -3,  -2,  -8,  -8,  -2,  -7,  -7,  -5,  -1,  -2,  -9,  -6,  -7,   6,  -8,   0,  -4, -10], // B
+    [ -4,  -7,  -7,  -8,  -4,  -6,  -7,  -9,  -7,   5,   5,  -6,   2,  -1,  -7,  -6,  -4,  -6,  -4,   2,  -8,   5,  -7,  -3, -10], // J
+    [ -2,  -1,  -2,   0,  -8,   5,   7,  -5,  -1,  -7,  -6,   0,  -4,  -7,  -4,  -2,  -3,  -7,  -6,  -5,   0,  -7,   6,  -2, -10], // Z
+    [ -2,  -3,  -3,  -4,  -5,  -2,  -3,  -4,  -4,  -3,  -3,  -2,  -3,  -4,  -4,  -2,  -2,  -6,  -4,  -3,  -4,  -3,  -2,  -3, -10], // X
+    [-10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10, -10,   1], // *
+];
+
+/// NCBI PAM 30.
+#[rustfmt::skip]
+pub(super) const PAM30: ScoreTable = [
+    //  A    R    N    D    C    Q    E    G    H    I    L    K    M    F    P    S    T    W    Y    V    B    J    Z    X    *
+    [  6,  -7,  -4,  -3,  -6,  -4,  -2,  -2,  -7,  -5,  -6,  -7,  -5,  -8,  -2,   0,  -1, -13,  -8,  -2,  -3,  -6,  -3,  -3, -17], // A
+    [ -7,   8,  -6, -10,  -8,  -2,  -9,  -9,  -2,  -5,  -8,   0,  -4,  -9,  -4,  -3,  -6,  -2, -10,  -8,  -7,  -7,  -4,  -6, -17], // R
+    [ -4,  -6,   8,   2, -11,  -3,  -2,  -3,   0,  -5,  -7,  -1,  -9,  -9,  -6,   0,  -2,  -8,  -4,  -8,   6,  -6,  -3,  -3, -17], // N
+    [ -3, -10,   2,   8, -14,  -2,   2,  -3,  -4,  -7, -12,  -4, -11, -15,  -8,  -4,  -5, -15, -11,  -8,   6, -10,   1,  -5, -17], // D
+    [ -6,  -8, -11, -14,  10, -14, -14,  -9,  -7,  -6, -15, -14, -13, -13,  -8,  -3,  -8, -15,  -4,  -6, -12, -11, -14,  -9, -17], // C
+    [ -4,  -2,  -3,  -2, -14,   8,   1,  -7,   1,  -8,  -5,  -3,  -4, -13,  -3,  -5,  -5, -13, -12,  -7,  -3,  -7,   6,  -5, -17], // Q
+    [ -2,  -9,  -2,   2, -14,   1,   8,  -4,  -5,  -5,  -9,  -4,  -7, -14,  -5,  -4,  -6, -17,  -8,  -6,   1,  -7,   6,  -5, -17], // E
+    [ -2,  -9,  -3,  -3,  -9,  -7,  -4,   6,  -9, -11, -10,  -7,  -8,  -9,  -6,  -2,  -6, -15, -14,  -5,  -3, -11,  -5,  -5, -17], // G
+    [ -7,  -2,   0,  -4,  -7,   1,  -5,  -9,   9,  -9,  -6,  -6, -10,  -6,  -4,  -6,  -7,  -7,  -3,  -6,  -1,  -8,  -1,  -5, -17], // H
+    [ -5,  -5,  -5,  -7,  -6,  -8,  -5, -11,  -9,   8,  -1,  -6,  -1,  -2,  -8,  -7,  -2, -14,  -6,   2,  -6,   4,  -6,  -5, -17], // I
+    [ -6,  -8,  -7, -12, -15,  -5,  -9, -10,  -6,  -1,   7,  -8,   1,  -3,  -7,  -8,  -7,  -6,  -7,  -2,  -9,   3,  -7,  -6, -17], // L
+    [ -7,   0,  -1,  -4, -14,  -3,  -4,  -7,  -6,  -6,  -8,   7,  -2, -14,  -6,  -4,  -3, -12,  -9,  -9,  -2,  -7,  -4,  -5, -17], // K
+    [ -5,  -4,  -9, -11, -13,  -4,  -7,  -8, -10,  -1,   1,  -2,  11,  -4,  -8,  -5,  -4, -13, -11,  -1, -10,   0,  -5,  -5, -17], // M
+    [ -8,  -9,  -9, -15, -13, -13, -14,  -9,  -6,  -2,  -3, -14,  -4,   9, -10,  -6,  -9,  -4,   2,  -8, -10,  -3, -13,  -8, -17], // F
+    [ -2,  -4,  -6,  -8,  -8,  -3,  -5,  -6,  -4,  -8,  -7,  -6,  -8, -10,   8,  -2,  -4, -14, -13,  -6,  -7,  -8,  -4,  -5, -17], // P
+    [  0,  -3,   0,  -4,  -3,  -5,  -4,  -2,  -6,  -7,  -8,  -4,  -5,  -6,  -2,   6,   0,  -5,  -7,  -6,  -1,  -8,  -5,  -3, -17], // S
+    [ -1,  -6,  -2,  -5,  -8,  -5,  -6,  -6,  -7,  -2,  -7,  -3,  -4,  -9,  -4,   0,   7, -13,  -6,  -3,  -3,  -5,  -6,  -4, -17], // T
+    [-13,  -2,  -8, -15, -15, -13, -17, -15,  -7, -14,  -6, -12, -13,  -4, -14,  -5, -13,  13,  -5, -15, -10, -10, -14, -11, -17], // W
+    [ -8, -10,  -4, -11,  -4, -12,  -8, -14,  -3,  -6,  -7,  -9, -11,   2, -13,  -7,  -6,  -5,  10,  -7,  -6,  -7,  -9,  -7, -17], // Y
+    [ -2,  -8,  -8,  -8,  -6,  -7,  -6,  -5,  -6,   2,  -2,  -9,  -1,  -8,  -6,  -6,  -3, -15,  -7,   7,  -8,   0,  -6,  -5, -17], // V
+    [ -3,  -7,   6,   6, -12,  -3,   1,  -3,  -1,  -6,  -9,  -2, -10, -10,  -7,  -1,  -3, -10,  -6,  -8,   6,  -8,   0,  -5, -17], // B
+    [ -6,  -7,  -6, -10, -11,  -7,  -7, -11,  -8,   4,   3,  -7,   0,  -3,  -8,  -8,  -5, -10,  -7,   0,  -8,   3,  -7,  -6, -17], // J
+    [ -3,  -4,  -3,   1, -14,   6,   6,  -5,  -1,  -6,  -7,  -4,  -5, -13,  -4,  -5,  -6, -14,  -9,  -6,   0,  -7,   6,  -5, -17], // Z
+    [ -3,  -6,  -3,  -5,  -9,  -5,  -5,  -5,  -5,  -5,  -6,  -5,  -5,  -8,  -5,  -3,  -4, -11,  -7,  -5,  -5,  -6,  -5,  -5, -17], // X
+    [-17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17, -17,   1], // *
+];
+
+/// NCBI PAM 70.
+#[rustfmt::skip]
+pub(super) const PAM70: ScoreTable = [
+    //  A    R    N    D    C    Q    E    G    H    I    L    K    M    F    P    S    T    W    Y    V    B    J    Z    X    *
+    [  5,  -4,  -2,  -1,  -4,  -2,  -1,   0,  -4,  -2,  -4,  -4,  -3,  -6,   0,   1,   1,  -9,  -5,  -1,  -1,  -3,  -1,  -2, -11], // A
+    [ -4,   8,  -3,  -6,  -5,   0,  -5,  -6,   0,  -3,  -6,   2,  -2,  -7,  -2,  -1,  -4,   0,  -7,  -5,  -4,  -5,  -2,  -3, -11], // R
+    [ -2,  -3,   6,   3,  -7,  -1,   0,  -1,   1,  -3,  -5,   0,  -5,  -6,  -3,   1,   0,  -6,  -3,  -5,   5,  -4,  -1,  -2, -11], // N
+    [ -1,  -6,   3,   6,  -9,   0,   3,  -1,  -1,  -5,  -8,  -2,  -7, -10,  -4,  -1,  -2, -10,  -7,  -5,   5,  -7,   2,  -3, -11], // D
+    [ -4,  -5,  -7,  -9,   9,  -9,  -9,  -6,  -5,  -4, -10,  -9,  -9,  -8,  -5,  -1,  -5, -11,  -2,  -4,  -8,  -7,  -9,  -6, -11], // C
+    [ -2,   0,  -1,   0,  -9,   7,   2,  -4,   2,  -5,  -3,  -1,  -2,  -9,  -1,  -3,  -3,  -8,  -8,  -4,  -1,  -4,   5,  -2, -11], // Q
+    [ -1,  -5,   0,   3,  -9,   2,   6,  -2,  -2,  -4,  -6,  -2,  -4,  -9,  -3,  -2,  -3, -11,  -6,  -4,   2,  -5,   5,  -3, -11], // E
+    [  0,  -6,  -1,  -1,  -6,  -4,  -2,   6,  -6,  -6,  -7,  -5,  -6,  -7,  -3,   0,  -3, -10,  -9,  -3,  -1,  -7,  -3,  -3, -11], // G
+    [ -4,   0,   1,  -1,  -5,   2,  -2,  -6,   8,  -6,  -4,  -3,  -6,  -4,  -2,  -3,  -4,  -5,  -1,  -4,   0,  -5,   1,  -3, -11], // H
+    [ -2,  -3,  -3,  -5,  -4,  -5,  -4,  -6,  -6,   7,   1,  -4,   1,   0,  -5,  -4,  -1,  -9,  -4,   3,  -4,   4,  -4,  -3, -11], // I
+    [ -4,  -6,  -5,  -8, -10,  -3,  -6,  -7,  -4,   1,   6,  -5,   2,  -1,  -5,  -6,  -4,  -4,  -4,   0,  -6,   4,  -4,  -4, -11], // L
+    [ -4,   2,   0,  -2,  -9,  -1,  -2,  -5,  -3,  -4,  -5,   6,   0,  -9,  -4,  -2,  -1,  -7,  -7,  -6,  -1,  -5,  -2,  -3, -11], // K
+    [ -3,  -2,  -5,  -7,  -9,  -2,  -4,  -6,  -6,   1,   2,   0,  10,  -2,  -5,  -3,  -2,  -8,  -7,   0,  -6,   2,  -3,  -3, -11], // M
+    [ -6,  -7,  -6, -10,  -8,  -9,  -9,  -7,  -4,   0,  -1,  -9,  -2,   8,  -7,  -4,  -6,  -2,   4,  -5,  -7,  -1,  -9,  -5, -11], // F
+    [  0,  -2,  -3,  -4,  -5,  -1,  -3,  -3,  -2,  -5,  -5,  -4,  -5,  -7,   7,   0,  -2,  -9,  -9,  -3,  -4,  -5,  -2,  -3, -11], // P
+    [  1,  -1,   1,  -1,  -1,  -3,  -2,   0,  -3,  -4,  -6,  -2,  -3,  -4,   0,   5,   2,  -3,  -5,  -3,   0,  -5,  -2,  -1, -11], // S
+    [  1,  -4,   0,  -2,  -5,  -3,  -3,  -3,  -4,  -1,  -4,  -1,  -2,  -6,  -2,   2,   6,  -8,  -4,  -1,  -1,  -3,  -3,  -2, -11], // T
+    [ -9,   0,  -6, -10, -11,  -8, -11, -10,  -5,  -9,  -4,  -7,  -8,  -2,  -9,  -3,  -8,  13,  -3, -10,  -7,  -7, -10,  -7, -11], // W
+    [ -5,  -7,  -3,  -7,  -2,  -8,  -6,  -9,  -1,  -4,  -4,  -7,  -7,   4,  -9,  -5,  -4,  -3,   9,  -5,  -4,  -4,  -7,  -5, -11], // Y
+    [ -1,  -5,  -5,  -5,  -4,  -4,  -4,  -3,  -4,   3,   0,  -6,   0,  -5,  -3,  -3,  -1, -10,  -5,   6,  -5,   2,  -4,  -2, -11], // V
+    [ -1,  -4,   5,   5,  -8,  -1,   2,  -1,   0,  -4,  -6,  -1,  -6,  -7,  -4,   0,  -1,  -7,  -4,  -5,   5,  -5,   1,  -2, -11], // B
+    [ -3,  -5,  -4,  -7,  -7,  -4,  -5,  -7,  -5,   4,   4,  -5,   2,  -1,  -5,  -5,  -3,  -7,  -4,   2,  -5,   4,  -4,  -4, -11], // J
+    [ -1,  -2,  -1,   2,  -9,   5,   5,  -3,   1,  -4,  -4,  -2,  -3,  -9,  -2,  -2,  -3, -10,  -7,  -4,   1,  -4,   5,  -3, -11], // Z
+    [ -2,  -3,  -2,  -3,  -6,  -2,  -3,  -3,  -3,  -3,  -4,  -3,  -3,  -5,  -3,  -1,  -2,  -7,  -5,  -2,  -2,  -4,  -3,  -3, -11], // X
+    [-11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11, -11,   1], // *
+];
+
+/// NCBI PAM 120.
+#[rustfmt::skip]
+pub(super) const PAM120: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 3, -3, -1,  0, -3, -1,  0,  1, -3, -1, -3, -2, -2, -4,  1,  1,  1, -7, -4,  0,  0, -2, -1, -1, -8], // A
+    [-3,  6, -1, -3, -4,  1, -3, -4,  1, -2, -4,  2, -1, -5, -1, -1, -2,  1, -5, -3, -2, -3, -1, -2, -8], // R
+    [-1, -1,  4,  2, -5,  0,  1,  0,  2, -2, -4,  1, -3, -4, -2,  1,  0, -4, -2, -3,  3, -3,  0, -1, -8], // N
+    [ 0, -3,  2,  5, -7,  1,  3,  0,  0, -3, -5, -1, -4, -7, -3,  0, -1, -8, -5, -3,  4, -4,  3, -2, -8], // D
+    [-3, -4, -5, -7,  9, -7, -7, -4, -4, -3, -7, -7, -6, -6, -4,  0, -3, -8, -1, -3, -6, -5, -7, -4, -8], // C
+    [-1,  1,  0,  1, -7,  6,  2, -3,  3, -3, -2,  0, -1, -6,  0, -2, -2, -6, -5, -3,  0, -3,  4, -1, -8], // Q
+    [ 0, -3,  1,  3, -7,  2,  5, -1, -1, -3, -4, -1, -3, -7, -2, -1, -2, -8, -5, -3,  3, -4,  4, -1, -8], // E
+    [ 1, -4,  0,  0, -4, -3, -1,  5, -4, -4, -5, -3, -4, -5, -2,  1, -1, -8, -6, -2,  0, -5, -2, -2, -8], // G
+    [-3,  1,  2,  0, -4,  3, -1, -4,  7, -4, -3, -2, -4, -3, -1, -2, -3, -3, -1, -3,  1, -4,  1, -2, -8], // H
+    [-1, -2, -2, -3, -3, -3, -3, -4, -4,  6,  1, -3,  1,  0, -3, -2,  0, -6, -2,  3, -3,  4, -3, -1, -8], // I
+    [-3, -4, -4, -5, -7, -2, -4, -5, -3,  1,  5, -4,  3,  0, -3, -4, -3, -3, -2,  1, -4,  3, -3, -2, -8], // L
+    [-2,  2,  1, -1, -7,  0, -1, -3, -2, -3, -4,  5,  0, -7, -2, -1, -1, -5, -5, -4,  0, -4, -1, -2, -8], // K
+    [-2, -1, -3, -4, -6, -1, -3, -4, -4,  1,  3,  0,  8, -1, -3, -2, -1, -6, -4,  1, -4,  2, -2, -2, -8], // M
+    [-4, -5, -4, -7, -6, -6, -7, -5, -3,  0,  0, -7, -1,  8, -5, -3, -4, -1,  4, -3, -5,  0, -6, -3, -8], // F
+    [ 1, -1, -2, -3, -4,  0, -2, -2, -1, -3, -3, -2, -3, -5,  6,  1, -1, -7, -6, -2, -2, -3, -1, -2, -8], // P
+    [ 1, -1,  1,  0,  0, -2, -1,  1, -2, -2, -4, -1, -2, -3,  1,  3,  2, -2, -3, -2,  0, -3, -1, -1, -8], // S
+    [ 1, -2,  0, -1, -3, -2, -2, -1, -3,  0, -3, -1, -1, -4, -1,  2,  4, -6, -3,  0,  0, -2, -2, -1, -8], // T
+    [-7,  1, -4, -8, -8, -6, -8, -8, -3, -6, -3, -5, -6, -1, -7, -2, -6, 12, -2, -8, -6, -5, -7, -5, -8], // W
+    [-4, -5, -2, -5, -1, -5, -5, -6, -1, -2, -2, -5, -4,  4, -6, -3, -3, -2,  8, -3, -3, -2, -5, -3, -8], // Y
+    [ 0, -3, -3, -3, -3, -3, -3, -2, -3,  3,  1, -4,  1, -3, -2, -2,  0, -8, -3,  5, -3,  2, -3, -1, -8], // V
+    [ 0, -2,  3,  4, -6,  0,  3,  0,  1, -3, -4,  0, -4, -5, -2,  0,  0, -6, -3, -3,  4, -4,  2, -1, -8], // B
+    [-2, -3, -3, -4, -5, -3, -4, -5, -4,  4,  3, -4,  2,  0, -3, -3, -2, -5, -2,  2, -4,  3, -3, -2, -8], // J
+    [-1, -1,  0,  3, -7,  4,  4, -2,  1, -3, -3, -1, -2, -6, -1, -1, -2, -7, -5, -3,  2, -3,  4, -1, -8], // Z
+    [-1, -2, -1, -2, -4, -1, -1, -2, -2, -1, -2, -2, -2, -3, -2, -1, -1, -5, -3, -1, -1, -2, -1, -2, -8], // X
+    [-8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8,  1], // *
+];
+
+/// NCBI PAM 250.
+#[rustfmt::skip]
+pub(super) const PAM250: ScoreTable = [
+    // A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   J   Z   X   *
+    [ 2, -2,  0,  0, -2,  0,  0,  1, -1, -1, -2, -1, -1, -3,  1,  1,  1, -6, -3,  0,  0, -2,  0,  0, -8], // A
+    [-2,  6,  0, -1, -4,  1, -1, -3,  2, -2, -3,  3,  0, -4,  0,  0, -1,  2, -4, -2, -1, -3,  0, -1, -8], // R
+    [ 0,  0,  2,  2, -4,  1,  1,  0,  2, -2, -3,  1, -2, -3,  0,  1,  0, -4, -2, -2,  2, -3,  1,  0, -8], // N
+    [ 0, -1,  2,  4, -5,  2,  3,  1,  1, -2, -4,  0, -3, -6, -1,  0,  0, -7, -4, -2,  3, -3,  3, -1, -8], // D
+    [-2, -4, -4, -5, 12, -5, -5, -3, -3, -2, -6, -5, -5, -4, -3,  0, -2, -8,  0, -2, -4, -4, -5, -3, -8], // C
+    [ 0,  1,  1,  2, -5,  4,  2, -1,  3, -2, -2,  1, -1, -5,  0, -1, -1, -5, -4, -2,  1, -2,  3, -1, -8], // Q
+    [ 0, -1,  1,  3, -5,  2,  4,  0,  1, -2, -3,  0, -2, -5, -1,  0,  0, -7, -4, -2,  3, -3,  3, -1, -8], // E
+    [ 1, -3,  0,  1, -3, -1,  0,  5, -2, -3, -4, -2, -3, -5,  0,  1,  0, -7, -5, -1,  0, -4,  0, -1, -8], // G
+    [-1,  2,  2,  1, -3,  3,  1, -2,  6, -2, -2,  0, -2, -2,  0, -1, -1, -3,  0, -2,  1, -2,  2, -1, -8], // H
+    [-1, -2, -2, -2, -2, -2, -2, -3, -2,  5,  2, -2,  2,  1, -2, -1,  0, -5, -1,  4, -2,  4, -2, -1, -8], // I
+    [-2, -3, -3, -4, -6, -2, -3, -4, -2,  2,  6, -3,  4,  2, -3, -3, -2, -2, -1,  2, -3,  4, -3, -1, -8], // L
+    [-1,  3,  1,  0, -5,  1,  0, -2,  0, -2, -3,  5,  0, -5, -1,  0,  0, -3, -4, -2,  1, -3,  0, -1, -8], // K
+    [-1,  0, -2, -3, -5, -1, -2, -3, -2,  2,  4,  0,  6,  0, -2, -2, -1, -4, -2,  2, -2,  3, -2, -1, -8], // M
+    [-3, -4, -3, -6, -4, -5, -5, -5, -2,  1,  2, -5,  0,  9, -5, -3, -3,  0,  7, -1, -4,  2, -5, -2, -8], // F
+    [ 1,  0,  0, -1, -3,  0, -1,  0,  0, -2, -3, -1, -2, -5,  6,  1,  0, -6, -5, -1, -1, -3,  0, -1, -8], // P
+    [ 1,  0,  1,  0,  0, -1,  0,  1, -1, -1, -3,  0, -2, -3,  1,  2,  1, -2, -3, -1,  0, -2,  0,  0, -8], // S
+    [ 1, -1,  0,  0, -2, -1,  0,  0, -1,  0, -2,  0, -1, -3,  0,  1,  3, -5, -3,  0,  0, -1, -1,  0, -8], // T
+    [-6,  2, -4, -7, -8, -5, -7, -7, -3, -5, -2, -3, -4,  0, -6, -2, -5, 17,  0, -6, -5, -4, -6, -4, -8], // W
+    [-3, -4, -2, -4,  0, -4, -4, -5,  0, -1, -1, -4, -2,  7, -5, -3, -3,  0, 10, -2, -3, -1, -4, -2, -8], // Y
+    [ 0, -2, -2, -2, -2, -2, -2, -1, -2,  4,  2, -2,  2, -1, -1, -1,  0, -6, -2,  4, -2,  3, -2, -1, -8], // V
+    [ 0, -1,  2,  3, -4,  1,  3,  0,  1, -2, -3,  1, -2, -4, -1,  0,  0, -5, -3, -2,  3, -3,  2, -1, -8], // B
+    [-2, -3, -3, -3, -4, -2, -3, -4, -2,  4,  4, -3,  3,  2, -3, -2, -1, -4, -1,  3, -3,  4, -3, -1, -8], // J
+    [ 0,  0,  1,  3, -5,  3,  3,  0,  2, -2, -3,  0, -2, -5,  0,  0, -1, -6, -4, -2,  2, -3,  3, -1, -8], // Z
+    [ 0, -1,  0, -1, -3, -1, -1, -1, -1, -1, -1, -1, -1, -2, -1,  0,  0, -4, -2, -1, -1, -1, -1, -1, -8], // X
+    [-8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8, -8,  1], // *
+];
+
+/// Get the score of two amino acids in a table
+pub(super) const fn read_table(table: &ScoreTable, code_1: Aac, code_2: Aac) -> i8 {
+    table[map_aminoacid_to_index(code_1)][map_aminoacid_to_index(code_2)]
 }
@@ -1,55 +1,85 @@
 //! Amino acid scoring schemas
 
-use super::aminoacid_data;
+use super::aminoacid_data::{self, ScoreTable};
+use super::substitution_matrix::SubstitutionMatrix;
 use super::Similarity;
 use super::SimilarityType;
 use crate::bioseq::Aac;
 
-/// Represents available Amino acid scoring matrices
+/// Represents available Amino acid scoring matrices. The built-in ones are the NCBI
+/// BLOSUM and PAM matrices; lower BLOSUM and higher PAM numbers suit more distant homologs.
 pub enum AaScoringKind {
+    Blosum30,
+    Blosum35,
+    Blosum40,
     Blosum45,
+    Blosum50,
+    Blosum55,
+    Blosum60,
     Blosum62,
+    Blosum65,
+    Blosum70,
+    Blosum75,
+    Blosum80,
+    Blosum85,
+    Blosum90,
+    Blosum100,
+    Pam30,
+    Pam70,
+    Pam120,
     Pam160,
+    Pam250,
+    /// Matrix read at runtime, see SubstitutionMatrix::from_ncbi_text.
+    Custom(SubstitutionMatrix),
 }
 
 /// Similarity schema constructor
 pub fn similarity_builder(kind: AaScoringKind) -> Box<dyn Similarity<Aac>> {
-    match kind {
-        AaScoringKind::Blosum45 => Box::new(Blosum45 {}),
-        AaScoringKind::Blosum62 => Box::new(Blosum62 {}),
-        AaScoringKind::Pam160 => Box::new(Pam160 {}),
-    }
+    let table: &'static ScoreTable = match kind {
+        AaScoringKind::Custom(matrix) => return Box::new(matrix),
+        AaScoringKind::Blosum30 => &aminoacid_data::BLOSUM30,
+        AaScoringKind::Blosum35 => &aminoacid_data::BLOSUM35,
+        AaScoringKind::Blosum40 => &aminoacid_data::BLOSUM40,
+        AaScoringKind::Blosum45 => &aminoacid_data::BLOSUM45,
+        AaScoringKind::Blosum50 => &aminoacid_data::BLOSUM50,
+        AaScoringKind::Blosum55 => &aminoacid_data::BLOSUM55,
+        AaScoringKind::Blosum60 => &aminoacid_data::BLOSUM60,
+        AaScoringKind::Blosum62 => &aminoacid_data::BLOSUM62,
+        AaScoringKind::Blosum65 => &aminoacid_data::BLOSUM65,
+        AaScoringKind::Blosum70 => &aminoacid_data::BLOSUM70,
+        AaScoringKind::Blosum75 => &aminoacid_data::BLOSUM75,
+        AaScoringKind::Blosum80 => &aminoacid_data::BLOSUM80,
+        AaScoringKind::Blosum85 => &aminoacid_data::BLOSUM85,
+        AaScoringKind::Blosum90 => &aminoacid_data::BLOSUM90,
+        AaScoringKind::Blosum100 => &aminoacid_data::BLOSUM100,
+        AaScoringKind::Pam30 => &aminoacid_data::PAM30,
+        AaScoringKind::Pam70 => &aminoacid_data::PAM70,
+        AaScoringKind::Pam120 => &aminoacid_data::PAM120,
+        AaScoringKind::Pam160 => &aminoacid_data::PAM160,
+        AaScoringKind::Pam250 => &aminoacid_data::PAM250,
+    };
+    Box::new(NcbiMatrix { table })
 }
 
-pub struct Blosum45 {}
-
-impl Similarity<Aac> for Blosum45 {
-    fn read_score(&self, code_1: Aac, code_2: Aac) -> SimilarityType {
-        aminoacid_data::read_blosum45(code_1, code_2)
-    }
+/// Built-in substitution matrix, see AaScoringKind.
+pub struct NcbiMatrix {
+    table: &'static ScoreTable,
 }
 
-pub struct Blosum62 {}
-
-impl Similarity<Aac> for Blosum62 {
+impl Similarity<Aac> for NcbiMatrix {
     fn read_score(&self, code_1: Aac, code_2: Aac) -> SimilarityType {
-        aminoacid_data::read_blosum62(code_1, code_2)
-    }
-}
-
-pub struct Pam160 {}
-
-impl Similarity<Aac> for Pam160 {
-    fn read_score(&self, code_1: Aac, code_2: Aac) -> SimilarityType {
-        aminoacid_data::read_pam160(code_1, code_2)
+        aminoacid_data::read_table(self.table, code_1, code_2)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::aminoacid_data::{
+        map_aminoacid_to_index, standard_index, STANDARD_CODES, TABLE_CODES,
+    };
     use super::*;
 
-    const ALL_AAC: [Aac; 20] = [
+    const ALL_AAC: [Aac; 27] = [
         Aac::A,
         Aac::C,
         Aac::D,
@@ -70,6 +100,13 @@ mod test {
         Aac::V,
         Aac::W,
         Aac::Y,
+        Aac::B,
+        Aac::Z,
+        Aac::J,
+        Aac::X,
+        Aac::U,
+        Aac::O,
+        Aac::Stop,
     ];
 
     #[test]
@@ -152,6 +189,180 @@ fn check_some_pam160() {
         }
     }
 
+    #[test]
+    fn check_ncbi_extra_codes() {
+        let score_cases = [
+            (AaScoringKind::Blosum62, 4, Aac::B, Aac::D),
+            (AaScoringKind::Blosum62, 4, Aac::Z, Aac::E),
+            (AaScoringKind::Blosum62, -1, Aac::X, Aac::X),
+            (AaScoringKind::Blosum62, -4, Aac::Stop, Aac::W),
+            (AaScoringKind::Blosum62, 1, Aac::Stop, Aac::Stop),
+            (AaScoringKind::Blosum45, 5, Aac::B, Aac::D),
+            (AaScoringKind::Blosum45, -5, Aac::A, Aac::Stop),
+            (AaScoringKind::Pam160, 3, Aac::Z, Aac::Z),
+            (AaScoringKind::Pam160, -7, Aac::Stop, Aac::C),
+        ];
+        for (kind, expected, code_1, code_2) in score_cases {
+            let matrix = similarity_builder(kind);
+            assert_eq!(expected, matrix.read_score(code_1, code_2));
+            assert_eq!(expected, matrix.read_score(code_2, code_1))
+        }
+    }
+
+    fn all_builtin() -> [AaScoringKind; 20] {
+        [
+            AaScoringKind::Blosum30,
+            AaScoringKind::Blosum35,
+            AaScoringKind::Blosum40,
+            AaScoringKind::Blosum45,
+            AaScoringKind::Blosum50,
+            AaScoringKind::Blosum55,
+            AaScoringKind::Blosum60,
+            AaScoringKind::Blosum62,
+            AaScoringKind::Blosum65,
+            AaScoringKind::Blosum70,
+            AaScoringKind::Blosum75,
+            AaScoringKind::Blosum80,
+            AaScoringKind::Blosum85,
+            AaScoringKind::Blosum90,
+            AaScoringKind::Blosum100,
+            AaScoringKind::Pam30,
+            AaScoringKind::Pam70,
+            AaScoringKind::Pam120,
+            AaScoringKind::Pam160,
+            AaScoringKind::Pam250,
+        ]
+    }
+
+    #[test]
+    fn check_some_blosum_and_pam_families() {
+        let score_cases = [
+            (AaScoringKind::Blosum30, 20, Aac::W, Aac::W),
+            (AaScoringKind::Blosum30, 3, Aac::L, Aac::Y),
+            (AaScoringKind::Blosum50, 13, Aac::C, Aac::C),
+            (AaScoringKind::Blosum50, -4, Aac::I, Aac::R),
+            (AaScoringKind::Blosum80, 11, Aac::W, Aac::W),
+            (AaScoringKind::Blosum80, -6, Aac::Stop, Aac::A),
+            (AaScoringKind::Blosum90, 1, Aac::H, Aac::Y),
+            (AaScoringKind::Blosum100, -10, Aac::W, Aac::D),
+            (AaScoringKind::Pam30, -17, Aac::W, Aac::E),
+            (AaScoringKind::Pam30, -17, Aac::Stop, Aac::A),
+            (AaScoringKind::Pam70, 10, Aac::M, Aac::M),
+            (AaScoringKind::Pam120, 4, Aac::Y, Aac::F),
+            (AaScoringKind::Pam250, 17, Aac::W, Aac::W),
+            (AaScoringKind::Pam250, 2, Aac::R, Aac::W),
+        ];
+        for (kind, expected, code_1, code_2) in score_cases {
+            let matrix = similarity_builder(kind);
+            assert_eq!(expected, matrix.read_score(code_1, code_2));
+            assert_eq!(expected, matrix.read_score(code_2, code_1))
+        }
+    }
+
+    #[test]
+    fn builtin_matrices_are_symmetric() {
+        for kind in all_builtin() {
+            let matrix = similarity_builder(kind);
+            for code_1 in ALL_AAC {
+                for code_2 in ALL_AAC {
+                    assert_eq!(
+                        matrix.read_score(code_1, code_2),
+                        matrix.read_score(code_2, code_1)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blosum62_j_row_is_ncbi() {
+        // The J row of the NCBI BLOSUM62 file.
+        let ncbi = [
+            (Aac::A, -1),
+            (Aac::R, -2),
+            (Aac::N, -3),
+            (Aac::D, -3),
+            (Aac::C, -1),
+            (Aac::Q, -2),
+            (Aac::E, -3),
+            (Aac::G, -4),
+            (Aac::H, -3),
+            (Aac::I, 3),
+            (Aac::L, 3),
+            (Aac::K, -3),
+            (Aac::M, 2),
+            (Aac::F, 0),
+            (Aac::P, -3),
+            (Aac::S, -2),
+            (Aac::T, -1),
+            (Aac::W, -2),
+            (Aac::Y, -1),
+            (Aac::V, 2),
+            (Aac::B, -3),
+            (Aac::J, 3),
+            (Aac::Z, -3),
+            (Aac::X, -1),
+            (Aac::Stop, -4),
+        ];
+        let matrix = similarity_builder(AaScoringKind::Blosum62);
+        for (code, score) in ncbi {
+            assert_eq!(
+                score,
+                matrix.read_score(Aac::J, code),
+                "J/{}",
+                char::from(&code)
+            );
+            assert_eq!(score, matrix.read_score(code, Aac::J));
+        }
+    }
+
+    #[test]
+    fn builtin_j_scores_between_i_and_l() {
+        for kind in all_builtin() {
+            let matrix = similarity_builder(kind);
+            for code in ALL_AAC {
+                if code == Aac::J {
+                    continue;
+                }
+                let i = matrix.read_score(Aac::I, code);
+                let l = matrix.read_score(Aac::L, code);
+                let j = matrix.read_score(Aac::J, code);
+                assert!(i.min(l) <= j && j <= i.max(l), "J/{}", char::from(&code));
+            }
+        }
+    }
+
+    #[test]
+    fn table_codes_follow_the_table_order() {
+        for (index, code) in TABLE_CODES.into_iter().enumerate() {
+            assert_eq!(index, map_aminoacid_to_index(code));
+        }
+        for code in ALL_AAC {
+            let expected = STANDARD_CODES.iter().position(|standard| *standard == code);
+            assert_eq!(expected, standard_index(code), "{}", char::from(&code));
+        }
+    }
+
+    #[test]
+    fn non_ncbi_codes_share_rows() {
+        for kind in all_builtin() {
+            let matrix = similarity_builder(kind);
+            for code in ALL_AAC {
+                assert_eq!(
+                    matrix.read_score(Aac::C, code),
+                    matrix.read_score(Aac::U, code)
+                );
+                assert_eq!(
+                    matrix.read_score(Aac::K, code),
+                    matrix.read_score(Aac::O, code)
+                );
+            }
+        }
+        let blosum = similarity_builder(AaScoringKind::Blosum62);
+        assert_eq!(3, blosum.read_score(Aac::J, Aac::J));
+        assert_eq!(2, blosum.read_score(Aac::J, Aac::V));
+    }
+
     #[test]
     fn read_pam160_is_complete() {
         let pam = similarity_builder(AaScoringKind::Pam160);
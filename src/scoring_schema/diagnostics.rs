@@ -0,0 +1,337 @@
+//! Information-theoretic properties of amino acid substitution matrices (Altschul 1991,
+//! "Amino acid substitution matrices from an information theoretic perspective").
+//!
+//! Any matrix with a positive score and a negative expected score is a log-odds matrix,
+//! s_ij = ln(q_ij / (p_i * p_j)) / λ, for a unique positive λ and the target frequencies
+//! q_ij = p_i * p_j * e^(λ s_ij) of the aligned pairs it implicitly expects, where p_i are
+//! the background frequencies of the residues. The relative entropy of the target and the
+//! background frequencies, H = Σ q_ij log2(q_ij / (p_i * p_j)), is the average information
+//! per aligned pair: matrices with a lower H suit more distant sequences.
+
+use super::aminoacid_data::{STANDARD, STANDARD_CODES};
+use super::substitution_matrix::SubstitutionMatrix;
+use super::{Similarity, SimilarityType};
+use crate::bioseq::Aac;
+use crate::statistics::KarlinAltschul;
+use std::f64::consts::LN_2;
+use std::{error, fmt};
+
+/// Properties of a substitution matrix under some background frequencies.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct MatrixDiagnostics {
+    lambda: f64,
+    relative_entropy: f64,
+    expected_score: f64,
+    target_frequencies: Vec<(Aac, Aac, f64)>,
+    // Scores of the standard residues, for rescaled
+    scores: [[SimilarityType; STANDARD]; STANDARD],
+}
+
+impl MatrixDiagnostics {
+    /// Analyses the matrix when the residues of both sequences follow the background
+    /// frequencies. The frequencies are normalised, so they only need to be proportional
+    /// to the actual ones; statistics::ROBINSON_FREQUENCIES are the usual ones.
+    ///
+    /// Returns DiagnosticsError if the matrix has no positive score or its expected score
+    /// is not negative; then it is not a log-odds matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::scoring_schema::aminoacid_schema::{
+    ///     similarity_builder, AaScoringKind,
+    /// };
+    /// use pairwasm_alignment::scoring_schema::diagnostics::MatrixDiagnostics;
+    /// use pairwasm_alignment::statistics::ROBINSON_FREQUENCIES;
+    ///
+    /// let blosum45 = similarity_builder(AaScoringKind::Blosum45);
+    /// let blosum62 = similarity_builder(AaScoringKind::Blosum62);
+    /// let diagnostics_45 =
+    ///     MatrixDiagnostics::new(blosum45.as_ref(), &ROBINSON_FREQUENCIES).unwrap();
+    /// let diagnostics_62 =
+    ///     MatrixDiagnostics::new(blosum62.as_ref(), &ROBINSON_FREQUENCIES).unwrap();
+    /// assert!(diagnostics_45.relative_entropy() < diagnostics_62.relative_entropy());
+    /// ```
+    pub fn new(
+        similarity: &dyn Similarity<Aac>,
+        background: &[(Aac, f64)],
+    ) -> Result<Self, DiagnosticsError> {
+        let lambda = KarlinAltschul::ungapped(similarity, background)
+            .map_err(|error| {
+                DiagnosticsError::new(
+                    DiagnosticsErrorKind::NotLogOdds,
+                    &error.to_string(),
+                )
+            })?
+            .lambda();
+
+        let total: f64 = background.iter().map(|(_, frequency)| frequency).sum();
+        let mut relative_entropy = 0.0;
+        let mut expected_score = 0.0;
+        let mut target_frequencies = Vec::with_capacity(background.len().pow(2));
+        for (code_1, frequency_1) in background {
+            for (code_2, frequency_2) in background {
+                let score = similarity.read_score(*code_1, *code_2) as f64;
+                let pair = frequency_1 * frequency_2 / (total * total);
+                let target = pair * (lambda * score).exp();
+                relative_entropy += target * lambda * score / LN_2;
+                expected_score += pair * score;
+                target_frequencies.push((*code_1, *code_2, target));
+            }
+        }
+
+        let mut scores = [[0; STANDARD]; STANDARD];
+        for (row, code_1) in scores.iter_mut().zip(STANDARD_CODES) {
+            for (score, code_2) in row.iter_mut().zip(STANDARD_CODES) {
+                *score = similarity.read_score(code_1, code_2);
+            }
+        }
+
+        Ok(Self {
+            lambda,
+            relative_entropy,
+            expected_score,
+            target_frequencies,
+            scores,
+        })
+    }
+
+    /// The λ that turns a score into its log-odds in nats.
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Relative entropy H, in bits per aligned pair.
+    pub fn relative_entropy(&self) -> f64 {
+        self.relative_entropy
+    }
+
+    /// Expected score of a pair of residues drawn from the background, in the units of
+    /// the matrix.
+    pub fn expected_score(&self) -> f64 {
+        self.expected_score
+    }
+
+    /// Scale of the matrix, as units per bit: 2 for a matrix in half bits.
+    pub fn bit_scale(&self) -> f64 {
+        LN_2 / self.lambda
+    }
+
+    /// Target frequencies of each ordered pair of the background residues; they add up
+    /// to 1.
+    pub fn target_frequencies(&self) -> &[(Aac, Aac, f64)] {
+        &self.target_frequencies
+    }
+
+    /// Target frequency of the pair, or None if a residue is not in the background.
+    pub fn target_frequency(&self, code_1: Aac, code_2: Aac) -> Option<f64> {
+        self.target_frequencies
+            .iter()
+            .find(|(a, b, _)| *a == code_1 && *b == code_2)
+            .map(|(_, _, frequency)| *frequency)
+    }
+
+    /// The matrix with its scores in other units, round(scale * λ * s / ln 2); 1 gives
+    /// bits and 2 half bits. The rows of B, J, Z, X and '*' are filled from the standard
+    /// residues as in SubstitutionMatrix::from_ncbi_text.
+    ///
+    /// Returns DiagnosticsError if the scale is not positive or a score does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::Aac;
+    /// use pairwasm_alignment::scoring_schema::aminoacid_schema::{
+    ///     similarity_builder, AaScoringKind,
+    /// };
+    /// use pairwasm_alignment::scoring_schema::diagnostics::MatrixDiagnostics;
+    /// use pairwasm_alignment::scoring_schema::Similarity;
+    /// use pairwasm_alignment::statistics::ROBINSON_FREQUENCIES;
+    ///
+    /// let blosum62 = similarity_builder(AaScoringKind::Blosum62);
+    /// let diagnostics =
+    ///     MatrixDiagnostics::new(blosum62.as_ref(), &ROBINSON_FREQUENCIES).unwrap();
+    /// let third_bits = diagnostics.rescaled(3.0).unwrap();
+    /// assert!(third_bits.read_score(Aac::W, Aac::W) > blosum62.read_score(Aac::W, Aac::W));
+    /// ```
+    pub fn rescaled(
+        &self,
+        bit_scale: f64,
+    ) -> Result<SubstitutionMatrix, DiagnosticsError> {
+        if !(bit_scale.is_finite() && bit_scale > 0.0) {
+            return Err(DiagnosticsError::new(
+                DiagnosticsErrorKind::InvalidBitScale,
+                "",
+            ));
+        }
+        let factor = bit_scale * self.lambda / LN_2;
+        let mut scores = [[0; STANDARD]; STANDARD];
+        for (i, row) in scores.iter_mut().enumerate() {
+            for (j, score) in row.iter_mut().enumerate() {
+                let rescaled = (factor * self.scores[i][j] as f64).round();
+                if rescaled < SimilarityType::MIN as f64
+                    || rescaled > SimilarityType::MAX as f64
+                {
+                    return Err(DiagnosticsError::new(
+                        DiagnosticsErrorKind::ScoreOutOfRange,
+                        &format!(
+                            "{} and {} score {rescaled}.",
+                            char::from(&STANDARD_CODES[i]),
+                            char::from(&STANDARD_CODES[j])
+                        ),
+                    ));
+                }
+                *score = rescaled as SimilarityType;
+            }
+        }
+        Ok(SubstitutionMatrix::from_standard(&scores))
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of DiagnosticsError.
+pub enum DiagnosticsErrorKind {
+    InvalidBitScale,
+    NotLogOdds,
+    ScoreOutOfRange,
+}
+
+#[derive(Debug)]
+/// Error type for the diagnostics of substitution matrices.
+pub struct DiagnosticsError {
+    kind: DiagnosticsErrorKind,
+    message: String,
+}
+
+impl DiagnosticsError {
+    fn new(kind: DiagnosticsErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            DiagnosticsErrorKind::InvalidBitScale => {
+                "The bit scale must be positive.".to_string()
+            }
+            DiagnosticsErrorKind::NotLogOdds => {
+                "The matrix is not a log-odds matrix under the background frequencies."
+                    .to_string()
+            }
+            DiagnosticsErrorKind::ScoreOutOfRange => {
+                "The scores must be in [-128, 127]; try a smaller bit scale.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &DiagnosticsErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for DiagnosticsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for DiagnosticsError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scoring_schema::aminoacid_schema::{similarity_builder, AaScoringKind};
+    use crate::statistics::ROBINSON_FREQUENCIES;
+
+    fn diagnostics(kind: AaScoringKind) -> MatrixDiagnostics {
+        let similarity = similarity_builder(kind);
+        MatrixDiagnostics::new(similarity.as_ref(), &ROBINSON_FREQUENCIES).unwrap()
+    }
+
+    #[test]
+    fn blosum62_properties() {
+        // NCBI BLAST reports λ = 0.3176 and H = 0.4012 nats.
+        let blosum62 = diagnostics(AaScoringKind::Blosum62);
+        assert!((blosum62.lambda() - 0.3176).abs() < 5e-4, "{blosum62:?}");
+        assert!(
+            (blosum62.relative_entropy() - 0.4012 / LN_2).abs() < 1e-2,
+            "{blosum62:?}"
+        );
+        assert!((blosum62.bit_scale() - 2.18).abs() < 1e-2);
+        assert!(blosum62.expected_score() < 0.0);
+
+        let total: f64 = blosum62
+            .target_frequencies()
+            .iter()
+            .map(|(_, _, frequency)| frequency)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        // The target frequencies give back the scores.
+        let (a, w) = (0.07805, 0.01330);
+        let q = blosum62.target_frequency(Aac::W, Aac::W).unwrap();
+        assert!(((q / (w * w)).ln() / blosum62.lambda() - 11.0).abs() < 1e-3);
+        assert_eq!(
+            blosum62.target_frequency(Aac::A, Aac::W),
+            blosum62.target_frequency(Aac::W, Aac::A)
+        );
+        let q = blosum62.target_frequency(Aac::A, Aac::W).unwrap();
+        assert!(((q / (a * w)).ln() / blosum62.lambda() + 3.0).abs() < 1e-3);
+        assert!(blosum62.target_frequency(Aac::B, Aac::A).is_none());
+    }
+
+    #[test]
+    fn entropy_decreases_with_distance() {
+        let entropies: Vec<f64> = [
+            AaScoringKind::Blosum80,
+            AaScoringKind::Blosum62,
+            AaScoringKind::Blosum45,
+            AaScoringKind::Pam250,
+        ]
+        .into_iter()
+        .map(|kind| diagnostics(kind).relative_entropy())
+        .collect();
+        assert!(
+            entropies.windows(2).all(|pair| pair[0] > pair[1]),
+            "{entropies:?}"
+        );
+    }
+
+    #[test]
+    fn rescale_blosum62() {
+        let blosum62 = diagnostics(AaScoringKind::Blosum62);
+        let similarity = similarity_builder(AaScoringKind::Blosum62);
+
+        // At its own scale the matrix does not change.
+        let same = blosum62.rescaled(blosum62.bit_scale()).unwrap();
+        for code_1 in STANDARD_CODES {
+            for code_2 in STANDARD_CODES {
+                assert_eq!(
+                    similarity.read_score(code_1, code_2),
+                    same.read_score(code_1, code_2)
+                );
+            }
+        }
+
+        // W-W scores 11 half bits, about 5 bits.
+        let bits = blosum62.rescaled(1.0).unwrap();
+        assert_eq!(5, bits.read_score(Aac::W, Aac::W));
+        let rescaled = diagnostics(AaScoringKind::Custom(bits));
+        assert!((rescaled.bit_scale() - 1.0).abs() < 0.25, "{rescaled:?}");
+
+        assert!(blosum62
+            .rescaled(0.0)
+            .is_err_and(|e| e.kind() == &DiagnosticsErrorKind::InvalidBitScale));
+        assert!(blosum62
+            .rescaled(100.0)
+            .is_err_and(|e| e.kind() == &DiagnosticsErrorKind::ScoreOutOfRange));
+    }
+
+    #[test]
+    fn not_log_odds() {
+        let matrix = SubstitutionMatrix::from_standard(&[[1; STANDARD]; STANDARD]);
+        let result = MatrixDiagnostics::new(&matrix, &ROBINSON_FREQUENCIES);
+        assert!(result.is_err_and(|e| e.kind() == &DiagnosticsErrorKind::NotLogOdds));
+    }
+}
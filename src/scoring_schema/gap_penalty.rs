@@ -4,6 +4,7 @@
 
 use super::CostType;
 use super::GapPenalty;
+use crate::{InputError, InputErrorKind};
 
 pub const MIN_OPEN_COST: CostType = 1.0;
 pub const MAX_OPEN_COST: CostType = 100.0;
@@ -15,6 +16,7 @@
 /// * `𝘬`
 /// * `𝙒ₒ` - Gap open cost
 /// * `𝙒ₑ` - Gap extend cost
+#[derive(Clone, Copy)]
 pub enum PenaltyKind {
     // open_cost: CostType, extend_cost: CostType
     /// The penalty function is defined as 𝙛(𝘬) = 𝙒ₒ + 𝘬 ⋅ 𝙒ₑ, 𝘬 >= 1.
@@ -27,14 +29,14 @@ pub enum PenaltyKind {
     Linear(CostType),
 }
 
-/// Penalty constructor
-pub fn penalty_builder(kind: PenaltyKind) -> Box<dyn GapPenalty> {
-    match kind {
+/// Penalty constructor. Returns InputError if any cost is out of its valid interval.
+pub fn penalty_builder(kind: PenaltyKind) -> Result<Box<dyn GapPenalty>, InputError> {
+    Ok(match kind {
         PenaltyKind::Affine(open_cost, extend_cost) => {
-            Box::new(Affine::new(open_cost, extend_cost))
+            Box::new(Affine::new(open_cost, extend_cost)?)
         }
-        PenaltyKind::Linear(extend_cost) => Box::new(Linear::new(extend_cost)),
-    }
+        PenaltyKind::Linear(extend_cost) => Box::new(Linear::new(extend_cost)?),
+    })
 }
 
 /// Implements affine gap model.
@@ -45,13 +47,13 @@ pub struct Affine {
 }
 
 impl Affine {
-    fn new(open_cost: CostType, extend_cost: CostType) -> Self {
-        check_open_cost(&open_cost);
-        check_extend_cost(&extend_cost);
-        Self {
+    fn new(open_cost: CostType, extend_cost: CostType) -> Result<Self, InputError> {
+        check_open_cost(&open_cost)?;
+        check_extend_cost(&extend_cost)?;
+        Ok(Self {
             open_cost,
             extend_cost,
-        }
+        })
     }
 }
 
@@ -76,9 +78,9 @@ pub struct Linear {
 }
 
 impl Linear {
-    fn new(extend_cost: CostType) -> Self {
-        check_extend_cost(&extend_cost);
-        Self { extend_cost }
+    fn new(extend_cost: CostType) -> Result<Self, InputError> {
+        check_extend_cost(&extend_cost)?;
+        Ok(Self { extend_cost })
     }
 }
 
@@ -98,29 +100,29 @@ fn extend(&self) -> CostType {
 }
 
 fn check_length(length: usize) {
-    // To guard in case of a future implementation changes length type
-    #[allow(clippy::absurd_extreme_comparisons)]
-    if length <= 0 {
-        panic!("Length must be a positive value.")
-    }
+    // The aligners only evaluate gaps of length 1 or more, so a zero length is a bug in
+    // the caller and not an input error.
+    debug_assert!(length > 0, "Length must be a positive value.");
 }
 
-fn check_open_cost(open_cost: &CostType) {
+fn check_open_cost(open_cost: &CostType) -> Result<(), InputError> {
     if !(MIN_OPEN_COST..=MAX_OPEN_COST).contains(open_cost) {
-        panic!(
-            "Invalid gap open cost ({}). It must be in the closed interval [{}, {}].",
-            open_cost, MIN_OPEN_COST, MAX_OPEN_COST
-        )
+        return Err(InputError::new(
+            InputErrorKind::InvalidOpenCost,
+            &format!("Got {open_cost}."),
+        ));
     }
+    Ok(())
 }
 
-fn check_extend_cost(extend_cost: &CostType) {
+fn check_extend_cost(extend_cost: &CostType) -> Result<(), InputError> {
     if !(MIN_EXTEND_COST..=MAX_EXTEND_COST).contains(extend_cost) {
-        panic!(
-            "Invalid gap extend cost ({}). It must be in the closed interval [{}, {}].",
-            extend_cost, MIN_EXTEND_COST, MAX_EXTEND_COST
-        )
+        return Err(InputError::new(
+            InputErrorKind::InvalidExtendCost,
+            &format!("Got {extend_cost}."),
+        ));
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -129,69 +131,75 @@ mod test {
 
     #[test]
     fn valid_affine() {
-        let gap_model = penalty_builder(PenaltyKind::Affine(1.0, 0.5));
+        let gap_model = penalty_builder(PenaltyKind::Affine(1.0, 0.5)).unwrap();
         assert_eq!(6.0, gap_model.function(10));
         assert_eq!(6.0, gap_model.open() + gap_model.extend() * 10.0);
 
-        let gap_model = penalty_builder(PenaltyKind::Affine(15.0, 2.0));
+        let gap_model = penalty_builder(PenaltyKind::Affine(15.0, 2.0)).unwrap();
         assert_eq!(21.0, gap_model.function(3));
         assert_eq!(21.0, gap_model.open() + gap_model.extend() * 3.0)
     }
 
     #[test]
-    #[should_panic(
-        expected = "Invalid gap open cost (-1). It must be in the closed interval [1, 100]."
-    )]
     fn invalid_affine_param_open_negative() {
-        penalty_builder(PenaltyKind::Affine(-1.0, 0.5));
+        assert!(penalty_builder(PenaltyKind::Affine(-1.0, 0.5))
+            .is_err_and(|e| e.kind == InputErrorKind::InvalidOpenCost));
     }
 
     #[test]
-    #[should_panic(
-        expected = "Invalid gap open cost (0). It must be in the closed interval [1, 100]."
-    )]
     fn invalid_affine_param_open_zero() {
-        penalty_builder(PenaltyKind::Affine(0.0, 0.5));
+        assert!(penalty_builder(PenaltyKind::Affine(0.0, 0.5))
+            .is_err_and(|e| e.kind == InputErrorKind::InvalidOpenCost));
     }
 
     #[test]
-    #[should_panic(
-        expected = "Invalid gap extend cost (-0.5). It must be in the closed interval [0, 10]."
-    )]
     fn invalid_affine_param_extend_negative() {
-        penalty_builder(PenaltyKind::Affine(1.0, -0.5));
+        assert!(penalty_builder(PenaltyKind::Affine(1.0, -0.5))
+            .is_err_and(|e| e.kind == InputErrorKind::InvalidExtendCost));
+    }
+
+    #[test]
+    fn invalid_cost_message_has_value() {
+        let error = penalty_builder(PenaltyKind::Affine(150.0, 0.5))
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Got 150."));
+        let error = penalty_builder(PenaltyKind::Linear(f32::NAN))
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Got NaN."));
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic(expected = "Length must be a positive value.")]
     fn invalid_affine_length() {
-        let gap_model = penalty_builder(PenaltyKind::Affine(1.0, 0.5));
+        let gap_model = penalty_builder(PenaltyKind::Affine(1.0, 0.5)).unwrap();
         gap_model.function(0);
     }
 
     #[test]
     fn valid_linear() {
-        let gap_model = penalty_builder(PenaltyKind::Linear(0.5));
+        let gap_model = penalty_builder(PenaltyKind::Linear(0.5)).unwrap();
         assert_eq!(4.5, gap_model.function(9));
         assert_eq!(4.5, gap_model.open() + gap_model.extend() * 9.0);
 
-        let gap_model = penalty_builder(PenaltyKind::Linear(9.0));
+        let gap_model = penalty_builder(PenaltyKind::Linear(9.0)).unwrap();
         assert_eq!(27.0, gap_model.function(3));
         assert_eq!(27.0, gap_model.open() + gap_model.extend() * 3.0)
     }
 
     #[test]
-    #[should_panic(
-        expected = "Invalid gap extend cost (-0.5). It must be in the closed interval [0, 10]."
-    )]
     fn invalid_linear_param_extend_negative() {
-        penalty_builder(PenaltyKind::Linear(-0.5));
+        assert!(penalty_builder(PenaltyKind::Linear(-0.5))
+            .is_err_and(|e| e.kind == InputErrorKind::InvalidExtendCost));
     }
 
     #[test]
+    #[cfg(debug_assertions)]
     #[should_panic(expected = "Length must be a positive value.")]
     fn invalid_linear_length() {
-        let gap_model = penalty_builder(PenaltyKind::Linear(7.0));
+        let gap_model = penalty_builder(PenaltyKind::Linear(7.0)).unwrap();
         gap_model.function(0);
     }
 }
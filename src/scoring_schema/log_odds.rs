@@ -0,0 +1,422 @@
+//! Log-odds substitution matrices derived from blocks of aligned sequences, as the BLOSUM
+//! matrices (Henikoff & Henikoff 1992, "Amino acid substitution matrices from protein
+//! blocks").
+//!
+//! Every column of a block contributes the pairs of residues aligned in it. Close sequences
+//! are clustered and weighted so that each cluster counts as one sequence, which keeps
+//! families with many near-identical members from dominating the counts. From the frequency
+//! q_ij of each pair and the frequency p_i of each residue among the counted pairs, the
+//! score of residues i and j is round(scale * log2(q_ij / (p_i * p_j))).
+
+use super::aminoacid_data::{standard_index, STANDARD, STANDARD_CODES};
+use super::substitution_matrix::SubstitutionMatrix;
+use super::SimilarityType;
+use crate::bioseq::Aac;
+use std::{error, fmt};
+
+/// Rows of a block of aligned sequences, one per sequence, with gaps as None.
+pub struct AlignedBlock {
+    rows: Vec<Vec<Option<Aac>>>,
+}
+
+impl AlignedBlock {
+    /// Reads the rows of a block. '-' and '.' are gaps and the other characters must be
+    /// amino acid codes; whitespace is ignored. All the rows must have the same length.
+    ///
+    /// Returns LogOddsError if a character is not valid or the rows differ in length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::scoring_schema::log_odds::AlignedBlock;
+    ///
+    /// assert!(AlignedBlock::new(&["HGEYW", "HG-YW", "HAEFW"]).is_ok());
+    /// assert!(AlignedBlock::new(&["HGEYW", "HGYW"]).is_err());
+    /// ```
+    pub fn new(rows: &[&str]) -> Result<Self, LogOddsError> {
+        let mut block: Vec<Vec<Option<Aac>>> = Vec::with_capacity(rows.len());
+        for (index, row) in rows.iter().enumerate() {
+            let mut residues: Vec<Option<Aac>> = Vec::with_capacity(row.len());
+            for char_code in row.chars().filter(|c| !c.is_whitespace()) {
+                residues.push(match char_code {
+                    '-' | '.' => None,
+                    _ => Some(Aac::from_char(char_code).map_err(|_| {
+                        LogOddsError::new(
+                            LogOddsErrorKind::InvalidCode,
+                            &format!("Row {}: '{char_code}'.", index + 1),
+                        )
+                    })?),
+                });
+            }
+            if let Some(first) = block.first() {
+                if first.len() != residues.len() {
+                    return Err(LogOddsError::new(
+                        LogOddsErrorKind::RowLength,
+                        &format!(
+                            "Row {} has {} columns, the first one {}.",
+                            index + 1,
+                            residues.len(),
+                            first.len()
+                        ),
+                    ));
+                }
+            }
+            block.push(residues);
+        }
+        Ok(Self { rows: block })
+    }
+
+    /// The rows of the block, with gaps as None.
+    pub fn rows(&self) -> &[Vec<Option<Aac>>] {
+        &self.rows
+    }
+
+    // Cluster of each row, by single linkage of the rows sharing at least `percentage`
+    // identical residues, and the size of each cluster.
+    fn clusters(&self, percentage: Option<f64>) -> (Vec<usize>, Vec<usize>) {
+        let mut parent: Vec<usize> = (0..self.rows.len()).collect();
+        if let Some(percentage) = percentage {
+            for a in 0..self.rows.len() {
+                for b in a + 1..self.rows.len() {
+                    if self.identity(a, b) >= percentage {
+                        let (root_a, root_b) =
+                            (find(&mut parent, a), find(&mut parent, b));
+                        parent[root_b] = root_a;
+                    }
+                }
+            }
+        }
+        let clusters: Vec<usize> = (0..self.rows.len())
+            .map(|row| find(&mut parent, row))
+            .collect();
+        let mut sizes = vec![0; self.rows.len()];
+        for cluster in &clusters {
+            sizes[*cluster] += 1;
+        }
+        (clusters, sizes)
+    }
+
+    // Percentage of identical residues over the columns where neither row has a gap.
+    fn identity(&self, a: usize, b: usize) -> f64 {
+        let mut aligned = 0;
+        let mut identical = 0;
+        for (residue_a, residue_b) in self.rows[a].iter().zip(&self.rows[b]) {
+            if let (Some(residue_a), Some(residue_b)) = (residue_a, residue_b) {
+                aligned += 1;
+                if residue_a == residue_b {
+                    identical += 1;
+                }
+            }
+        }
+        match aligned {
+            0 => 0.0,
+            _ => 100.0 * identical as f64 / aligned as f64,
+        }
+    }
+}
+
+// Root of the union-find tree of `node`, compressing the path to it.
+fn find(parent: &mut [usize], node: usize) -> usize {
+    let mut root = node;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut node = node;
+    while parent[node] != root {
+        let next = parent[node];
+        parent[node] = root;
+        node = next;
+    }
+    root
+}
+
+/// How derive_matrix weights the pairs and scales the scores.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LogOddsSettings {
+    /// Rows of a block sharing at least this percentage of identical residues (over the
+    /// columns where both have one) join the same cluster, and the rows of a cluster
+    /// weigh as one sequence. Pairs within a cluster are not counted. BLOSUM62 used 62.
+    /// None weighs every row as one sequence.
+    pub clustering: Option<f64>,
+    /// Added to the weighted count of each of the 210 pairs of standard residues, so the
+    /// pairs missing from the blocks still get a finite score.
+    pub pseudocount: f64,
+    /// Score units per bit. 2 gives half-bit scores, as BLOSUM62; 3 third-bit scores, as
+    /// BLOSUM50.
+    pub bit_scale: f64,
+}
+
+impl Default for LogOddsSettings {
+    fn default() -> Self {
+        Self {
+            clustering: Some(62.0),
+            pseudocount: 1.0,
+            bit_scale: 2.0,
+        }
+    }
+}
+
+/// Derives a log-odds substitution matrix from the pairs of residues aligned in the
+/// blocks. Only the 20 standard amino acids are counted; the rows of the ambiguity codes
+/// and the stop are filled as in SubstitutionMatrix::from_ncbi_text. Use the result with
+/// AaScoringKind::Custom, or export it with SubstitutionMatrix::to_ncbi_text.
+///
+/// Returns LogOddsError if the settings are not valid, no pair could be counted, a pair
+/// has no count (add a pseudocount) or a score does not fit in the matrix.
+///
+/// # Examples
+///
+/// ```
+/// use pairwasm_alignment::bioseq::Aac;
+/// use pairwasm_alignment::scoring_schema::log_odds::{
+///     derive_matrix, AlignedBlock, LogOddsSettings,
+/// };
+/// use pairwasm_alignment::scoring_schema::Similarity;
+///
+/// let blocks = [
+///     AlignedBlock::new(&["LIVMFW", "IVLMYW", "VLIFWY"]).unwrap(),
+///     AlignedBlock::new(&["DEKRST", "EDRKTS", "DEKRSS"]).unwrap(),
+/// ];
+/// let matrix = derive_matrix(&blocks, LogOddsSettings::default()).unwrap();
+/// assert!(matrix.read_score(Aac::I, Aac::L) > matrix.read_score(Aac::I, Aac::D));
+/// ```
+pub fn derive_matrix(
+    blocks: &[AlignedBlock],
+    settings: LogOddsSettings,
+) -> Result<SubstitutionMatrix, LogOddsError> {
+    let valid_clustering = settings
+        .clustering
+        .is_none_or(|percentage| (0.0..=100.0).contains(&percentage));
+    let valid_pseudocount =
+        settings.pseudocount.is_finite() && settings.pseudocount >= 0.0;
+    let valid_bit_scale = settings.bit_scale.is_finite() && settings.bit_scale > 0.0;
+    if !(valid_clustering && valid_pseudocount && valid_bit_scale) {
+        return Err(LogOddsError::new(LogOddsErrorKind::InvalidSettings, ""));
+    }
+
+    // Weighted counts of the ordered pairs; a pair of different residues adds half of its
+    // weight to each order.
+    let mut counts = [[0.0_f64; STANDARD]; STANDARD];
+    let mut counted = false;
+    for block in blocks {
+        let (clusters, sizes) = block.clusters(settings.clustering);
+        let width = block.rows.first().map_or(0, |row| row.len());
+        for column in 0..width {
+            for a in 0..block.rows.len() {
+                for b in a + 1..block.rows.len() {
+                    if clusters[a] == clusters[b] {
+                        continue;
+                    }
+                    let (Some(i), Some(j)) = (
+                        block.rows[a][column].and_then(standard_index),
+                        block.rows[b][column].and_then(standard_index),
+                    ) else {
+                        continue;
+                    };
+                    let weight = 1.0 / (sizes[clusters[a]] * sizes[clusters[b]]) as f64;
+                    counts[i][j] += weight / 2.0;
+                    counts[j][i] += weight / 2.0;
+                    counted = true;
+                }
+            }
+        }
+    }
+    if !counted {
+        return Err(LogOddsError::new(LogOddsErrorKind::NoPairs, ""));
+    }
+    for (i, row) in counts.iter_mut().enumerate() {
+        for (j, count) in row.iter_mut().enumerate() {
+            *count += match i == j {
+                true => settings.pseudocount,
+                false => settings.pseudocount / 2.0,
+            };
+        }
+    }
+
+    let total: f64 = counts.iter().flatten().sum();
+    let frequencies: Vec<f64> = counts
+        .iter()
+        .map(|row| row.iter().sum::<f64>() / total)
+        .collect();
+    let mut scores = [[0; STANDARD]; STANDARD];
+    for i in 0..STANDARD {
+        for j in 0..STANDARD {
+            if counts[i][j] == 0.0 {
+                return Err(LogOddsError::new(
+                    LogOddsErrorKind::UnobservedPair,
+                    &format!(
+                        "{} and {}.",
+                        char::from(&STANDARD_CODES[i]),
+                        char::from(&STANDARD_CODES[j])
+                    ),
+                ));
+            }
+            let odds = counts[i][j] / total / (frequencies[i] * frequencies[j]);
+            let score = (settings.bit_scale * odds.log2()).round();
+            if score < SimilarityType::MIN as f64 || score > SimilarityType::MAX as f64 {
+                return Err(LogOddsError::new(
+                    LogOddsErrorKind::ScoreOutOfRange,
+                    &format!(
+                        "{} and {} score {score}.",
+                        char::from(&STANDARD_CODES[i]),
+                        char::from(&STANDARD_CODES[j])
+                    ),
+                ));
+            }
+            scores[i][j] = score as SimilarityType;
+        }
+    }
+    Ok(SubstitutionMatrix::from_standard(&scores))
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of LogOddsError.
+pub enum LogOddsErrorKind {
+    InvalidCode,
+    InvalidSettings,
+    NoPairs,
+    RowLength,
+    ScoreOutOfRange,
+    UnobservedPair,
+}
+
+#[derive(Debug)]
+/// Error type for the derivation of log-odds matrices.
+pub struct LogOddsError {
+    kind: LogOddsErrorKind,
+    message: String,
+}
+
+impl LogOddsError {
+    fn new(kind: LogOddsErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            LogOddsErrorKind::InvalidCode => {
+                "The blocks must only have amino acid codes and gaps.".to_string()
+            }
+            LogOddsErrorKind::InvalidSettings => {
+                "The clustering percentage must be in [0, 100], the pseudocount not negative and the bit scale positive."
+                    .to_string()
+            }
+            LogOddsErrorKind::NoPairs => {
+                "The blocks must align standard residues of different clusters.".to_string()
+            }
+            LogOddsErrorKind::RowLength => {
+                "The rows of a block must have the same length.".to_string()
+            }
+            LogOddsErrorKind::ScoreOutOfRange => {
+                "The scores must be in [-128, 127]; try a smaller bit scale.".to_string()
+            }
+            LogOddsErrorKind::UnobservedPair => {
+                "A pair of residues was never aligned; try a pseudocount.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &LogOddsErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for LogOddsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for LogOddsError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scoring_schema::Similarity;
+
+    const NO_PSEUDOCOUNT: LogOddsSettings = LogOddsSettings {
+        clustering: None,
+        pseudocount: 0.0,
+        bit_scale: 2.0,
+    };
+
+    #[test]
+    fn score_of_two_residues() {
+        // Columns A/A and A/S: q(A, A) = 1/2, q(A, S) = q(S, A) = 1/4, p(A) = 3/4 and
+        // p(S) = 1/4, so s(A, A) = 2 log2(8/9) and s(A, S) = 2 log2(4/3).
+        let block = AlignedBlock::new(&["AA", "AS"]).unwrap();
+        let settings = LogOddsSettings {
+            pseudocount: 1e-9,
+            ..NO_PSEUDOCOUNT
+        };
+        let matrix = derive_matrix(&[block], settings).unwrap();
+        assert_eq!(0, matrix.read_score(Aac::A, Aac::A));
+        assert_eq!(1, matrix.read_score(Aac::A, Aac::S));
+        assert_eq!(1, matrix.read_score(Aac::S, Aac::A));
+    }
+
+    #[test]
+    fn clusters_weigh_as_one_sequence() {
+        // Both A rows join one cluster, which weighs as much as the S row.
+        let block = AlignedBlock::new(&["AW", "AW", "SW"]).unwrap();
+        assert_eq!((vec![0, 0, 2], vec![2, 0, 1]), block.clusters(Some(60.0)));
+        assert_eq!((vec![0, 1, 2], vec![1, 1, 1]), block.clusters(None));
+        assert_eq!(50.0, block.identity(0, 2));
+
+        let identical = AlignedBlock::new(&["HGEYW", "HGEYW"]).unwrap();
+        let error = derive_matrix(&[identical], LogOddsSettings::default()).unwrap_err();
+        assert_eq!(&LogOddsErrorKind::NoPairs, error.kind());
+    }
+
+    #[test]
+    fn similar_residues_score_higher() {
+        let blocks = [
+            AlignedBlock::new(&["LIVMFWACGH", "IVLMYWSCGH", "VLIFWYACGN"]).unwrap(),
+            AlignedBlock::new(&["DEKRSTPQ", "EDRKTSPQ", "DEKRSSPE"]).unwrap(),
+        ];
+        let matrix = derive_matrix(&blocks, LogOddsSettings::default()).unwrap();
+        assert!(matrix.read_score(Aac::I, Aac::L) > matrix.read_score(Aac::I, Aac::D));
+        assert!(matrix.read_score(Aac::C, Aac::C) > 0);
+        assert!(matrix.read_score(Aac::C, Aac::W) < 0);
+        let text = matrix.to_ncbi_text("Derived");
+        assert_eq!(matrix, SubstitutionMatrix::from_ncbi_text(&text).unwrap());
+    }
+
+    #[test]
+    fn invalid_blocks_and_settings() {
+        let error = AlignedBlock::new(&["HGEYW", "HGEY"]).err().unwrap();
+        assert_eq!(&LogOddsErrorKind::RowLength, error.kind());
+        let error = AlignedBlock::new(&["HGEYW", "HGE#W"]).err().unwrap();
+        assert_eq!(&LogOddsErrorKind::InvalidCode, error.kind());
+
+        let block = || AlignedBlock::new(&["HGEYW", "HAEFW"]).unwrap();
+        let error = derive_matrix(&[block()], NO_PSEUDOCOUNT).unwrap_err();
+        assert_eq!(&LogOddsErrorKind::UnobservedPair, error.kind());
+        for settings in [
+            LogOddsSettings {
+                clustering: Some(101.0),
+                ..Default::default()
+            },
+            LogOddsSettings {
+                pseudocount: -1.0,
+                ..Default::default()
+            },
+            LogOddsSettings {
+                bit_scale: 0.0,
+                ..Default::default()
+            },
+        ] {
+            let error = derive_matrix(&[block()], settings).unwrap_err();
+            assert_eq!(&LogOddsErrorKind::InvalidSettings, error.kind());
+        }
+        let settings = LogOddsSettings {
+            bit_scale: 100.0,
+            ..Default::default()
+        };
+        let error = derive_matrix(&[block()], settings).unwrap_err();
+        assert_eq!(&LogOddsErrorKind::ScoreOutOfRange, error.kind());
+    }
+}
@@ -6,13 +6,22 @@
 
 mod aminoacid_data;
 pub mod aminoacid_schema;
+pub mod diagnostics;
 pub mod gap_penalty;
+pub mod log_odds;
+mod nucleotide_data;
+pub mod nucleotide_schema;
+pub mod profile;
+pub mod substitution_matrix;
 
-use crate::bioseq::Aac;
+use crate::bioseq::{Aac, Nuc};
 use crate::utils::AlignmentUnit;
+use crate::InputError;
+use std::rc::Rc;
 
 use self::aminoacid_schema::AaScoringKind;
 use self::gap_penalty::PenaltyKind;
+use self::nucleotide_schema::NucScoringKind;
 
 type CostType = f32;
 type SimilarityType = i8;
@@ -27,7 +36,7 @@ pub trait Similarity<A>
 
 /// Scoring schema's gap penalty component
 pub trait GapPenalty {
-    /// The gap penalty is a map (length) ↦ ℝ.
+    /// The gap penalty is a map (length) ↦ ℝ, defined for length ≥ 1.
     fn function(&self, length: usize) -> CostType;
 
     /// Get the open gap parameter. Be aware that under some gap penalty models
@@ -50,6 +59,50 @@ pub trait ScoringSchema<A>
     fn get_open(&self) -> CostType;
 
     fn get_extend(&self) -> CostType;
+
+    /// Gap costs [open, extend] of the gaps facing the unit: a gap in front of the units
+    /// u₁...uₖ of the other sequence costs open(u₁) + extend(u₁) + ... + extend(uₖ).
+    /// Position-specific schemas, as profiles, give some units their own costs; the others
+    /// use the same ones for every unit.
+    fn get_gap_costs(&self, _code: A) -> [CostType; 2] {
+        [self.get_open(), self.get_extend()]
+    }
+
+    /// Whether get_gap_costs changes with the unit. The linear-space algorithm assumes it
+    /// does not, so the aligners always keep the traceback matrices of these schemas.
+    fn has_position_specific_gaps(&self) -> bool {
+        false
+    }
+}
+
+// A shared schema scores as the schema, so several aligners can use the same one.
+impl<A> ScoringSchema<A> for Rc<dyn ScoringSchema<A>>
+where
+    A: AlignmentUnit,
+{
+    fn get_score(&self, code_1: A, code_2: A) -> SimilarityType {
+        self.as_ref().get_score(code_1, code_2)
+    }
+
+    fn get_function(&self, length: usize) -> CostType {
+        self.as_ref().get_function(length)
+    }
+
+    fn get_open(&self) -> CostType {
+        self.as_ref().get_open()
+    }
+
+    fn get_extend(&self) -> CostType {
+        self.as_ref().get_extend()
+    }
+
+    fn get_gap_costs(&self, code: A) -> [CostType; 2] {
+        self.as_ref().get_gap_costs(code)
+    }
+
+    fn has_position_specific_gaps(&self) -> bool {
+        self.as_ref().has_position_specific_gaps()
+    }
 }
 
 /// Amino acid sequence scoring schema
@@ -59,13 +112,17 @@ pub struct AaScoringSchema {
 }
 
 impl AaScoringSchema {
-    pub fn new(score_kind: AaScoringKind, penalty_kind: PenaltyKind) -> Self {
+    /// Returns InputError if the gap penalty parameters are not valid.
+    pub fn new(
+        score_kind: AaScoringKind,
+        penalty_kind: PenaltyKind,
+    ) -> Result<Self, InputError> {
         let substitution = aminoacid_schema::similarity_builder(score_kind);
-        let penalty = gap_penalty::penalty_builder(penalty_kind);
-        Self {
+        let penalty = gap_penalty::penalty_builder(penalty_kind)?;
+        Ok(Self {
             substitution,
             penalty,
-        }
+        })
     }
 }
 
@@ -86,3 +143,42 @@ fn get_extend(&self) -> CostType {
         self.penalty.extend()
     }
 }
+
+/// Nucleotide sequence scoring schema
+pub struct NucScoringSchema {
+    substitution: Box<dyn Similarity<Nuc>>,
+    penalty: Box<dyn GapPenalty>,
+}
+
+impl NucScoringSchema {
+    /// Returns InputError if the gap penalty parameters are not valid.
+    pub fn new(
+        score_kind: NucScoringKind,
+        penalty_kind: PenaltyKind,
+    ) -> Result<Self, InputError> {
+        let substitution = nucleotide_schema::similarity_builder(score_kind);
+        let penalty = gap_penalty::penalty_builder(penalty_kind)?;
+        Ok(Self {
+            substitution,
+            penalty,
+        })
+    }
+}
+
+impl ScoringSchema<Nuc> for NucScoringSchema {
+    fn get_score(&self, code_1: Nuc, code_2: Nuc) -> SimilarityType {
+        self.substitution.read_score(code_1, code_2)
+    }
+
+    fn get_function(&self, length: usize) -> CostType {
+        self.penalty.function(length)
+    }
+
+    fn get_open(&self) -> CostType {
+        self.penalty.open()
+    }
+
+    fn get_extend(&self) -> CostType {
+        self.penalty.extend()
+    }
+}
@@ -0,0 +1,50 @@
+//! Data structures storing nucleotide substitution matrices
+
+use crate::bioseq::Nuc;
+
+// Rows and columns order of the tables: A T G C S W R Y K M B V H D N.
+// Uracil shares the Thymine row.
+const fn map_nucleotide_to_index(code: Nuc) -> usize {
+    match code {
+        Nuc::A => 0,
+        Nuc::T | Nuc::U => 1,
+        Nuc::G => 2,
+        Nuc::C => 3,
+        Nuc::S => 4,
+        Nuc::W => 5,
+        Nuc::R => 6,
+        Nuc::Y => 7,
+        Nuc::K => 8,
+        Nuc::M => 9,
+        Nuc::B => 10,
+        Nuc::V => 11,
+        Nuc::H => 12,
+        Nuc::D => 13,
+        Nuc::N => 14,
+    }
+}
+
+/// NCBI EDNAFULL (NUC.4.4).
+#[rustfmt::skip]
+const EDNAFULL: [[i8; 15]; 15] = [
+    //A  T   G   C   S   W   R   Y   K   M   B   V   H   D   N
+    [ 5, -4, -4, -4, -4,  1,  1, -4, -4,  1, -4, -1, -1, -1, -2], // A
+    [-4,  5, -4, -4, -4,  1, -4,  1,  1, -4, -1, -4, -1, -1, -2], // T
+    [-4, -4,  5, -4,  1, -4,  1, -4,  1, -4, -1, -1, -4, -1, -2], // G
+    [-4, -4, -4,  5,  1, -4, -4,  1, -4,  1, -1, -1, -1, -4, -2], // C
+    [-4, -4,  1,  1, -1, -4, -2, -2, -2, -2, -1, -1, -3, -3, -1], // S
+    [ 1,  1, -4, -4, -4, -1, -2, -2, -2, -2, -3, -3, -1, -1, -1], // W
+    [ 1, -4,  1, -4, -2, -2, -1, -4, -2, -2, -3, -1, -3, -1, -1], // R
+    [-4,  1, -4,  1, -2, -2, -4, -1, -2, -2, -1, -3, -1, -3, -1], // Y
+    [-4,  1,  1, -4, -2, -2, -2, -2, -1, -4, -1, -3, -3, -1, -1], // K
+    [ 1, -4, -4,  1, -2, -2, -2, -2, -4, -1, -3, -1, -1, -3, -1], // M
+    [-4, -1, -1, -1, -1, -3, -3, -1, -1, -3, -1, -2, -2, -2, -1], // B
+    [-1, -4, -1, -1, -1, -3, -1, -3, -3, -1, -2, -1, -2, -2, -1], // V
+    [-1, -1, -4, -1, -3, -1, -3, -1, -3, -1, -2, -2, -1, -2, -1], // H
+    [-1, -1, -1, -4, -3, -1, -1, -3, -1, -3, -2, -2, -2, -1, -1], // D
+    [-2, -2, -2, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1], // N
+];
+
+pub const fn read_ednafull(code_1: Nuc, code_2: Nuc) -> i8 {
+    EDNAFULL[map_nucleotide_to_index(code_1)][map_nucleotide_to_index(code_2)]
+}
@@ -0,0 +1,248 @@
+//! Nucleotide scoring schemas
+//!
+//! The match/mismatch and transition/transversion schemas score the IUPAC ambiguity codes as
+//! the average score among all the pairs of bases they represent, rounded to the nearest
+//! integer.
+
+use super::nucleotide_data;
+use super::Similarity;
+use super::SimilarityType;
+use crate::bioseq::Nuc;
+
+/// Represents available Nucleotide scoring schemas
+pub enum NucScoringKind {
+    /// Same score for any match and for any mismatch.
+    /// MatchMismatch(match_score, mismatch_score)
+    MatchMismatch(SimilarityType, SimilarityType),
+    /// Distinguishes mismatches between two purines or two pyrimidines (transitions) from
+    /// mismatches between a purine and a pyrimidine (transversions).
+    /// TransitionTransversion(match_score, transition_score, transversion_score)
+    TransitionTransversion(SimilarityType, SimilarityType, SimilarityType),
+    /// NCBI EDNAFULL (NUC.4.4) matrix.
+    Ednafull,
+}
+
+/// Similarity schema constructor
+pub fn similarity_builder(kind: NucScoringKind) -> Box<dyn Similarity<Nuc>> {
+    match kind {
+        NucScoringKind::MatchMismatch(match_score, mismatch_score) => {
+            Box::new(MatchMismatch {
+                match_score,
+                mismatch_score,
+            })
+        }
+        NucScoringKind::TransitionTransversion(
+            match_score,
+            transition_score,
+            transversion_score,
+        ) => Box::new(TransitionTransversion {
+            match_score,
+            transition_score,
+            transversion_score,
+        }),
+        NucScoringKind::Ednafull => Box::new(Ednafull {}),
+    }
+}
+
+const BASE_A: u8 = 0b0001;
+const BASE_C: u8 = 0b0010;
+const BASE_G: u8 = 0b0100;
+// Thymine and Uracil play the same role in DNA and RNA, so they are scored as the same base.
+const BASE_T: u8 = 0b1000;
+const PURINES: u8 = BASE_A | BASE_G;
+const PYRIMIDINES: u8 = BASE_C | BASE_T;
+
+/// Set of bases represented by a code.
+const fn base_set(code: Nuc) -> u8 {
+    match code {
+        Nuc::A => BASE_A,
+        Nuc::C => BASE_C,
+        Nuc::G => BASE_G,
+        Nuc::T | Nuc::U => BASE_T,
+        Nuc::R => BASE_A | BASE_G,
+        Nuc::Y => BASE_C | BASE_T,
+        Nuc::S => BASE_G | BASE_C,
+        Nuc::W => BASE_A | BASE_T,
+        Nuc::K => BASE_G | BASE_T,
+        Nuc::M => BASE_A | BASE_C,
+        Nuc::B => BASE_C | BASE_G | BASE_T,
+        Nuc::D => BASE_A | BASE_G | BASE_T,
+        Nuc::H => BASE_A | BASE_C | BASE_T,
+        Nuc::V => BASE_A | BASE_C | BASE_G,
+        Nuc::N => BASE_A | BASE_C | BASE_G | BASE_T,
+    }
+}
+
+/// Averages the score of all the pairs of bases represented by both codes.
+fn average_over_bases(
+    code_1: Nuc,
+    code_2: Nuc,
+    base_score: impl Fn(u8, u8) -> SimilarityType,
+) -> SimilarityType {
+    let bases = [BASE_A, BASE_C, BASE_G, BASE_T];
+    let (set_1, set_2) = (base_set(code_1), base_set(code_2));
+    let mut total: i32 = 0;
+    let mut count: i32 = 0;
+    for base_1 in bases.into_iter().filter(|b| set_1 & b != 0) {
+        for base_2 in bases.into_iter().filter(|b| set_2 & b != 0) {
+            total += base_score(base_1, base_2) as i32;
+            count += 1;
+        }
+    }
+    (total as f32 / count as f32).round() as SimilarityType
+}
+
+pub struct MatchMismatch {
+    match_score: SimilarityType,
+    mismatch_score: SimilarityType,
+}
+
+impl Similarity<Nuc> for MatchMismatch {
+    fn read_score(&self, code_1: Nuc, code_2: Nuc) -> SimilarityType {
+        average_over_bases(code_1, code_2, |base_1, base_2| {
+            if base_1 == base_2 {
+                self.match_score
+            } else {
+                self.mismatch_score
+            }
+        })
+    }
+}
+
+pub struct TransitionTransversion {
+    match_score: SimilarityType,
+    transition_score: SimilarityType,
+    transversion_score: SimilarityType,
+}
+
+impl Similarity<Nuc> for TransitionTransversion {
+    fn read_score(&self, code_1: Nuc, code_2: Nuc) -> SimilarityType {
+        average_over_bases(code_1, code_2, |base_1, base_2| {
+            let pair = base_1 | base_2;
+            if base_1 == base_2 {
+                self.match_score
+            } else if pair == PURINES || pair == PYRIMIDINES {
+                self.transition_score
+            } else {
+                self.transversion_score
+            }
+        })
+    }
+}
+
+pub struct Ednafull {}
+
+impl Similarity<Nuc> for Ednafull {
+    fn read_score(&self, code_1: Nuc, code_2: Nuc) -> SimilarityType {
+        nucleotide_data::read_ednafull(code_1, code_2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ALL_NUC: [Nuc; 16] = [
+        Nuc::A,
+        Nuc::C,
+        Nuc::G,
+        Nuc::T,
+        Nuc::U,
+        Nuc::R,
+        Nuc::Y,
+        Nuc::S,
+        Nuc::W,
+        Nuc::K,
+        Nuc::M,
+        Nuc::B,
+        Nuc::D,
+        Nuc::H,
+        Nuc::V,
+        Nuc::N,
+    ];
+
+    #[test]
+    fn check_match_mismatch() {
+        let schema = similarity_builder(NucScoringKind::MatchMismatch(2, -3));
+        let score_cases = [
+            (2, Nuc::A, Nuc::A),
+            (2, Nuc::G, Nuc::G),
+            (2, Nuc::T, Nuc::U),
+            (2, Nuc::U, Nuc::T),
+            (-3, Nuc::A, Nuc::G),
+            (-3, Nuc::C, Nuc::T),
+            (-3, Nuc::U, Nuc::C),
+            // ambiguity codes: (2 - 3) / 2 and (2 - 3 * 3) / 4
+            (-1, Nuc::A, Nuc::R),
+            (-2, Nuc::N, Nuc::T),
+            (2, Nuc::U, Nuc::T),
+        ];
+        for (expected, code_1, code_2) in score_cases {
+            assert_eq!(expected, schema.read_score(code_1, code_2))
+        }
+    }
+
+    #[test]
+    fn check_transition_transversion() {
+        let schema =
+            similarity_builder(NucScoringKind::TransitionTransversion(5, -1, -4));
+        let score_cases = [
+            (5, Nuc::A, Nuc::A),
+            (5, Nuc::T, Nuc::U),
+            // transitions
+            (-1, Nuc::A, Nuc::G),
+            (-1, Nuc::C, Nuc::T),
+            (-1, Nuc::U, Nuc::C),
+            // transversions
+            (-4, Nuc::A, Nuc::C),
+            (-4, Nuc::G, Nuc::T),
+            (-4, Nuc::U, Nuc::A),
+            // ambiguity codes: purine vs purine (5 - 1) / 2, purine vs pyrimidine
+            (2, Nuc::R, Nuc::A),
+            (-4, Nuc::R, Nuc::Y),
+        ];
+        for (expected, code_1, code_2) in score_cases {
+            assert_eq!(expected, schema.read_score(code_1, code_2))
+        }
+    }
+
+    #[test]
+    fn check_some_ednafull() {
+        let schema = similarity_builder(NucScoringKind::Ednafull);
+        let score_cases = [
+            (5, Nuc::A, Nuc::A),
+            (5, Nuc::U, Nuc::T),
+            (-4, Nuc::G, Nuc::C),
+            (1, Nuc::A, Nuc::W),
+            (-1, Nuc::S, Nuc::S),
+            (-3, Nuc::B, Nuc::W),
+            (-2, Nuc::N, Nuc::C),
+            (-1, Nuc::N, Nuc::N),
+        ];
+        for (expected, code_1, code_2) in score_cases {
+            assert_eq!(expected, schema.read_score(code_1, code_2))
+        }
+    }
+
+    #[test]
+    fn schemas_are_symmetric() {
+        for kind in [
+            NucScoringKind::MatchMismatch(1, -1),
+            NucScoringKind::TransitionTransversion(2, -1, -2),
+            NucScoringKind::Ednafull,
+        ] {
+            let schema = similarity_builder(kind);
+            for code_1 in ALL_NUC {
+                for code_2 in ALL_NUC {
+                    assert_eq!(
+                        schema.read_score(code_1, code_2),
+                        schema.read_score(code_2, code_1),
+                        "Failed at {:?}, {:?}",
+                        code_1,
+                        code_2
+                    );
+                }
+            }
+        }
+    }
+}
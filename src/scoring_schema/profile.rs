@@ -0,0 +1,620 @@
+//! Position-specific scoring matrices (PSSM): profiles of a block of aligned sequences to
+//! align new sequences against.
+//!
+//! Each column of the profile scores the residues by their frequency in the block, as
+//! ln(Q_a / p_a) / λ, where p_a is the background frequency of the residue and λ the one of
+//! a substitution matrix, so the scores are in the units of the matrix. The rows of the
+//! block are weighted by position (Henikoff & Henikoff 1994, "Position-based sequence
+//! weights"), so that close sequences do not dominate the frequencies. The observed
+//! frequencies f_a are mixed with pseudocounts from the substitution matrix as in PSI-BLAST
+//! (Altschul et al. 1997, "Gapped BLAST and PSI-BLAST"), Q_a = (α f_a + β g_a) / (α + β),
+//! with g_a = Σ_b f_b q_ab / p_b, q_ab the target frequencies of the matrix, α the mean
+//! number of different residues per column minus one and β the pseudocount weight. A
+//! profile of a single sequence scores as the matrix.
+//!
+//! A gap in the sequence facing a column (a deletion) costs the gap penalty scaled by the
+//! weighted fraction of rows with a residue in that column, so gaps are cheaper where the
+//! block has them. Residues of the sequence facing a gap (an insertion) cost the plain gap
+//! penalty.
+
+use super::aminoacid_data::{
+    map_aminoacid_to_index, standard_index, STANDARD, STANDARD_CODES,
+};
+use super::aminoacid_schema::{similarity_builder, AaScoringKind};
+use super::diagnostics::MatrixDiagnostics;
+use super::gap_penalty::{penalty_builder, PenaltyKind};
+use super::log_odds::AlignedBlock;
+use super::{CostType, ScoringSchema, SimilarityType};
+use crate::bioseq::{Aac, HasSequence};
+use crate::statistics::ROBINSON_FREQUENCIES;
+use crate::utils::AlignmentUnit;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::{error, fmt};
+
+// Ambiguity codes and the standard residues they stand for.
+const AMBIGUOUS: [(Aac, &[Aac]); 4] = [
+    (Aac::B, &[Aac::D, Aac::N]),
+    (Aac::J, &[Aac::I, Aac::L]),
+    (Aac::Z, &[Aac::E, Aac::Q]),
+    (Aac::X, &STANDARD_CODES),
+];
+
+/// Unit of a sequence-to-profile alignment: a residue of the sequence or a column of the
+/// profile. A column behaves as its consensus residue except for the scores and gap costs,
+/// so the identities and the text of an alignment compare the sequence with the consensus.
+#[derive(Clone, Copy)]
+#[cfg_attr(test, derive(Debug))]
+pub struct ProfileUnit {
+    residue: Aac,
+    column: Option<usize>,
+}
+
+impl ProfileUnit {
+    /// The residue, or the consensus residue of a column.
+    pub fn residue(&self) -> Aac {
+        self.residue
+    }
+
+    /// The 0-based column of the profile, or None for a residue of the sequence.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl PartialEq for ProfileUnit {
+    fn eq(&self, other: &Self) -> bool {
+        self.residue == other.residue
+    }
+}
+
+impl Eq for ProfileUnit {}
+
+impl Hash for ProfileUnit {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.residue.hash(state);
+    }
+}
+
+impl AlignmentUnit for ProfileUnit {}
+
+/// A sequence to align against a profile.
+pub(crate) struct ProfileSequence {
+    units: Vec<ProfileUnit>,
+}
+
+impl ProfileSequence {
+    pub(crate) fn new(sequence: &(impl HasSequence<Aac> + ?Sized)) -> Self {
+        let units = sequence
+            .seq()
+            .iter()
+            .map(|residue| ProfileUnit {
+                residue: *residue,
+                column: None,
+            })
+            .collect();
+        Self { units }
+    }
+}
+
+impl HasSequence<ProfileUnit> for ProfileSequence {
+    fn seq(&self) -> &Vec<ProfileUnit> {
+        &self.units
+    }
+}
+
+/// How Profile::new weights the rows and adds the pseudocounts.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ProfileSettings {
+    /// Weights the rows by position, see the module documentation. false weighs every row
+    /// as one sequence.
+    pub sequence_weighting: bool,
+    /// Weight β of the pseudocounts against the observed frequencies. PSI-BLAST uses 10;
+    /// 0 only uses the observed frequencies.
+    pub pseudocount_weight: f64,
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        Self {
+            sequence_weighting: true,
+            pseudocount_weight: 10.0,
+        }
+    }
+}
+
+/// Position-specific scores and gap costs of a block of aligned sequences, one column per
+/// column of the block. Align sequences against it with aligner::profile_align_builder.
+#[derive(Clone)]
+pub struct Profile {
+    // A unit per column, with its consensus residue
+    columns: Vec<ProfileUnit>,
+    // Scores of each column, indexed with map_aminoacid_to_index
+    scores: Vec<[SimilarityType; 25]>,
+    // [open, extend] costs of the gaps facing each column
+    gap_costs: Vec<[CostType; 2]>,
+    open: CostType,
+    extend: CostType,
+}
+
+impl Profile {
+    /// Builds the profile of the block, in the units of the substitution matrix and with
+    /// the gap penalty as the costs of the columns without gaps. The background
+    /// frequencies are statistics::ROBINSON_FREQUENCIES.
+    ///
+    /// Returns ProfileError if the block is empty, the settings or the gap penalty are not
+    /// valid, or the matrix is not a log-odds matrix (see diagnostics::MatrixDiagnostics).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::Aac;
+    /// use pairwasm_alignment::scoring_schema::aminoacid_schema::AaScoringKind;
+    /// use pairwasm_alignment::scoring_schema::gap_penalty::PenaltyKind;
+    /// use pairwasm_alignment::scoring_schema::log_odds::AlignedBlock;
+    /// use pairwasm_alignment::scoring_schema::profile::{Profile, ProfileSettings};
+    ///
+    /// let block = AlignedBlock::new(&["HGEYWAKLV", "HGEFW-KLV", "HAEYWSKIV"]).unwrap();
+    /// let profile = Profile::new(
+    ///     &block,
+    ///     AaScoringKind::Blosum62,
+    ///     PenaltyKind::Affine(11.0, 1.0),
+    ///     ProfileSettings::default(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(9, profile.len());
+    /// assert!(profile.score(4, Aac::W) > profile.score(4, Aac::A));
+    /// assert!(profile.gap_costs(5)[0] < profile.gap_costs(4)[0]);
+    /// ```
+    pub fn new(
+        block: &AlignedBlock,
+        score_kind: AaScoringKind,
+        penalty_kind: PenaltyKind,
+        settings: ProfileSettings,
+    ) -> Result<Self, ProfileError> {
+        let rows = block.rows();
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 {
+            return Err(ProfileError::new(ProfileErrorKind::EmptyBlock, ""));
+        }
+        let beta = settings.pseudocount_weight;
+        if !(beta.is_finite() && beta >= 0.0) {
+            return Err(ProfileError::new(ProfileErrorKind::InvalidSettings, ""));
+        }
+        let penalty = penalty_builder(penalty_kind).map_err(|error| {
+            ProfileError::new(ProfileErrorKind::InvalidGapPenalty, &error.to_string())
+        })?;
+        let similarity = similarity_builder(score_kind);
+        let diagnostics =
+            MatrixDiagnostics::new(similarity.as_ref(), &ROBINSON_FREQUENCIES).map_err(
+                |error| {
+                    ProfileError::new(ProfileErrorKind::NotLogOdds, &error.to_string())
+                },
+            )?;
+        let lambda = diagnostics.lambda();
+
+        let total: f64 = ROBINSON_FREQUENCIES
+            .iter()
+            .map(|(_, frequency)| frequency)
+            .sum();
+        let mut background = [0.0; STANDARD];
+        for (code, frequency) in ROBINSON_FREQUENCIES {
+            if let Some(a) = standard_index(code) {
+                background[a] = frequency / total;
+            }
+        }
+        // q_ab / p_b, the pseudocount of residue a for each observed residue b
+        let mut conditional = [[0.0; STANDARD]; STANDARD];
+        for (code_1, code_2, frequency) in diagnostics.target_frequencies() {
+            if let (Some(a), Some(b)) = (standard_index(*code_1), standard_index(*code_2))
+            {
+                conditional[a][b] = frequency / background[b];
+            }
+        }
+
+        // Weighted counts of the standard residues and of all the residues of each column
+        let weights = sequence_weights(rows, settings.sequence_weighting);
+        let counts: Vec<([f64; STANDARD], f64)> = (0..width)
+            .map(|column| {
+                let mut frequencies = [0.0; STANDARD];
+                let mut occupancy = 0.0;
+                for (row, weight) in rows.iter().zip(&weights) {
+                    if let Some(residue) = row[column] {
+                        occupancy += weight;
+                        if let Some(a) = standard_index(residue) {
+                            frequencies[a] += weight;
+                        }
+                    }
+                }
+                (frequencies, occupancy)
+            })
+            .collect();
+        let different: usize = counts
+            .iter()
+            .map(|(frequencies, _)| frequencies.iter().filter(|f| **f > 0.0).count())
+            .sum();
+        let alpha = (different as f64 / width as f64 - 1.0).max(0.0);
+
+        let mut profile = Self {
+            columns: Vec::with_capacity(width),
+            scores: Vec::with_capacity(width),
+            gap_costs: Vec::with_capacity(width),
+            open: penalty.open(),
+            extend: penalty.extend(),
+        };
+        for (column, (frequencies, occupancy)) in counts.into_iter().enumerate() {
+            let residues: f64 = frequencies.iter().sum();
+            // A column without standard residues keeps the background frequencies.
+            let mut target = background;
+            if residues > 0.0 {
+                let observed = frequencies.map(|frequency| frequency / residues);
+                for (a, target) in target.iter_mut().enumerate() {
+                    let pseudocount: f64 =
+                        (0..STANDARD).map(|b| observed[b] * conditional[a][b]).sum();
+                    *target = match alpha + beta > 0.0 {
+                        true => {
+                            (alpha * observed[a] + beta * pseudocount) / (alpha + beta)
+                        }
+                        false => observed[a],
+                    };
+                }
+            }
+            let score = |codes: &[Aac]| -> SimilarityType {
+                let indices = codes.iter().filter_map(|code| standard_index(*code));
+                let (q, p) = indices
+                    .fold((0.0, 0.0), |(q, p), a| (q + target[a], p + background[a]));
+                ((q / p).ln() / lambda)
+                    .round()
+                    .clamp(SimilarityType::MIN as f64, SimilarityType::MAX as f64)
+                    as SimilarityType
+            };
+
+            let mut scores = [0; 25];
+            for code in STANDARD_CODES {
+                scores[map_aminoacid_to_index(code)] = score(&[code]);
+            }
+            for (code, codes) in AMBIGUOUS {
+                scores[map_aminoacid_to_index(code)] = score(codes);
+            }
+            // The stop scores the lowest score of the column.
+            let lowest = scores.iter().copied().min().unwrap_or_default();
+            scores[map_aminoacid_to_index(Aac::Stop)] = lowest;
+
+            let consensus = match residues > 0.0 {
+                true => {
+                    let mut best = 0;
+                    for a in 1..STANDARD {
+                        if frequencies[a] > frequencies[best] {
+                            best = a;
+                        }
+                    }
+                    STANDARD_CODES[best]
+                }
+                false => Aac::X,
+            };
+            profile.columns.push(ProfileUnit {
+                residue: consensus,
+                column: Some(column),
+            });
+            profile.scores.push(scores);
+            profile.gap_costs.push([
+                profile.open * occupancy as CostType,
+                profile.extend * occupancy as CostType,
+            ]);
+        }
+        Ok(profile)
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Score of the residue at the 0-based column. Panics if the column is out of range.
+    pub fn score(&self, column: usize, code: Aac) -> SimilarityType {
+        self.scores[column][map_aminoacid_to_index(code)]
+    }
+
+    /// Costs [open, extend] of the gaps facing the 0-based column: a gap in front of the
+    /// columns a..=b costs open(a) + extend(a) + ... + extend(b). Panics if the column is
+    /// out of range.
+    pub fn gap_costs(&self, column: usize) -> [CostType; 2] {
+        self.gap_costs[column]
+    }
+
+    /// The residue with the highest weighted frequency of each column, or X if a column
+    /// has no standard residues.
+    pub fn consensus(&self) -> Vec<Aac> {
+        self.columns.iter().map(|column| column.residue).collect()
+    }
+}
+
+impl HasSequence<ProfileUnit> for Profile {
+    fn seq(&self) -> &Vec<ProfileUnit> {
+        &self.columns
+    }
+}
+
+impl ScoringSchema<ProfileUnit> for Profile {
+    // Pairs of two residues or two columns do not occur when aligning a sequence against
+    // the profile; they score 0.
+    fn get_score(&self, code_1: ProfileUnit, code_2: ProfileUnit) -> SimilarityType {
+        match (code_1.column, code_2.column) {
+            (Some(column), None) => self.score(column, code_2.residue),
+            (None, Some(column)) => self.score(column, code_1.residue),
+            _ => 0,
+        }
+    }
+
+    fn get_function(&self, length: usize) -> CostType {
+        self.open + self.extend * length as CostType
+    }
+
+    fn get_open(&self) -> CostType {
+        self.open
+    }
+
+    fn get_extend(&self) -> CostType {
+        self.extend
+    }
+
+    fn get_gap_costs(&self, code: ProfileUnit) -> [CostType; 2] {
+        code.column
+            .map_or([self.open, self.extend], |column| self.gap_costs[column])
+    }
+
+    fn has_position_specific_gaps(&self) -> bool {
+        true
+    }
+}
+
+// Position-based weights of the rows, adding up to 1. Each column shares a unit of weight
+// among its different symbols (residues or gap), and each symbol among its rows. Every row
+// weighs the same when `weighting` is false.
+fn sequence_weights(rows: &[Vec<Option<Aac>>], weighting: bool) -> Vec<f64> {
+    let mut weights = vec![1.0; rows.len()];
+    if weighting {
+        weights.fill(0.0);
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut counts: HashMap<Option<Aac>, usize> = HashMap::new();
+        for column in 0..width {
+            counts.clear();
+            for row in rows {
+                *counts.entry(row[column]).or_default() += 1;
+            }
+            for (weight, row) in weights.iter_mut().zip(rows) {
+                *weight += 1.0 / (counts.len() * counts[&row[column]]) as f64;
+            }
+        }
+    }
+    let total: f64 = weights.iter().sum();
+    weights.into_iter().map(|weight| weight / total).collect()
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of ProfileError.
+pub enum ProfileErrorKind {
+    EmptyBlock,
+    InvalidGapPenalty,
+    InvalidSettings,
+    NotLogOdds,
+}
+
+#[derive(Debug)]
+/// Error type for the construction of profiles.
+pub struct ProfileError {
+    kind: ProfileErrorKind,
+    message: String,
+}
+
+impl ProfileError {
+    fn new(kind: ProfileErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            ProfileErrorKind::EmptyBlock => {
+                "The block must have at least one row and one column.".to_string()
+            }
+            ProfileErrorKind::InvalidGapPenalty => {
+                "The gap penalty is not valid.".to_string()
+            }
+            ProfileErrorKind::InvalidSettings => {
+                "The pseudocount weight must not be negative.".to_string()
+            }
+            ProfileErrorKind::NotLogOdds => {
+                "The substitution matrix must be a log-odds matrix.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &ProfileErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for ProfileError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aligner::utils::AlignmentResult;
+    use crate::aligner::{profile_align_builder, AlignerKind};
+    use crate::bioseq::Protein;
+
+    const BLOCK: [&str; 4] = ["HGEYWAKLV", "HGEFW-KLV", "HAEYWAKIV", "HGDYW-RLV"];
+
+    fn profile(rows: &[&str], settings: ProfileSettings) -> Profile {
+        Profile::new(
+            &AlignedBlock::new(rows).unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            settings,
+        )
+        .unwrap()
+    }
+
+    // Score of the alignment with the position-specific scores and gap costs.
+    fn rescore(profile: &Profile, alignment: &AlignmentResult<ProfileUnit>) -> f32 {
+        let mut score = 0.0;
+        let mut previous = [true, true];
+        for pair in alignment.read() {
+            match pair {
+                [Some(column), Some(residue)] => {
+                    score += profile.get_score(*column, *residue) as f32
+                }
+                [Some(unit), None] | [None, Some(unit)] => {
+                    let [open, extend] = profile.get_gap_costs(*unit);
+                    let current = [pair[0].is_some(), pair[1].is_some()];
+                    score -= if current == previous {
+                        extend
+                    } else {
+                        open + extend
+                    };
+                }
+                [None, None] => unreachable!(),
+            }
+            previous = [pair[0].is_some(), pair[1].is_some()];
+        }
+        score
+    }
+
+    #[test]
+    fn single_sequence_profile_is_the_matrix() {
+        let sequence = "HGEYWAKLVCDMNPQRST";
+        let single = profile(&[sequence], ProfileSettings::default());
+        let blosum62 = similarity_builder(AaScoringKind::Blosum62);
+        for (column, row) in sequence.chars().enumerate() {
+            let row = Aac::from_char(row).unwrap();
+            for code in STANDARD_CODES {
+                assert_eq!(blosum62.read_score(row, code), single.score(column, code));
+            }
+            assert_eq!([11.0, 1.0], single.gap_costs(column));
+        }
+    }
+
+    #[test]
+    fn weighting_balances_redundant_rows() {
+        let rows = ["AAAA", "AAAA", "AAAA", "CCCC"];
+        let settings = ProfileSettings {
+            sequence_weighting: true,
+            pseudocount_weight: 0.0,
+        };
+        let weighted = profile(&rows, settings);
+        assert_eq!(
+            weighted.score(0, Aac::A) - weighted.score(0, Aac::C),
+            profile(&["AAAA", "CCCC"], settings).score(0, Aac::A)
+                - profile(&["AAAA", "CCCC"], settings).score(0, Aac::C)
+        );
+        let unweighted = profile(
+            &rows,
+            ProfileSettings {
+                sequence_weighting: false,
+                ..settings
+            },
+        );
+        assert!(
+            unweighted.score(0, Aac::A) - unweighted.score(0, Aac::C)
+                > weighted.score(0, Aac::A) - weighted.score(0, Aac::C)
+        );
+    }
+
+    #[test]
+    fn columns_of_the_block() {
+        let profile = profile(&BLOCK, ProfileSettings::default());
+        let consensus: Vec<Aac> = "HGEYWAKLV"
+            .chars()
+            .map(|c| Aac::from_char(c).unwrap())
+            .collect();
+        assert_eq!(consensus, profile.consensus());
+        // The conserved W, with pseudocounts for the similar residues.
+        assert!(profile.score(4, Aac::W) > profile.score(4, Aac::Y));
+        assert!(profile.score(4, Aac::Y) > profile.score(4, Aac::G));
+        assert!(profile.score(3, Aac::F) > 0);
+        // Half the rows have a gap at the sixth column.
+        let [open, extend] = profile.gap_costs(5);
+        assert!(open < 11.0 && open > 0.0 && extend < 1.0, "{open} {extend}");
+        assert_eq!([11.0, 1.0], profile.gap_costs(0));
+
+        let block = AlignedBlock::new(&[]).unwrap();
+        let empty = Profile::new(
+            &block,
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            ProfileSettings::default(),
+        );
+        assert!(empty.is_err_and(|e| e.kind() == &ProfileErrorKind::EmptyBlock));
+        let invalid = Profile::new(
+            &AlignedBlock::new(&BLOCK).unwrap(),
+            AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+            ProfileSettings {
+                pseudocount_weight: -1.0,
+                ..Default::default()
+            },
+        );
+        assert!(invalid.is_err_and(|e| e.kind() == &ProfileErrorKind::InvalidSettings));
+    }
+
+    #[test]
+    fn global_profile_alignment() {
+        let profile = profile(&BLOCK, ProfileSettings::default());
+        for sequence in ["HGEYWKLV", "HAEFWSKIV", "MKTHGEYWKLVAAPQSR"] {
+            let mut aligner = profile_align_builder(
+                AlignerKind::NeedlemanWunsch,
+                &profile,
+                Protein::new(sequence).unwrap(),
+            );
+            let alignment = aligner.run().remove(0);
+            assert_eq!(
+                rescore(&profile, &alignment),
+                alignment.score(),
+                "{sequence}"
+            );
+            assert_eq!(aligner.score().score(), alignment.score(), "{sequence}");
+        }
+
+        // The missing residue goes to the gappy column.
+        let alignment = profile_align_builder(
+            AlignerKind::NeedlemanWunsch,
+            &profile,
+            Protein::new("HGEYWKLV").unwrap(),
+        )
+        .run()
+        .remove(0);
+        assert_eq!("5M1D3M", alignment.alignment().cigar());
+        assert_eq!(8, alignment.identities());
+    }
+
+    #[test]
+    fn local_profile_alignment() {
+        let profile = profile(&BLOCK, ProfileSettings::default());
+        let mut aligner = profile_align_builder(
+            AlignerKind::SmithWaterman,
+            &profile,
+            Protein::new("MKTAPPHGEFWSKLVDDRPQ").unwrap(),
+        );
+        let alignment = aligner.run().remove(0);
+        assert_eq!([1, 7], alignment.begin());
+        assert_eq!([9, 15], alignment.end());
+        assert_eq!(rescore(&profile, &alignment), alignment.score());
+        assert_eq!(aligner.score().score(), alignment.score());
+        assert_eq!(
+            ["HGEYWAKLV".to_string(), "HGEFWSKLV".to_string()],
+            alignment.alignment().aligned_strings()
+        );
+    }
+}
@@ -0,0 +1,550 @@
+//! Amino acid substitution matrices read at runtime from the NCBI/EMBOSS text format.
+
+use super::aminoacid_data::{map_aminoacid_to_index, STANDARD, TABLE_CODES};
+use super::{Similarity, SimilarityType};
+use crate::bioseq::Aac;
+use std::{error, fmt};
+
+// Rows of the table of the ambiguity and stop codes.
+const B: usize = map_aminoacid_to_index(Aac::B);
+const J: usize = map_aminoacid_to_index(Aac::J);
+const Z: usize = map_aminoacid_to_index(Aac::Z);
+const X: usize = map_aminoacid_to_index(Aac::X);
+const STOP: usize = map_aminoacid_to_index(Aac::Stop);
+
+/// Substitution matrix with the rows and columns of the built-in ones. Read it with
+/// from_ncbi_text and use it through AaScoringKind::Custom.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SubstitutionMatrix {
+    table: Box<[[SimilarityType; 25]; 25]>,
+}
+
+impl SubstitutionMatrix {
+    /// Reads a matrix in the text format of the NCBI and EMBOSS matrix files:
+    ///
+    /// ```text
+    /// # Lines starting with '#' are comments
+    ///    A  R  N ...
+    /// A  4 -1 -2 ...
+    /// R -1  5  0 ...
+    /// ```
+    ///
+    /// The header lists the column labels, one character each; every column needs a row
+    /// starting with the same label, in any order. The 20 standard amino acids are
+    /// required and the matrix must be symmetric. The optional B, Z, J and X rows, when
+    /// missing, take the rounded mean of the scores of the residues they stand for (X
+    /// stands for the 20 standard ones). A missing '*' row scores the lowest score of the
+    /// matrix against any residue and 1 against itself. Selenocysteine (U) and Pyrrolysine
+    /// (O) share the rows of Cysteine and Lysine, as in the built-in matrices.
+    ///
+    /// Returns MatrixError with the 1-based line of the problem, or 0 when it concerns the
+    /// whole matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::bioseq::Aac;
+    /// use pairwasm_alignment::scoring_schema::substitution_matrix::SubstitutionMatrix;
+    /// use pairwasm_alignment::scoring_schema::Similarity;
+    ///
+    /// let mut text = String::from("# Identity matrix\n  A R N D C Q E G H I L K M F P S T W Y V\n");
+    /// let labels = "ARNDCQEGHILKMFPSTWYV";
+    /// for row in labels.chars() {
+    ///     let scores: Vec<&str> = labels
+    ///         .chars()
+    ///         .map(|column| if column == row { "1" } else { "0" })
+    ///         .collect();
+    ///     text.push_str(&format!("{} {}\n", row, scores.join(" ")));
+    /// }
+    /// let matrix = SubstitutionMatrix::from_ncbi_text(&text).unwrap();
+    /// assert_eq!(1, matrix.read_score(Aac::W, Aac::W));
+    /// assert_eq!(0, matrix.read_score(Aac::W, Aac::Y));
+    /// assert_eq!(1, matrix.read_score(Aac::Stop, Aac::Stop));
+    /// ```
+    pub fn from_ncbi_text(text: &str) -> Result<Self, MatrixError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let Some((header_line, header)) = lines.next() else {
+            return Err(MatrixError::new(MatrixErrorKind::MissingHeader, 0, ""));
+        };
+        let mut columns: Vec<usize> = Vec::new();
+        for label in header.split_whitespace() {
+            let index = read_label(label, header_line)?;
+            if columns.contains(&index) {
+                return Err(MatrixError::new(
+                    MatrixErrorKind::InvalidLabel,
+                    header_line,
+                    &format!("The column '{label}' is repeated."),
+                ));
+            }
+            columns.push(index);
+        }
+
+        let mut table = Box::new([[0; 25]; 25]);
+        let mut given = [false; 25];
+        for (line_number, line) in lines {
+            let mut fields = line.split_whitespace();
+            let label = fields.next().unwrap_or_default();
+            let row = read_label(label, line_number)?;
+            if !columns.contains(&row) {
+                return Err(MatrixError::new(
+                    MatrixErrorKind::InvalidLabel,
+                    line_number,
+                    &format!("The row '{label}' is not in the header."),
+                ));
+            }
+            if given[row] {
+                return Err(MatrixError::new(
+                    MatrixErrorKind::InvalidLabel,
+                    line_number,
+                    &format!("The row '{label}' is repeated."),
+                ));
+            }
+            let scores: Vec<&str> = fields.collect();
+            if scores.len() != columns.len() {
+                return Err(MatrixError::new(
+                    MatrixErrorKind::RowLength,
+                    line_number,
+                    &format!(
+                        "The row '{label}' has {} scores, the header {} columns.",
+                        scores.len(),
+                        columns.len()
+                    ),
+                ));
+            }
+            for (column, score) in columns.iter().zip(scores) {
+                table[row][*column] = score.parse().map_err(|_| {
+                    MatrixError::new(
+                        MatrixErrorKind::InvalidScore,
+                        line_number,
+                        &format!("'{score}' is not an integer in [-128, 127]."),
+                    )
+                })?;
+            }
+            given[row] = true;
+        }
+
+        let mut missing: Vec<char> = columns
+            .iter()
+            .copied()
+            .chain(0..STANDARD)
+            .filter(|index| !given[*index])
+            .map(|index| char::from(&TABLE_CODES[index]))
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        if !missing.is_empty() {
+            return Err(MatrixError::new(
+                MatrixErrorKind::Incomplete,
+                0,
+                &format!("Missing rows: {}.", String::from_iter(missing)),
+            ));
+        }
+        for &row in &columns {
+            for &column in &columns {
+                if table[row][column] != table[column][row] {
+                    return Err(MatrixError::new(
+                        MatrixErrorKind::NotSymmetric,
+                        0,
+                        &format!(
+                            "The score of {0} and {1} differs from the one of {1} and {0}.",
+                            char::from(&TABLE_CODES[row]),
+                            char::from(&TABLE_CODES[column])
+                        ),
+                    ));
+                }
+            }
+        }
+
+        fill_missing(&mut table, &given);
+        Ok(Self { table })
+    }
+
+    /// Matrix from the scores of the 20 standard amino acids, in the order
+    /// A R N D C Q E G H I L K M F P S T W Y V. The other rows are filled as in
+    /// from_ncbi_text.
+    pub(super) fn from_standard(scores: &[[SimilarityType; STANDARD]; STANDARD]) -> Self {
+        let mut table = Box::new([[0; 25]; 25]);
+        for (row, row_scores) in table.iter_mut().zip(scores) {
+            row[..STANDARD].copy_from_slice(row_scores);
+        }
+        let mut given = [false; 25];
+        given[..STANDARD].fill(true);
+        fill_missing(&mut table, &given);
+        Self { table }
+    }
+
+    /// Writes the matrix in the NCBI text format read by from_ncbi_text, with a row and a
+    /// column for each of A R N D C Q E G H I L K M F P S T W Y V B J Z X *. Each line of
+    /// `comments` is written first, after a '#'.
+    pub fn to_ncbi_text(&self, comments: &str) -> String {
+        // Columns as wide as the longest score plus a space, and at least 3 as in NCBI.
+        let width = self
+            .table
+            .iter()
+            .flatten()
+            .map(|score| score.to_string().len() + 1)
+            .max()
+            .unwrap_or_default()
+            .max(3);
+        let mut text = String::new();
+        for comment in comments.lines() {
+            text.push_str(&format!("# {comment}\n"));
+        }
+        text.push(' ');
+        for code in &TABLE_CODES {
+            text.push_str(&format!("{:>width$}", char::from(code)));
+        }
+        text.push('\n');
+        for (code, row) in TABLE_CODES.iter().zip(self.table.iter()) {
+            text.push(char::from(code));
+            for score in row {
+                text.push_str(&format!("{score:>width$}"));
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Similarity<Aac> for SubstitutionMatrix {
+    fn read_score(&self, code_1: Aac, code_2: Aac) -> SimilarityType {
+        self.table[map_aminoacid_to_index(code_1)][map_aminoacid_to_index(code_2)]
+    }
+}
+
+fn read_label(label: &str, line: usize) -> Result<usize, MatrixError> {
+    let mut chars = label.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(code), None) => Aac::from_char(code).ok(),
+        _ => None,
+    };
+    match code {
+        Some(Aac::U) | Some(Aac::O) => Err(MatrixError::new(
+            MatrixErrorKind::InvalidLabel,
+            line,
+            &format!("'{label}' shares the row of C or K, it cannot have its own."),
+        )),
+        Some(code) => Ok(map_aminoacid_to_index(code)),
+        None => Err(MatrixError::new(
+            MatrixErrorKind::InvalidLabel,
+            line,
+            &format!("'{label}' is not an amino acid code."),
+        )),
+    }
+}
+
+// The residues each row stands for, when the row is missing.
+fn expansion(index: usize, given: &[bool; 25]) -> Vec<usize> {
+    match index {
+        _ if given[index] => vec![index],
+        B => vec![
+            map_aminoacid_to_index(Aac::N),
+            map_aminoacid_to_index(Aac::D),
+        ],
+        J => vec![
+            map_aminoacid_to_index(Aac::I),
+            map_aminoacid_to_index(Aac::L),
+        ],
+        Z => vec![
+            map_aminoacid_to_index(Aac::Q),
+            map_aminoacid_to_index(Aac::E),
+        ],
+        X => (0..STANDARD).collect(),
+        _ => vec![index],
+    }
+}
+
+fn fill_missing(table: &mut [[SimilarityType; 25]; 25], given: &[bool; 25]) {
+    for row in STANDARD..STOP {
+        for column in 0..25 {
+            if (given[row] && given[column]) || (column == STOP && !given[STOP]) {
+                continue;
+            }
+            let rows = expansion(row, given);
+            let columns = expansion(column, given);
+            let mut sum = 0.0;
+            for r in &rows {
+                for c in &columns {
+                    sum += table[*r][*c] as f64;
+                }
+            }
+            let mean = (sum / (rows.len() * columns.len()) as f64).round();
+            table[row][column] = mean as SimilarityType;
+            table[column][row] = mean as SimilarityType;
+        }
+    }
+    if !given[STOP] {
+        let lowest = table[..STOP]
+            .iter()
+            .flat_map(|row| row[..STOP].iter())
+            .min()
+            .copied()
+            .unwrap_or_default();
+        for row in table.iter_mut() {
+            row[STOP] = lowest;
+        }
+        table[STOP] = [lowest; 25];
+        table[STOP][STOP] = 1;
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of MatrixError.
+pub enum MatrixErrorKind {
+    Incomplete,
+    InvalidLabel,
+    InvalidScore,
+    MissingHeader,
+    NotSymmetric,
+    RowLength,
+}
+
+#[derive(Debug)]
+/// Error type for substitution matrix parsing. Keeps the 1-based line where the error was
+/// found; the line is 0 when the error concerns the whole matrix.
+pub struct MatrixError {
+    kind: MatrixErrorKind,
+    line: usize,
+    message: String,
+}
+
+impl MatrixError {
+    fn new(kind: MatrixErrorKind, line: usize, detail: &str) -> Self {
+        let mut message: String = match kind {
+            MatrixErrorKind::Incomplete => {
+                "The matrix must have a row for every column and the 20 standard amino acids."
+                    .to_string()
+            }
+            MatrixErrorKind::InvalidLabel => "Invalid row or column label.".to_string(),
+            MatrixErrorKind::InvalidScore => "Invalid score.".to_string(),
+            MatrixErrorKind::MissingHeader => {
+                "The matrix must start with a header of column labels.".to_string()
+            }
+            MatrixErrorKind::NotSymmetric => "The matrix must be symmetric.".to_string(),
+            MatrixErrorKind::RowLength => {
+                "Every row must have a score for each column.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+
+        Self {
+            kind,
+            line,
+            message,
+        }
+    }
+
+    pub fn kind(&self) -> &MatrixErrorKind {
+        &self.kind
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) line {}: {}", self.kind, self.line, self.message)
+    }
+}
+
+impl error::Error for MatrixError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scoring_schema::aminoacid_schema::{similarity_builder, AaScoringKind};
+
+    // NCBI BLOSUM62 file, without the J row.
+    const BLOSUM62_TEXT: &str = "\
+#  Matrix made by matblas from blosum62.iij
+#  * column uses minimum score
+   A  R  N  D  C  Q  E  G  H  I  L  K  M  F  P  S  T  W  Y  V  B  Z  X  *
+A  4 -1 -2 -2  0 -1 -1  0 -2 -1 -1 -1 -1 -2 -1  1  0 -3 -2  0 -2 -1  0 -4
+R -1  5  0 -2 -3  1  0 -2  0 -3 -2  2 -1 -3 -2 -1 -1 -3 -2 -3 -1  0 -1 -4
+N -2  0  6  1 -3  0  0  0  1 -3 -3  0 -2 -3 -2  1  0 -4 -2 -3  3  0 -1 -4
+D -2 -2  1  6 -3  0  2 -1 -1 -3 -4 -1 -3 -3 -1  0 -1 -4 -3 -3  4  1 -1 -4
+C  0 -3 -3 -3  9 -3 -4 -3 -3 -1 -1 -3 -1 -2 -3 -1 -1 -2 -2 -1 -3 -3 -2 -4
+Q -1  1  0  0 -3  5  2 -2  0 -3 -2  1  0 -3 -1  0 -1 -2 -1 -2  0  3 -1 -4
+E -1  0  0  2 -4  2  5 -2  0 -3 -3  1 -2 -3 -1  0 -1 -3 -2 -2  1  4 -1 -4
+G  0 -2  0 -1 -3 -2 -2  6 -2 -4 -4 -2 -3 -3 -2  0 -2 -2 -3 -3 -1 -2 -1 -4
+H -2  0  1 -1 -3  0  0 -2  8 -3 -3 -1 -2 -1 -2 -1 -2 -2  2 -3  0  0 -1 -4
+I -1 -3 -3 -3 -1 -3 -3 -4 -3  4  2 -3  1  0 -3 -2 -1 -3 -1  3 -3 -3 -1 -4
+L -1 -2 -3 -4 -1 -2 -3 -4 -3  2  4 -2  2  0 -3 -2 -1 -2 -1  1 -4 -3 -1 -4
+K -1  2  0 -1 -3  1  1 -2 -1 -3 -2  5 -1 -3 -1  0 -1 -3 -2 -2  0  1 -1 -4
+M -1 -1 -2 -3 -1  0 -2 -3 -2  1  2 -1  5  0 -2 -1 -1 -1 -1  1 -3 -1 -1 -4
+F -2 -3 -3 -3 -2 -3 -3 -3 -1  0  0 -3  0  6 -4 -2 -2  1  3 -1 -3 -3 -1 -4
+P -1 -2 -2 -1 -3 -1 -1 -2 -2 -3 -3 -1 -2 -4  7 -1 -1 -4 -3 -2 -2 -1 -2 -4
+S  1 -1  1  0 -1  0  0  0 -1 -2 -2  0 -1 -2 -1  4  1 -3 -2 -2  0  0  0 -4
+T  0 -1  0 -1 -1 -1 -1 -2 -2 -1 -1 -1 -1 -2 -1  1  5 -2 -2  0 -1 -1  0 -4
+W -3 -3 -4 -4 -2 -2 -3 -2 -2 -3 -2 -3 -1  1 -4 -3 -2 11  2 -3 -4 -3 -2 -4
+Y -2 -2 -2 -3 -2 -1 -2 -3  2 -1 -1 -2 -1  3 -3 -2 -2  2  7 -1 -3 -2 -1 -4
+V  0 -3 -3 -3 -1 -2 -2 -3 -3  3  1 -2  1 -1 -2 -2  0 -3 -1  4 -3 -2 -1 -4
+B -2 -1  3  4 -3  0  1 -1  0 -3 -4  0 -3 -3 -2  0 -1 -4 -3 -3  4  1 -1 -4
+Z -1  0  0  1 -3  3  4 -2  0 -3 -3  1 -1 -3 -1  0 -1 -3 -2 -2  1  4 -1 -4
+X  0 -1 -1 -1 -2 -1 -1 -1 -1 -1 -1 -1 -1 -1 -2  0  0 -2 -1 -1 -1 -1 -1 -4
+* -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4 -4  1
+";
+
+    const ALL_AAC: [Aac; 27] = [
+        Aac::A,
+        Aac::C,
+        Aac::D,
+        Aac::E,
+        Aac::F,
+        Aac::G,
+        Aac::H,
+        Aac::I,
+        Aac::K,
+        Aac::L,
+        Aac::M,
+        Aac::N,
+        Aac::P,
+        Aac::Q,
+        Aac::R,
+        Aac::S,
+        Aac::T,
+        Aac::V,
+        Aac::W,
+        Aac::Y,
+        Aac::B,
+        Aac::Z,
+        Aac::J,
+        Aac::X,
+        Aac::U,
+        Aac::O,
+        Aac::Stop,
+    ];
+
+    // Keeps the rows and columns whose label is in `labels`.
+    fn select(text: &str, labels: &str) -> String {
+        let mut lines = text.lines().filter(|line| !line.starts_with('#'));
+        let header: Vec<&str> = lines.next().unwrap().split_whitespace().collect();
+        let keep: Vec<bool> = header.iter().map(|label| labels.contains(label)).collect();
+        let mut output = String::from("   ");
+        for (label, _) in header.iter().zip(&keep).filter(|(_, keep)| **keep) {
+            output.push_str(&format!("{label:>3}"));
+        }
+        output.push('\n');
+        for line in lines {
+            let mut fields = line.split_whitespace();
+            let label = fields.next().unwrap();
+            if !labels.contains(label) {
+                continue;
+            }
+            output.push_str(label);
+            for (score, _) in fields.zip(&keep).filter(|(_, keep)| **keep) {
+                output.push_str(&format!("{score:>3}"));
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    #[test]
+    fn read_ncbi_blosum62() {
+        let matrix = SubstitutionMatrix::from_ncbi_text(BLOSUM62_TEXT).unwrap();
+        let blosum = similarity_builder(AaScoringKind::Blosum62);
+        // The file has no J row: it is filled with the mean of the I and L rows, while the
+        // built-in matrix has the J row of the newer NCBI file.
+        for code_1 in ALL_AAC.into_iter().filter(|code| *code != Aac::J) {
+            for code_2 in ALL_AAC.into_iter().filter(|code| *code != Aac::J) {
+                assert_eq!(
+                    blosum.read_score(code_1, code_2),
+                    matrix.read_score(code_1, code_2)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fill_optional_rows() {
+        let matrix = SubstitutionMatrix::from_ncbi_text(&select(
+            BLOSUM62_TEXT,
+            "ARNDCQEGHILKMFPSTWYV",
+        ))
+        .unwrap();
+        // Rounded means of the rows they stand for
+        assert_eq!(4, matrix.read_score(Aac::B, Aac::B));
+        assert_eq!(4, matrix.read_score(Aac::Z, Aac::E));
+        assert_eq!(1, matrix.read_score(Aac::B, Aac::Z));
+        assert_eq!(-1, matrix.read_score(Aac::X, Aac::X));
+        assert_eq!(-1, matrix.read_score(Aac::X, Aac::A));
+        // The lowest score
+        assert_eq!(-4, matrix.read_score(Aac::Stop, Aac::W));
+        assert_eq!(1, matrix.read_score(Aac::Stop, Aac::Stop));
+        for code_1 in ALL_AAC {
+            for code_2 in ALL_AAC {
+                assert_eq!(
+                    matrix.read_score(code_1, code_2),
+                    matrix.read_score(code_2, code_1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn write_and_read_ncbi_text() {
+        let matrix = SubstitutionMatrix::from_ncbi_text(BLOSUM62_TEXT).unwrap();
+        let text = matrix.to_ncbi_text("BLOSUM62\nwith J row");
+        assert!(text.starts_with("# BLOSUM62\n# with J row\n"));
+        assert_eq!(matrix, SubstitutionMatrix::from_ncbi_text(&text).unwrap());
+    }
+
+    #[test]
+    fn invalid_matrices() {
+        let standard = "ARNDCQEGHILKMFPSTWYV";
+        let cases = [
+            (
+                "# Only comments\n\n".to_string(),
+                MatrixErrorKind::MissingHeader,
+                0,
+            ),
+            (
+                select(BLOSUM62_TEXT, "ARNDCQEGHILKMFPSTWY"),
+                MatrixErrorKind::Incomplete,
+                0,
+            ),
+            (
+                BLOSUM62_TEXT.replacen("A  4 -1", "A  4 -2", 1),
+                MatrixErrorKind::NotSymmetric,
+                0,
+            ),
+            (
+                BLOSUM62_TEXT.replacen("A  4 -1", "A  4 1.5", 1),
+                MatrixErrorKind::InvalidScore,
+                4,
+            ),
+            (
+                BLOSUM62_TEXT.replacen("R -1  5  0", "R -1  5", 1),
+                MatrixErrorKind::RowLength,
+                5,
+            ),
+            (
+                select(BLOSUM62_TEXT, standard).replacen("A", "U", 1),
+                MatrixErrorKind::InvalidLabel,
+                1,
+            ),
+            (
+                BLOSUM62_TEXT.replacen("R -1", "N -1", 1),
+                MatrixErrorKind::InvalidLabel,
+                6,
+            ),
+        ];
+        for (text, kind, line) in cases {
+            let error = SubstitutionMatrix::from_ncbi_text(&text).unwrap_err();
+            assert_eq!(&kind, error.kind(), "{error}");
+            assert_eq!(line, error.line(), "{error}");
+        }
+    }
+}
@@ -0,0 +1,517 @@
+//! Karlin-Altschul statistics of local alignment scores.
+//!
+//! The score of the best local alignment between two random sequences follows an extreme
+//! value distribution whose parameters, λ and K, depend on the scoring system. They turn a
+//! raw score S into a bit score, S' = (λS - ln K) / ln 2, comparable across scoring
+//! systems, and into an E-value, E = m n 2^-S', the number of alignments with that score
+//! expected by chance in a search space of m * n residue pairs.
+//!
+//! For ungapped alignments, λ and K follow from the substitution matrix and the background
+//! frequencies of the residues (Karlin & Altschul 1990, "Methods for assessing the
+//! statistical significance of molecular sequence features by using general scoring
+//! schemes"). Gapped alignments have no analytic solution, so their parameters were
+//! estimated by simulation; the ones of the NCBI BLAST tables are included here.
+
+use crate::bioseq::Aac;
+use crate::scoring_schema::aminoacid_schema::AaScoringKind;
+use crate::scoring_schema::gap_penalty::PenaltyKind;
+use crate::scoring_schema::Similarity;
+use std::{error, fmt};
+
+/// Background frequencies of the 20 standard amino acids, from Robinson & Robinson (1991)
+/// "Distribution of glutamine and asparagine residues and their near neighbors in sequences
+/// of proteins", as used by NCBI BLAST.
+pub const ROBINSON_FREQUENCIES: [(Aac, f64); 20] = [
+    (Aac::A, 0.07805),
+    (Aac::R, 0.05129),
+    (Aac::N, 0.04487),
+    (Aac::D, 0.05364),
+    (Aac::C, 0.01925),
+    (Aac::Q, 0.04264),
+    (Aac::E, 0.06295),
+    (Aac::G, 0.07377),
+    (Aac::H, 0.02199),
+    (Aac::I, 0.05142),
+    (Aac::L, 0.09019),
+    (Aac::K, 0.05744),
+    (Aac::M, 0.02243),
+    (Aac::F, 0.03856),
+    (Aac::P, 0.05203),
+    (Aac::S, 0.07120),
+    (Aac::T, 0.05841),
+    (Aac::W, 0.01330),
+    (Aac::Y, 0.03216),
+    (Aac::V, 0.06441),
+];
+
+// [open, extend, λ, K, H] of gapped alignments with the affine penalty open + k * extend,
+// from the NCBI BLAST tables (blast_stat.c).
+const BLOSUM45_GAPPED: [[f64; 5]; 13] = [
+    [13.0, 3.0, 0.207, 0.049, 0.14],
+    [12.0, 3.0, 0.199, 0.039, 0.11],
+    [11.0, 3.0, 0.190, 0.031, 0.095],
+    [10.0, 3.0, 0.179, 0.023, 0.075],
+    [16.0, 2.0, 0.210, 0.051, 0.14],
+    [15.0, 2.0, 0.203, 0.041, 0.12],
+    [14.0, 2.0, 0.195, 0.032, 0.10],
+    [13.0, 2.0, 0.185, 0.024, 0.084],
+    [12.0, 2.0, 0.171, 0.016, 0.061],
+    [19.0, 1.0, 0.205, 0.040, 0.11],
+    [18.0, 1.0, 0.198, 0.032, 0.10],
+    [17.0, 1.0, 0.189, 0.024, 0.079],
+    [16.0, 1.0, 0.176, 0.016, 0.063],
+];
+
+const BLOSUM62_GAPPED: [[f64; 5]; 11] = [
+    [11.0, 2.0, 0.297, 0.082, 0.27],
+    [10.0, 2.0, 0.291, 0.075, 0.23],
+    [9.0, 2.0, 0.279, 0.058, 0.19],
+    [8.0, 2.0, 0.264, 0.045, 0.15],
+    [7.0, 2.0, 0.239, 0.027, 0.10],
+    [6.0, 2.0, 0.201, 0.012, 0.061],
+    [13.0, 1.0, 0.292, 0.071, 0.23],
+    [12.0, 1.0, 0.283, 0.059, 0.19],
+    [11.0, 1.0, 0.267, 0.041, 0.14],
+    [10.0, 1.0, 0.243, 0.024, 0.10],
+    [9.0, 1.0, 0.206, 0.010, 0.052],
+];
+
+const BLOSUM50_GAPPED: [[f64; 5]; 15] = [
+    [13.0, 3.0, 0.212, 0.063, 0.19],
+    [12.0, 3.0, 0.206, 0.055, 0.17],
+    [11.0, 3.0, 0.197, 0.042, 0.14],
+    [10.0, 3.0, 0.186, 0.031, 0.11],
+    [9.0, 3.0, 0.172, 0.022, 0.082],
+    [16.0, 2.0, 0.215, 0.066, 0.20],
+    [15.0, 2.0, 0.210, 0.058, 0.17],
+    [14.0, 2.0, 0.202, 0.045, 0.14],
+    [13.0, 2.0, 0.193, 0.035, 0.12],
+    [12.0, 2.0, 0.181, 0.025, 0.095],
+    [19.0, 1.0, 0.212, 0.057, 0.18],
+    [18.0, 1.0, 0.207, 0.050, 0.15],
+    [17.0, 1.0, 0.198, 0.037, 0.12],
+    [16.0, 1.0, 0.186, 0.025, 0.10],
+    [15.0, 1.0, 0.171, 0.015, 0.063],
+];
+
+const BLOSUM80_GAPPED: [[f64; 5]; 9] = [
+    [25.0, 2.0, 0.342, 0.17, 0.66],
+    [13.0, 2.0, 0.336, 0.15, 0.57],
+    [9.0, 2.0, 0.319, 0.11, 0.42],
+    [8.0, 2.0, 0.308, 0.090, 0.35],
+    [7.0, 2.0, 0.293, 0.070, 0.27],
+    [6.0, 2.0, 0.268, 0.045, 0.19],
+    [11.0, 1.0, 0.314, 0.095, 0.35],
+    [10.0, 1.0, 0.299, 0.071, 0.27],
+    [9.0, 1.0, 0.279, 0.048, 0.20],
+];
+
+const BLOSUM90_GAPPED: [[f64; 5]; 7] = [
+    [9.0, 2.0, 0.310, 0.12, 0.46],
+    [8.0, 2.0, 0.300, 0.099, 0.39],
+    [7.0, 2.0, 0.283, 0.072, 0.30],
+    [6.0, 2.0, 0.259, 0.048, 0.22],
+    [11.0, 1.0, 0.302, 0.093, 0.39],
+    [10.0, 1.0, 0.290, 0.075, 0.28],
+    [9.0, 1.0, 0.265, 0.044, 0.20],
+];
+
+const PAM30_GAPPED: [[f64; 5]; 6] = [
+    [7.0, 2.0, 0.305, 0.15, 0.87],
+    [6.0, 2.0, 0.287, 0.11, 0.68],
+    [5.0, 2.0, 0.264, 0.079, 0.45],
+    [10.0, 1.0, 0.309, 0.15, 0.88],
+    [9.0, 1.0, 0.294, 0.11, 0.61],
+    [8.0, 1.0, 0.270, 0.072, 0.40],
+];
+
+const PAM70_GAPPED: [[f64; 5]; 6] = [
+    [8.0, 2.0, 0.301, 0.12, 0.54],
+    [7.0, 2.0, 0.286, 0.093, 0.43],
+    [6.0, 2.0, 0.264, 0.064, 0.29],
+    [11.0, 1.0, 0.305, 0.12, 0.52],
+    [10.0, 1.0, 0.291, 0.091, 0.41],
+    [9.0, 1.0, 0.270, 0.060, 0.28],
+];
+
+const PAM250_GAPPED: [[f64; 5]; 15] = [
+    [15.0, 3.0, 0.205, 0.049, 0.13],
+    [14.0, 3.0, 0.200, 0.043, 0.12],
+    [13.0, 3.0, 0.194, 0.036, 0.10],
+    [12.0, 3.0, 0.186, 0.029, 0.085],
+    [11.0, 3.0, 0.174, 0.020, 0.070],
+    [17.0, 2.0, 0.204, 0.047, 0.12],
+    [16.0, 2.0, 0.198, 0.038, 0.11],
+    [15.0, 2.0, 0.191, 0.031, 0.087],
+    [14.0, 2.0, 0.182, 0.024, 0.073],
+    [13.0, 2.0, 0.171, 0.017, 0.059],
+    [21.0, 1.0, 0.205, 0.045, 0.11],
+    [20.0, 1.0, 0.199, 0.040, 0.10],
+    [19.0, 1.0, 0.192, 0.032, 0.088],
+    [18.0, 1.0, 0.183, 0.025, 0.072],
+    [17.0, 1.0, 0.171, 0.017, 0.057],
+];
+
+// The series of K stops when its terms are smaller than this, or after MAX_ITERATIONS.
+const K_TOLERANCE: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 200;
+
+/// Karlin-Altschul parameters of a scoring system.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct KarlinAltschul {
+    lambda: f64,
+    k: f64,
+    entropy: f64,
+}
+
+impl KarlinAltschul {
+    /// Creates the parameters from known values, for example from a published table.
+    ///
+    /// * `entropy`: the relative entropy H, in nats per aligned pair.
+    pub fn new(lambda: f64, k: f64, entropy: f64) -> Self {
+        Self { lambda, k, entropy }
+    }
+
+    /// Parameters of ungapped alignments with the given substitution matrix, where the
+    /// residues of both sequences follow the background frequencies. The frequencies are
+    /// normalised, so they only need to be proportional to the actual ones.
+    ///
+    /// Returns StatisticsError if the expected score is not negative or there is no
+    /// positive score; then the local alignment scores do not follow the distribution.
+    pub fn ungapped(
+        similarity: &dyn Similarity<Aac>,
+        background: &[(Aac, f64)],
+    ) -> Result<Self, StatisticsError> {
+        let total: f64 = background.iter().map(|(_, frequency)| frequency).sum();
+        let mut pairs: Vec<(i32, f64)> = Vec::with_capacity(background.len().pow(2));
+        for (code_1, frequency_1) in background {
+            for (code_2, frequency_2) in background {
+                let score = similarity.read_score(*code_1, *code_2) as i32;
+                pairs.push((score, frequency_1 * frequency_2 / (total * total)));
+            }
+        }
+        Self::from_score_distribution(&pairs)
+    }
+
+    /// Parameters of ungapped alignments from the probabilities of each integer score of
+    /// an aligned pair. See KarlinAltschul::ungapped.
+    pub fn from_score_distribution(
+        pairs: &[(i32, f64)],
+    ) -> Result<Self, StatisticsError> {
+        let distribution = ScoreDistribution::new(pairs)?;
+        let lambda = distribution.lambda();
+        let entropy = distribution.entropy(lambda);
+        let k = distribution.k(lambda, entropy);
+        Ok(Self { lambda, k, entropy })
+    }
+
+    /// Parameters of gapped alignments with the substitution matrix and the affine gap
+    /// penalty, from the NCBI BLAST tables. None if the combination is not tabulated.
+    pub fn gapped(score_kind: &AaScoringKind, penalty_kind: PenaltyKind) -> Option<Self> {
+        let PenaltyKind::Affine(open, extend) = penalty_kind else {
+            return None;
+        };
+        let table: &[[f64; 5]] = match score_kind {
+            AaScoringKind::Blosum45 => &BLOSUM45_GAPPED,
+            AaScoringKind::Blosum50 => &BLOSUM50_GAPPED,
+            AaScoringKind::Blosum62 => &BLOSUM62_GAPPED,
+            AaScoringKind::Blosum80 => &BLOSUM80_GAPPED,
+            AaScoringKind::Blosum90 => &BLOSUM90_GAPPED,
+            AaScoringKind::Pam30 => &PAM30_GAPPED,
+            AaScoringKind::Pam70 => &PAM70_GAPPED,
+            AaScoringKind::Pam250 => &PAM250_GAPPED,
+            _ => &[],
+        };
+        table
+            .iter()
+            .find(|row| row[0] == open as f64 && row[1] == extend as f64)
+            .map(|row| Self::new(row[2], row[3], row[4]))
+    }
+
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    pub fn k(&self) -> f64 {
+        self.k
+    }
+
+    /// Relative entropy H, in nats per aligned pair.
+    pub fn entropy(&self) -> f64 {
+        self.entropy
+    }
+
+    /// Normalised score, in bits.
+    pub fn bit_score(&self, score: f32) -> f64 {
+        (self.lambda * score as f64 - self.k.ln()) / std::f64::consts::LN_2
+    }
+
+    /// Expected number of alignments scoring at least `score` by chance.
+    ///
+    /// * `search_space`: number of residue pairs that can be aligned, the product of the
+    ///   sequence lengths; or the query length times the database length.
+    pub fn e_value(&self, score: f32, search_space: f64) -> f64 {
+        search_space * (-self.bit_score(score) * std::f64::consts::LN_2).exp()
+    }
+}
+
+// Probabilities of the integer scores low..=high, stored from the lowest one.
+struct ScoreDistribution {
+    low: i32,
+    probabilities: Vec<f64>,
+    // Greatest common divisor of the scores with a non-zero probability
+    divisor: i32,
+}
+
+impl ScoreDistribution {
+    fn new(pairs: &[(i32, f64)]) -> Result<Self, StatisticsError> {
+        let nonzero = || pairs.iter().filter(|(_, probability)| *probability > 0.0);
+        let low = nonzero().map(|(score, _)| *score).min().unwrap_or(0);
+        let high = nonzero().map(|(score, _)| *score).max().unwrap_or(0);
+        let mut probabilities = vec![0.0; (high - low + 1) as usize];
+        let total: f64 = nonzero().map(|(_, probability)| probability).sum();
+        for (score, probability) in nonzero() {
+            probabilities[(score - low) as usize] += probability / total;
+        }
+
+        let expected: f64 = (low..=high)
+            .zip(&probabilities)
+            .map(|(score, probability)| score as f64 * probability)
+            .sum();
+        if high <= 0 {
+            return Err(StatisticsError::new(StatisticsErrorKind::NoPositiveScore));
+        }
+        if expected >= 0.0 {
+            return Err(StatisticsError::new(
+                StatisticsErrorKind::NonNegativeExpectedScore,
+            ));
+        }
+        let divisor = nonzero().fold(0, |acc, (score, _)| gcd(acc, score.abs()));
+        Ok(Self {
+            low,
+            probabilities,
+            divisor,
+        })
+    }
+
+    fn scores(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        (self.low..).zip(self.probabilities.iter().copied())
+    }
+
+    // The unique positive root of Σ p(s) e^(λs) = 1. The sum is convex in λ, it starts
+    // decreasing from 1 at λ = 0 and grows without bound, so bisection always finds it.
+    fn lambda(&self) -> f64 {
+        let moment = |lambda: f64| -> f64 {
+            self.scores()
+                .map(|(score, probability)| probability * (lambda * score as f64).exp())
+                .sum()
+        };
+        let mut upper = 0.5;
+        while moment(upper) < 1.0 {
+            upper *= 2.0;
+        }
+        let mut lower = 0.0;
+        for _ in 0..100 {
+            let middle = (lower + upper) / 2.0;
+            if moment(middle) < 1.0 {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        (lower + upper) / 2.0
+    }
+
+    // H = λ Σ s p(s) e^(λs)
+    fn entropy(&self, lambda: f64) -> f64 {
+        lambda
+            * self
+                .scores()
+                .map(|(score, probability)| {
+                    score as f64 * probability * (lambda * score as f64).exp()
+                })
+                .sum::<f64>()
+    }
+
+    // K = δλ e^(-2σ) / (H (1 - e^(-λδ))), with δ the scores divisor and
+    // σ = Σₖ (1/k) (E[e^(λSₖ); Sₖ < 0] + P(Sₖ ≥ 0)), where Sₖ is the sum of k scores.
+    fn k(&self, lambda: f64, entropy: f64) -> f64 {
+        let mut sum_low = 0;
+        let mut sum_probabilities = vec![1.0];
+        let mut sigma = 0.0;
+        for k in 1..=MAX_ITERATIONS {
+            let mut next =
+                vec![0.0; sum_probabilities.len() + self.probabilities.len() - 1];
+            for (i, previous) in sum_probabilities.iter().enumerate() {
+                for (j, probability) in self.probabilities.iter().enumerate() {
+                    next[i + j] += previous * probability;
+                }
+            }
+            sum_low += self.low;
+            sum_probabilities = next;
+
+            let term: f64 = (sum_low..)
+                .zip(&sum_probabilities)
+                .map(|(score, probability)| match score < 0 {
+                    true => probability * (lambda * score as f64).exp(),
+                    false => *probability,
+                })
+                .sum::<f64>()
+                / k as f64;
+            sigma += term;
+            if term < K_TOLERANCE {
+                break;
+            }
+        }
+        let divisor = self.divisor as f64;
+        divisor * lambda * (-2.0 * sigma).exp()
+            / (entropy * (1.0 - (-lambda * divisor).exp()))
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[derive(Debug)]
+/// Error type for the statistics of alignment scores.
+pub struct StatisticsError {
+    kind: StatisticsErrorKind,
+    message: String,
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of StatisticsError.
+pub enum StatisticsErrorKind {
+    NoPositiveScore,
+    NonNegativeExpectedScore,
+}
+
+impl StatisticsError {
+    fn new(kind: StatisticsErrorKind) -> Self {
+        let message: String = match kind {
+            StatisticsErrorKind::NoPositiveScore => {
+                "The scoring system must have at least one positive score.".to_string()
+            }
+            StatisticsErrorKind::NonNegativeExpectedScore => {
+                "The expected score of a random pair must be negative.".to_string()
+            }
+        };
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &StatisticsErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for StatisticsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for StatisticsError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scoring_schema::aminoacid_schema::similarity_builder;
+
+    #[test]
+    fn ungapped_blosum62() {
+        // NCBI BLAST reports λ = 0.3176, K = 0.134 and H = 0.4012.
+        let similarity = similarity_builder(AaScoringKind::Blosum62);
+        let parameters =
+            KarlinAltschul::ungapped(similarity.as_ref(), &ROBINSON_FREQUENCIES).unwrap();
+        assert!(
+            (parameters.lambda() - 0.3176).abs() < 5e-4,
+            "{parameters:?}"
+        );
+        assert!((parameters.k() - 0.134).abs() < 5e-3, "{parameters:?}");
+        assert!(
+            (parameters.entropy() - 0.4012).abs() < 5e-3,
+            "{parameters:?}"
+        );
+    }
+
+    #[test]
+    fn simple_score_distribution() {
+        // +1 with probability 1/4 and -1 with probability 3/4: e^λ = 3, and the closed
+        // form K = (p₊ - p₋)² / p₋ of the ±1 case.
+        let parameters =
+            KarlinAltschul::from_score_distribution(&[(1, 0.25), (-1, 0.75)]).unwrap();
+        assert!((parameters.lambda() - 3f64.ln()).abs() < 1e-9);
+        assert!((parameters.k() - 1.0 / 3.0).abs() < 1e-6, "{parameters:?}");
+
+        // The scores lattice does not change the normalised score.
+        let scaled =
+            KarlinAltschul::from_score_distribution(&[(2, 0.25), (-2, 0.75)]).unwrap();
+        assert!((scaled.lambda() * 2.0 - parameters.lambda()).abs() < 1e-9);
+        assert!((scaled.k() - parameters.k()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn invalid_score_distributions() {
+        let positive = KarlinAltschul::from_score_distribution(&[(1, 0.5), (-1, 0.5)]);
+        assert!(positive
+            .is_err_and(|e| e.kind == StatisticsErrorKind::NonNegativeExpectedScore));
+        let negative = KarlinAltschul::from_score_distribution(&[(0, 0.5), (-1, 0.5)]);
+        assert!(negative.is_err_and(|e| e.kind == StatisticsErrorKind::NoPositiveScore));
+    }
+
+    #[test]
+    fn gapped_bit_score_and_e_value() {
+        let parameters = KarlinAltschul::gapped(
+            &AaScoringKind::Blosum62,
+            PenaltyKind::Affine(11.0, 1.0),
+        )
+        .unwrap();
+        assert_eq!(KarlinAltschul::new(0.267, 0.041, 0.14), parameters);
+        // λS - ln K = 0.267 * 100 + 3.194 nats
+        assert!((parameters.bit_score(100.0) - 43.13).abs() < 0.01);
+        let e_value = parameters.e_value(100.0, 250.0 * 250.0);
+        assert!((e_value - 6.25e4 * 0.041 * (-26.7f64).exp()).abs() < 1e-12);
+
+        assert!(KarlinAltschul::gapped(
+            &AaScoringKind::Pam160,
+            PenaltyKind::Affine(11.0, 1.0)
+        )
+        .is_none());
+        assert!(KarlinAltschul::gapped(
+            &AaScoringKind::Blosum62,
+            PenaltyKind::Linear(1.0)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn gapped_tables() {
+        // One affine setting of each matrix, as in the NCBI BLAST tables.
+        let expected = [
+            (AaScoringKind::Blosum45, [14.0, 2.0], [0.195, 0.032, 0.10]),
+            (AaScoringKind::Blosum50, [13.0, 2.0], [0.193, 0.035, 0.12]),
+            (AaScoringKind::Blosum62, [11.0, 1.0], [0.267, 0.041, 0.14]),
+            (AaScoringKind::Blosum80, [10.0, 1.0], [0.299, 0.071, 0.27]),
+            (AaScoringKind::Blosum90, [10.0, 1.0], [0.290, 0.075, 0.28]),
+            (AaScoringKind::Pam30, [9.0, 1.0], [0.294, 0.11, 0.61]),
+            (AaScoringKind::Pam70, [10.0, 1.0], [0.291, 0.091, 0.41]),
+            (AaScoringKind::Pam250, [14.0, 2.0], [0.182, 0.024, 0.073]),
+        ];
+        for (score_kind, [open, extend], [lambda, k, entropy]) in expected {
+            let parameters =
+                KarlinAltschul::gapped(&score_kind, PenaltyKind::Affine(open, extend));
+            assert_eq!(Some(KarlinAltschul::new(lambda, k, entropy)), parameters);
+        }
+        assert!(KarlinAltschul::gapped(
+            &AaScoringKind::Pam30,
+            PenaltyKind::Affine(11.0, 1.0)
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,260 @@
+//! Brute-force reference for the alignment algorithms.
+//!
+//! Enumerates every possible alignment of two short sequences, so the optimal score is known
+//! without relying on any dynamic programming recursion.
+
+use crate::aligner::EndGaps;
+use crate::scoring_schema::ScoringSchema;
+use crate::utils::AlignmentUnit;
+
+/// Scores an alignment directly from its definition: the sum of the substitution scores
+/// minus the penalty 𝙛(𝘬) of each maximal gap of length 𝘬 on each sequence.
+pub fn alignment_score<A>(
+    pairs: &[[Option<A>; 2]],
+    scoring_schema: &dyn ScoringSchema<A>,
+) -> f32
+where
+    A: AlignmentUnit,
+{
+    let mut score = 0.0;
+    // length of the gap currently open at [left, top] sequence
+    let mut open_gaps: [usize; 2] = [0, 0];
+    for pair in pairs {
+        for (k, residue) in pair.iter().enumerate() {
+            if residue.is_none() {
+                open_gaps[k] += 1;
+            } else if open_gaps[k] > 0 {
+                score -= scoring_schema.get_function(open_gaps[k]);
+                open_gaps[k] = 0;
+            }
+        }
+        if let [Some(left), Some(top)] = pair {
+            score += scoring_schema.get_score(*left, *top) as f32;
+        }
+    }
+    for length in open_gaps {
+        if length > 0 {
+            score -= scoring_schema.get_function(length);
+        }
+    }
+    score
+}
+
+/// Best score among all the global alignments of both sequences.
+pub fn best_global_score<A>(
+    left: &[A],
+    top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+) -> f32
+where
+    A: AlignmentUnit,
+{
+    let mut best = f32::NEG_INFINITY;
+    let mut pairs: Vec<[Option<A>; 2]> = Vec::with_capacity(left.len() + top.len());
+    enumerate(left, top, &mut pairs, &mut |alignment| {
+        best = best.max(alignment_score(alignment, scoring_schema))
+    });
+    best
+}
+
+/// Best score among all the global alignments of both sequences, without counting the
+/// terminal gaps set as free in end_gaps.
+pub fn best_semi_global_score<A>(
+    left: &[A],
+    top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+    end_gaps: EndGaps,
+) -> f32
+where
+    A: AlignmentUnit,
+{
+    let mut best = f32::NEG_INFINITY;
+    let mut pairs: Vec<[Option<A>; 2]> = Vec::with_capacity(left.len() + top.len());
+    enumerate(left, top, &mut pairs, &mut |alignment| {
+        best = best.max(semi_global_alignment_score(
+            alignment,
+            scoring_schema,
+            end_gaps,
+        ))
+    });
+    best
+}
+
+/// Same as alignment_score, but the terminal gaps set as free in end_gaps are not penalised.
+pub fn semi_global_alignment_score<A>(
+    pairs: &[[Option<A>; 2]],
+    scoring_schema: &dyn ScoringSchema<A>,
+    end_gaps: EndGaps,
+) -> f32
+where
+    A: AlignmentUnit,
+{
+    alignment_score(trim_end_gaps(pairs, end_gaps), scoring_schema)
+}
+
+// Removes the free terminal gaps. Before the first residue of a sequence, all the pairs are
+// gaps at that sequence; the same holds after its last residue.
+fn trim_end_gaps<A>(alignment: &[[Option<A>; 2]], end_gaps: EndGaps) -> &[[Option<A>; 2]]
+where
+    A: AlignmentUnit,
+{
+    let leading = |k: usize| alignment.iter().take_while(|p| p[k].is_none()).count();
+    let trailing = |k: usize| {
+        alignment
+            .iter()
+            .rev()
+            .take_while(|p| p[k].is_none())
+            .count()
+    };
+    let mut start = 0;
+    let mut end = alignment.len();
+    if end_gaps.leading_left {
+        start = start.max(leading(0));
+    }
+    if end_gaps.leading_top {
+        start = start.max(leading(1));
+    }
+    if end_gaps.trailing_left {
+        end = end.min(alignment.len() - trailing(0));
+    }
+    if end_gaps.trailing_top {
+        end = end.min(alignment.len() - trailing(1));
+    }
+    &alignment[start..end.max(start)]
+}
+
+/// Best score among all the global alignments of any pair of substrings. The empty
+/// alignment scores 0.
+pub fn best_local_score<A>(
+    left: &[A],
+    top: &[A],
+    scoring_schema: &dyn ScoringSchema<A>,
+) -> f32
+where
+    A: AlignmentUnit,
+{
+    let mut best: f32 = 0.0;
+    let mut pairs: Vec<[Option<A>; 2]> = Vec::with_capacity(left.len() + top.len());
+    // Every partial alignment starting at [left_start, top_start] is the global alignment
+    // of a pair of substrings.
+    for left_start in 0..left.len() {
+        for top_start in 0..top.len() {
+            enumerate_partial(
+                &left[left_start..],
+                &top[top_start..],
+                &mut pairs,
+                &mut |alignment| {
+                    best = best.max(alignment_score(alignment, scoring_schema))
+                },
+            );
+        }
+    }
+    best
+}
+
+// Recursively visits every global alignment; each step consumes a residue from the left
+// sequence, the top sequence, or both.
+fn enumerate<A, F>(left: &[A], top: &[A], pairs: &mut Vec<[Option<A>; 2]>, visit: &mut F)
+where
+    A: AlignmentUnit,
+    F: FnMut(&[[Option<A>; 2]]),
+{
+    walk(left, top, pairs, &mut |alignment, complete| {
+        if complete {
+            visit(alignment)
+        }
+    });
+}
+
+// Same as enumerate, but also visits every partial alignment.
+fn enumerate_partial<A, F>(
+    left: &[A],
+    top: &[A],
+    pairs: &mut Vec<[Option<A>; 2]>,
+    visit: &mut F,
+) where
+    A: AlignmentUnit,
+    F: FnMut(&[[Option<A>; 2]]),
+{
+    walk(left, top, pairs, &mut |alignment, _| visit(alignment));
+}
+
+fn walk<A, F>(left: &[A], top: &[A], pairs: &mut Vec<[Option<A>; 2]>, visit: &mut F)
+where
+    A: AlignmentUnit,
+    F: FnMut(&[[Option<A>; 2]], bool),
+{
+    let complete = left.is_empty() && top.is_empty();
+    visit(pairs, complete);
+    if complete {
+        return;
+    }
+    if !left.is_empty() && !top.is_empty() {
+        pairs.push([Some(left[0]), Some(top[0])]);
+        walk(&left[1..], &top[1..], pairs, visit);
+        pairs.pop();
+    }
+    if !left.is_empty() {
+        pairs.push([Some(left[0]), None]);
+        walk(&left[1..], top, pairs, visit);
+        pairs.pop();
+    }
+    if !top.is_empty() {
+        pairs.push([None, Some(top[0])]);
+        walk(left, &top[1..], pairs, visit);
+        pairs.pop();
+    }
+}
+
+/// The 20 basic amino acids IUPAC codes.
+pub const AMINOACIDS: &[u8] = b"ACDEFGHIKLMNPQRSTVWY";
+
+/// Minimal xorshift generator, enough to build reproducible random sequences.
+pub struct TestRng(u64);
+
+impl TestRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Random string of length between 1 and max_length, drawn from the alphabet.
+    pub fn sequence(&mut self, alphabet: &[u8], max_length: usize) -> String {
+        let length = 1 + (self.next_u64() as usize) % max_length;
+        (0..length)
+            .map(|_| alphabet[(self.next_u64() as usize) % alphabet.len()] as char)
+            .collect()
+    }
+
+    /// Mutates the sequence with random substitutions, insertions and deletions. Useful to
+    /// get pairs whose best alignments have gaps.
+    pub fn homolog(&mut self, alphabet: &[u8], sequence: &str) -> String {
+        let mut output = String::with_capacity(2 * sequence.len());
+        for residue in sequence.chars() {
+            match self.next_u64() % 8 {
+                0 => continue,
+                1 => {
+                    for _ in 0..1 + self.next_u64() % 3 {
+                        output.push(
+                            alphabet[(self.next_u64() as usize) % alphabet.len()] as char,
+                        )
+                    }
+                    output.push(residue)
+                }
+                2 => output
+                    .push(alphabet[(self.next_u64() as usize) % alphabet.len()] as char),
+                _ => output.push(residue),
+            }
+        }
+        if output.is_empty() {
+            output.push_str(sequence)
+        }
+        output
+    }
+}
@@ -36,6 +36,270 @@ fn protein_alignment() {
     PTDEGVAPTPPLPAGPRPAPMGGPPPIPERTSPAVGSIGRLSSRSSISEVPEEPSSSGPS
     TVGGVRRDSQTSQSSTISSSVSRAGQRPPQTQNSVVEDAEDTMKNLRKTFAGIFGDM";
 
-    let output = do_protein_alignment(string_1, string_2, 10.0, 0.5, 1, 1);
+    let output = do_protein_alignment(string_1, string_2, 10.0, 0.5, 1, 1, None);
     assert!(output.is_ok())
 }
+
+#[wasm_bindgen_test]
+fn fasta_protein_alignment() {
+    let fasta = ">seq1 first\nMNFLRRRLSDSSF\nMANLPNGYMTD\n>seq2 second\nMNFSSFKSSFTSN\n";
+    assert!(do_fasta_protein_alignment(fasta, 10.0, 0.5, 2, 2, None).is_ok());
+    assert!(do_fasta_protein_alignment(">seq1\nMNFL\n", 10.0, 0.5, 2, 2, None).is_err())
+}
+
+#[wasm_bindgen_test]
+fn every_matrix_code_aligns() {
+    for code in 1..=20 {
+        assert!(do_protein_alignment("HGEYW", "HGEW", 10.0, 0.5, code, 2, None).is_ok());
+    }
+    assert!(do_protein_alignment("HGEYW", "HGEW", 10.0, 0.5, 21, 2, None).is_err())
+}
+
+#[wasm_bindgen_test]
+fn builtin_matrix_diagnostics() {
+    let Ok(blosum62) = matrix_diagnostics(JsAaScoringKind::Blosum62) else {
+        panic!()
+    };
+    let Ok(blosum45) = matrix_diagnostics(JsAaScoringKind::Blosum45) else {
+        panic!()
+    };
+    assert!(blosum45.relative_entropy() < blosum62.relative_entropy());
+    assert!(blosum62.expected_score() < 0.0);
+    assert!(blosum62
+        .target_frequency('W', 'W')
+        .is_ok_and(|q| q.is_some()));
+    assert!(blosum62.target_frequency('W', '-').is_err());
+    assert!(blosum62.rescaled(1.0).is_ok());
+    assert!(blosum62.rescaled(-1.0).is_err());
+}
+
+#[wasm_bindgen_test]
+fn multiple_alignment_from_fasta() {
+    let fasta = ">seq1\nHGEYWAKLV\n>seq2\nHGEFWKLV\n>seq3\nHAEYWAKIV\n";
+    let Ok(alignment) = multiple_protein_alignment(
+        fasta,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        11.0,
+        1.0,
+        JsTreeMethod::NeighborJoining,
+    ) else {
+        panic!("The alignment must succeed")
+    };
+    assert_eq!(vec!["seq1", "seq2", "seq3"], alignment.ids());
+    assert_eq!(9, alignment.width());
+    assert_eq!("HGEFW-KLV", alignment.aligned_sequences()[1]);
+    assert!(multiple_protein_alignment(
+        ">seq1\nHGEYW\n",
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        11.0,
+        1.0,
+        JsTreeMethod::Upgma,
+    )
+    .is_err());
+}
+
+#[wasm_bindgen_test]
+fn tree_from_fasta() {
+    let fasta = ">seq1\nHGEYWAKLV\n>seq2\nHGEFWKLV\n>seq3\nMKTAYIAKQR\n";
+    let Ok(newick) = protein_tree(
+        fasta,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        11.0,
+        1.0,
+        JsTreeMethod::Upgma,
+    ) else {
+        panic!("The tree must be built")
+    };
+    assert!(newick.starts_with("((seq1:") && newick.ends_with(");"));
+}
+
+#[wasm_bindgen_test]
+fn semi_global_protein_alignment() {
+    let output =
+        do_protein_alignment("HGEYW", "MKVLAAGHGEYWRTPLLK", 10.0, 0.5, 2, 3, Some(3));
+    assert!(output.is_ok())
+}
+
+#[wasm_bindgen_test]
+fn structured_protein_alignment() {
+    let Ok(alignment) = protein_alignment(
+        "KVGAHAGEYA",
+        "KIGGHGAEYGA",
+        JsAlignerKind::NeedlemanWunsch,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Linear,
+        0.0,
+        4.0,
+        None,
+    ) else {
+        panic!("The alignment must succeed")
+    };
+    assert_eq!("KVGAHAGEY-A", alignment.aligned_sequence_1());
+    assert_eq!("KIGGHGAEYGA", alignment.aligned_sequence_2());
+    assert_eq!("9M1I1M", alignment.cigar());
+    assert_eq!(
+        [1, 10, 1, 11],
+        [
+            alignment.begin_1(),
+            alignment.end_1(),
+            alignment.begin_2(),
+            alignment.end_2()
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn invalid_gap_cost_is_an_error() {
+    let output = do_protein_alignment("HGEYW", "HGEW", 0.5, 0.5, 2, 2, None);
+    assert!(output.is_err())
+}
+
+#[wasm_bindgen_test]
+fn banded_protein_alignment_finds_the_seed() {
+    let domain = "MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ";
+    let protein = format!("{}{}GGSWWPL", "DEKRSTNQ".repeat(5), domain);
+    let Ok(alignment) = banded_protein_alignment(
+        domain,
+        &protein,
+        JsAlignerKind::SmithWaterman,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        10.0,
+        1.0,
+        None,
+        3,
+        None,
+    ) else {
+        panic!("The alignment must succeed")
+    };
+    assert_eq!([1, 41], [alignment.begin_1(), alignment.begin_2()]);
+    assert!(!alignment.touches_band_edge());
+}
+
+#[wasm_bindgen_test]
+fn protein_alignment_score_matches_the_alignment() {
+    let domain = "MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ";
+    let protein = format!("{}{}GGSWWPL", "DEKRSTNQ".repeat(5), domain);
+    let Ok(alignment) = protein_alignment(
+        domain,
+        &protein,
+        JsAlignerKind::SmithWaterman,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        10.0,
+        1.0,
+        None,
+    ) else {
+        panic!("The alignment must succeed")
+    };
+    let Ok(score) = protein_alignment_score(
+        domain,
+        &protein,
+        JsAlignerKind::SmithWaterman,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        10.0,
+        1.0,
+        None,
+    ) else {
+        panic!("The score must succeed")
+    };
+    assert_eq!(alignment.score(), score.score());
+    assert_eq!(Some(33), score.end_1());
+    assert_eq!(Some(73), score.end_2());
+}
+
+#[wasm_bindgen_test]
+fn local_alignment_has_e_value() {
+    let Ok(alignment) = protein_alignment(
+        "MKTAYIAKQRQISFVKSHFSRQ",
+        "GSMKTAYIAKQRQISFVKSHFSRQW",
+        JsAlignerKind::SmithWaterman,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        11.0,
+        1.0,
+        None,
+    ) else {
+        panic!("The alignment must succeed")
+    };
+    assert!(alignment.bit_score().is_some());
+    assert!(alignment.e_value().is_some_and(|e_value| e_value < 1e-5));
+}
+
+#[wasm_bindgen_test]
+fn custom_matrix_protein_alignment() {
+    let labels = "ARNDCQEGHILKMFPSTWYV";
+    let mut text = format!(
+        "# Identity\n{}\n",
+        labels
+            .chars()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    for row in labels.chars() {
+        let scores: Vec<&str> = labels
+            .chars()
+            .map(|column| if column == row { "1" } else { "-1" })
+            .collect();
+        text.push_str(&format!("{} {}\n", row, scores.join(" ")));
+    }
+    let Ok(matrix) = JsSubstitutionMatrix::new(&text) else {
+        panic!("The matrix must be valid")
+    };
+    let Ok(alignment) = protein_alignment_custom_matrix(
+        "HGEYW",
+        "HGEW",
+        JsAlignerKind::NeedlemanWunsch,
+        &matrix,
+        JsPenaltyKind::Linear,
+        0.0,
+        1.0,
+        None,
+    ) else {
+        panic!("The alignment must succeed")
+    };
+    assert_eq!(3.0, alignment.score());
+    assert!(JsSubstitutionMatrix::new("A R\nA 1 0\nR 0 1\n").is_err());
+}
+
+#[wasm_bindgen_test]
+fn database_search() {
+    let library = ">noise\nPPPPPPPP\n>close\nMKHGEFWKLVR\n>exact\nMKTHGEYWAKLVRRA\n";
+    let Ok(result) = protein_database_search(
+        "HGEYWAKLV",
+        library,
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        11.0,
+        1.0,
+        10,
+        false,
+    ) else {
+        panic!("The search must succeed")
+    };
+    assert_eq!(3, result.searched_records());
+    assert_eq!(["exact", "close"].to_vec(), result.ids());
+    let Some(alignment) = result.alignment(0) else {
+        panic!("The best hit must have an alignment")
+    };
+    assert_eq!(54.0, alignment.score());
+    assert!(alignment.e_value().is_some());
+    assert!(result.alignment(2).is_none());
+    assert_eq!(3, result.hit_table().lines().count());
+    assert!(protein_database_search(
+        "HGEYWAKLV",
+        ">broken\nHG1\n",
+        JsAaScoringKind::Blosum62,
+        JsPenaltyKind::Affine,
+        11.0,
+        1.0,
+        10,
+        false,
+    )
+    .is_err());
+}
@@ -0,0 +1,689 @@
+//! Phylogenetic trees built from pairwise distances, and their Newick format.
+//!
+//! UPGMA joins the two closest clusters and places their common ancestor at half their
+//! distance, so the tree is rooted and ultrametric. Neighbor joining (Saitou & Nei 1987)
+//! joins the pair that minimises the total branch length and allows different rates along
+//! the branches; its tree is unrooted, written with a root of three children.
+//!
+//! A Newick text writes a node as its children between parentheses, separated by commas,
+//! followed by its label and ':' with the length of the branch to its parent. The tree
+//! ends with ';'. For example, "((A:0.1,B:0.2):0.05,C:0.3);".
+
+use crate::aligner::distance::DistanceMatrix;
+use crate::matrix::Matrix;
+use std::fmt::Write;
+use std::{error, fmt};
+
+// Characters with a meaning in Newick; labels with them, or with whitespaces, are quoted.
+const NEWICK_PUNCTUATION: &str = "()[]':;,";
+// Deepest nesting of parentheses read from Newick, so the recursive parser and writer do
+// not overflow the stack.
+const MAX_NEWICK_DEPTH: usize = 1000;
+
+/// Method that builds a tree from pairwise distances.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum TreeMethod {
+    /// Joins the two closest clusters, averaging their distances to the others. Assumes
+    /// the sequences evolve at the same rate.
+    Upgma,
+    /// Saitou & Nei (1987) neighbor joining. Allows different rates along the branches.
+    #[default]
+    NeighborJoining,
+}
+
+/// A node of a Tree.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Node {
+    label: Option<String>,
+    branch_length: Option<f64>,
+    children: Vec<usize>,
+}
+
+impl Node {
+    /// The name of a leaf, or the optional label of an internal node.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Length of the branch to the parent. None for the root and for the branches without
+    /// length in a Newick text.
+    pub fn branch_length(&self) -> Option<f64> {
+        self.branch_length
+    }
+
+    /// Indices of the children, see Tree::node.
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// A phylogenetic tree. The nodes are stored in a list, children before their parents,
+/// and the root is the last one.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    /// Builds the tree of the square matrix of distances, with a leaf per row labelled
+    /// with the row label. The leaves are the nodes 0..n, in the order of the rows, and the
+    /// next nodes are created in the order they are joined. Neighbor joining may estimate
+    /// negative branch lengths; they are set to 0.
+    ///
+    /// Returns TreeError if there are no labels, or the matrix is not square with a row per
+    /// label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::matrix::Matrix;
+    /// use pairwasm_alignment::tree::{Tree, TreeMethod};
+    ///
+    /// let mut distances = Matrix::full(0.0, 3, 3);
+    /// for (i, j, distance) in [(0, 1, 0.5), (0, 2, 1.5), (1, 2, 1.5)] {
+    ///     distances[[i, j]] = distance;
+    ///     distances[[j, i]] = distance;
+    /// }
+    /// let tree = Tree::build(&["A", "B", "C"], &distances, TreeMethod::Upgma).unwrap();
+    /// assert_eq!("(C:0.75,(A:0.25,B:0.25):0.5);", tree.to_newick());
+    /// ```
+    pub fn build(
+        labels: &[impl AsRef<str>],
+        distances: &Matrix<f64>,
+        method: TreeMethod,
+    ) -> Result<Self, TreeError> {
+        let size = labels.len();
+        if size == 0 {
+            return Err(TreeError::new(TreeErrorKind::EmptyMatrix, ""));
+        }
+        if distances.dim() != [size, size] {
+            return Err(TreeError::new(
+                TreeErrorKind::InvalidMatrix,
+                &format!(
+                    "It has dimension {:?} for {} labels.",
+                    distances.dim(),
+                    size
+                ),
+            ));
+        }
+
+        let mut nodes: Vec<Node> = labels
+            .iter()
+            .map(|label| Node {
+                label: Some(label.as_ref().to_string()),
+                branch_length: None,
+                children: Vec::new(),
+            })
+            .collect();
+        let capacity = 2 * size - 1;
+        let mut distance = Matrix::full(0.0, capacity, capacity);
+        for i in 0..size {
+            for j in 0..size {
+                distance[[i, j]] = distances[[i, j]];
+            }
+        }
+        // Active nodes, and the number of leaves under and the height of each node
+        let mut active: Vec<usize> = (0..size).collect();
+        let mut leaves: Vec<f64> = vec![1.0; capacity];
+        let mut heights: Vec<f64> = vec![0.0; capacity];
+
+        while active.len() > 1 {
+            let node = nodes.len();
+            let remaining = active.len() as f64;
+            // Neighbor joining ends with a root of three children, at the point where
+            // their branches meet.
+            if method == TreeMethod::NeighborJoining && active.len() == 3 {
+                let [a, b, c] = [active[0], active[1], active[2]];
+                let lengths = [
+                    (distance[[a, b]] + distance[[a, c]] - distance[[b, c]]) / 2.0,
+                    (distance[[a, b]] + distance[[b, c]] - distance[[a, c]]) / 2.0,
+                    (distance[[a, c]] + distance[[b, c]] - distance[[a, b]]) / 2.0,
+                ];
+                for (child, length) in [a, b, c].into_iter().zip(lengths) {
+                    nodes[child].branch_length = Some(length.max(0.0));
+                }
+                nodes.push(Node {
+                    label: None,
+                    branch_length: None,
+                    children: vec![a, b, c],
+                });
+                break;
+            }
+
+            let net_divergence: Vec<f64> = active
+                .iter()
+                .map(|i| active.iter().map(|j| distance[[*i, *j]]).sum())
+                .collect();
+            let criterion = |a: usize, b: usize| match method {
+                TreeMethod::Upgma => distance[[active[a], active[b]]],
+                TreeMethod::NeighborJoining => {
+                    (remaining - 2.0) * distance[[active[a], active[b]]]
+                        - net_divergence[a]
+                        - net_divergence[b]
+                }
+            };
+            let mut closest = [0, 1];
+            for a in 0..active.len() {
+                for b in a + 1..active.len() {
+                    if criterion(a, b) < criterion(closest[0], closest[1]) {
+                        closest = [a, b];
+                    }
+                }
+            }
+
+            let [i, j] = closest.map(|index| active[index]);
+            let lengths = match method {
+                TreeMethod::Upgma => {
+                    heights[node] = distance[[i, j]] / 2.0;
+                    [heights[node] - heights[i], heights[node] - heights[j]]
+                }
+                TreeMethod::NeighborJoining if active.len() == 2 => {
+                    [distance[[i, j]] / 2.0, distance[[i, j]] / 2.0]
+                }
+                TreeMethod::NeighborJoining => {
+                    let [divergence_i, divergence_j] =
+                        closest.map(|index| net_divergence[index]);
+                    let length_i = distance[[i, j]] / 2.0
+                        + (divergence_i - divergence_j) / (2.0 * (remaining - 2.0));
+                    [length_i, distance[[i, j]] - length_i]
+                }
+            };
+            nodes[i].branch_length = Some(lengths[0].max(0.0));
+            nodes[j].branch_length = Some(lengths[1].max(0.0));
+
+            for k in active.iter().copied() {
+                let new_distance = match method {
+                    TreeMethod::Upgma => {
+                        (leaves[i] * distance[[i, k]] + leaves[j] * distance[[j, k]])
+                            / (leaves[i] + leaves[j])
+                    }
+                    TreeMethod::NeighborJoining => {
+                        (distance[[i, k]] + distance[[j, k]] - distance[[i, j]]) / 2.0
+                    }
+                };
+                distance[[node, k]] = new_distance;
+                distance[[k, node]] = new_distance;
+            }
+            distance[[node, node]] = 0.0;
+            leaves[node] = leaves[i] + leaves[j];
+            active.retain(|k| *k != i && *k != j);
+            active.push(node);
+            nodes.push(Node {
+                label: None,
+                branch_length: None,
+                children: vec![i, j],
+            });
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Builds the tree of the distances of all-versus-all alignments, with the sequence
+    /// identifiers as leaf labels. See build.
+    pub fn from_distances(
+        distances: &DistanceMatrix,
+        method: TreeMethod,
+    ) -> Result<Self, TreeError> {
+        Self::build(distances.ids(), distances.distances(), method)
+    }
+
+    /// Reads a tree in the Newick format. Labels may be quoted with single quotes, where
+    /// two quotes stand for one. Comments between brackets and whitespaces between
+    /// tokens are ignored.
+    ///
+    /// Returns TreeError with the position of the first character that does not fit the
+    /// format, if the parentheses nest deeper than 1000 levels or if no leaf has a label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pairwasm_alignment::tree::Tree;
+    ///
+    /// let tree = Tree::from_newick("((A:0.1,'B b':0.2)AB:0.05,C:0.3);").unwrap();
+    /// assert_eq!(vec!["A", "B b", "C"], tree.leaf_labels());
+    /// assert_eq!(Some("AB"), tree.node(2).label());
+    /// assert_eq!("((A:0.1,'B b':0.2)AB:0.05,C:0.3);", tree.to_newick());
+    /// ```
+    pub fn from_newick(text: &str) -> Result<Self, TreeError> {
+        let mut parser = NewickParser {
+            chars: text.chars().collect(),
+            position: 0,
+            nodes: Vec::new(),
+        };
+        parser.subtree(0)?;
+        parser.skip_ignored()?;
+        let labelled_leaf = parser
+            .nodes
+            .iter()
+            .any(|node| node.is_leaf() && node.label.is_some());
+        if !labelled_leaf {
+            return Err(parser.error("The tree has no labelled leaves."));
+        }
+        parser.expect(';')?;
+        parser.skip_ignored()?;
+        if parser.position < parser.chars.len() {
+            return Err(parser.error("Text after the end of the tree."));
+        }
+        Ok(Self {
+            nodes: parser.nodes,
+        })
+    }
+
+    /// Index of the root, the last node.
+    pub fn root(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// The node at the index. Panics if the index is out of range.
+    pub fn node(&self, index: usize) -> &Node {
+        &self.nodes[index]
+    }
+
+    /// Every node, children before their parents.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Labels of the leaves, from left to right as written in Newick. Leaves without a
+    /// label are skipped.
+    pub fn leaf_labels(&self) -> Vec<&str> {
+        let mut labels = Vec::new();
+        let mut pending = vec![self.root()];
+        while let Some(index) = pending.pop() {
+            let node = &self.nodes[index];
+            match node.is_leaf() {
+                true => labels.extend(node.label()),
+                false => pending.extend(node.children.iter().rev()),
+            }
+        }
+        labels
+    }
+
+    /// The tree in the Newick format. The lengths are written in their shortest form that
+    /// reads back as the same number.
+    pub fn to_newick(&self) -> String {
+        let mut text = String::new();
+        self.write_newick(self.root(), &mut text);
+        text.push(';');
+        text
+    }
+
+    fn write_newick(&self, index: usize, text: &mut String) {
+        let node = &self.nodes[index];
+        if !node.is_leaf() {
+            text.push('(');
+            for (position, child) in node.children.iter().enumerate() {
+                if position > 0 {
+                    text.push(',');
+                }
+                self.write_newick(*child, text);
+            }
+            text.push(')');
+        }
+        if let Some(label) = &node.label {
+            let quoted = label
+                .chars()
+                .any(|c| c.is_whitespace() || NEWICK_PUNCTUATION.contains(c));
+            match quoted {
+                true => write!(text, "'{}'", label.replace('\'', "''")).unwrap(),
+                false => text.push_str(label),
+            }
+        }
+        if let Some(length) = node.branch_length {
+            write!(text, ":{}", length).unwrap();
+        }
+    }
+}
+
+// Recursive descent parser of Newick. The nodes are pushed once their children are read,
+// so children come before their parents.
+struct NewickParser {
+    chars: Vec<char>,
+    position: usize,
+    nodes: Vec<Node>,
+}
+
+impl NewickParser {
+    fn error(&self, detail: &str) -> TreeError {
+        TreeError::new(
+            TreeErrorKind::InvalidNewick,
+            &format!("At character {}: {}", self.position + 1, detail),
+        )
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    // Skips whitespaces and comments.
+    fn skip_ignored(&mut self) -> Result<(), TreeError> {
+        while let Some(c) = self.peek() {
+            match c {
+                '[' => {
+                    let start = self.position;
+                    while self.peek().is_some_and(|c| c != ']') {
+                        self.position += 1;
+                    }
+                    if self.peek().is_none() {
+                        self.position = start;
+                        return Err(self.error("Unclosed comment."));
+                    }
+                    self.position += 1;
+                }
+                c if c.is_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TreeError> {
+        match self.peek() == Some(expected) {
+            true => {
+                self.position += 1;
+                Ok(())
+            }
+            false => Err(self.error(&format!("Expected '{}'.", expected))),
+        }
+    }
+
+    // Reads a node and its descendants, and returns its index. The depth is the number of
+    // open parentheses around the node.
+    fn subtree(&mut self, depth: usize) -> Result<usize, TreeError> {
+        self.skip_ignored()?;
+        let mut children = Vec::new();
+        if self.peek() == Some('(') {
+            if depth == MAX_NEWICK_DEPTH {
+                return Err(self.error(&format!(
+                    "Parentheses nested deeper than {} levels.",
+                    MAX_NEWICK_DEPTH
+                )));
+            }
+            self.position += 1;
+            loop {
+                children.push(self.subtree(depth + 1)?);
+                self.skip_ignored()?;
+                match self.peek() {
+                    Some(',') => self.position += 1,
+                    Some(')') => {
+                        self.position += 1;
+                        break;
+                    }
+                    _ => return Err(self.error("Expected ',' or ')'.")),
+                }
+            }
+        }
+        self.skip_ignored()?;
+        let label = self.label()?;
+        self.skip_ignored()?;
+        let mut branch_length = None;
+        if self.peek() == Some(':') {
+            self.position += 1;
+            self.skip_ignored()?;
+            branch_length = Some(self.length()?);
+        }
+        self.nodes.push(Node {
+            label,
+            branch_length,
+            children,
+        });
+        Ok(self.nodes.len() - 1)
+    }
+
+    fn label(&mut self) -> Result<Option<String>, TreeError> {
+        let mut label = String::new();
+        if self.peek() == Some('\'') {
+            let start = self.position;
+            self.position += 1;
+            loop {
+                match self.peek() {
+                    Some('\'') if self.chars.get(self.position + 1) == Some(&'\'') => {
+                        label.push('\'');
+                        self.position += 2;
+                    }
+                    Some('\'') => {
+                        self.position += 1;
+                        return Ok(Some(label));
+                    }
+                    Some(c) => {
+                        label.push(c);
+                        self.position += 1;
+                    }
+                    None => {
+                        self.position = start;
+                        return Err(self.error("Unclosed quoted label."));
+                    }
+                }
+            }
+        }
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || NEWICK_PUNCTUATION.contains(c) {
+                break;
+            }
+            label.push(c);
+            self.position += 1;
+        }
+        Ok((!label.is_empty()).then_some(label))
+    }
+
+    fn length(&mut self) -> Result<f64, TreeError> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+        {
+            self.position += 1;
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        match text.parse::<f64>() {
+            Ok(length) if length.is_finite() => Ok(length),
+            _ => {
+                self.position = start;
+                Err(self.error("Expected a branch length."))
+            }
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+/// A list specifying general error categories of TreeError.
+pub enum TreeErrorKind {
+    EmptyMatrix,
+    InvalidMatrix,
+    InvalidNewick,
+}
+
+#[derive(Debug)]
+/// Error type for building and reading trees.
+pub struct TreeError {
+    kind: TreeErrorKind,
+    message: String,
+}
+
+impl TreeError {
+    fn new(kind: TreeErrorKind, detail: &str) -> Self {
+        let mut message: String = match kind {
+            TreeErrorKind::EmptyMatrix => {
+                "The distance matrix must have at least one sequence.".to_string()
+            }
+            TreeErrorKind::InvalidMatrix => {
+                "The distance matrix must be square, with a row per label.".to_string()
+            }
+            TreeErrorKind::InvalidNewick => {
+                "The text is not a valid Newick tree.".to_string()
+            }
+        };
+        if !detail.is_empty() {
+            message.push(' ');
+            message.push_str(detail);
+        }
+        Self { kind, message }
+    }
+
+    pub fn kind(&self) -> &TreeErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?}) {}", self.kind, self.message)
+    }
+}
+
+impl error::Error for TreeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matrix(values: &[&[f64]]) -> Matrix<f64> {
+        let mut distances = Matrix::full(0.0, values.len(), values.len());
+        for (i, row) in values.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                distances[[i, j]] = *value;
+            }
+        }
+        distances
+    }
+
+    // Tree of two pairs of close sequences, A-B and C-D.
+    fn two_pairs(method: TreeMethod) -> Tree {
+        let distances = matrix(&[
+            &[0.0, 0.1, 0.6, 0.7],
+            &[0.1, 0.0, 0.7, 0.8],
+            &[0.6, 0.7, 0.0, 0.2],
+            &[0.7, 0.8, 0.2, 0.0],
+        ]);
+        Tree::build(&["A", "B", "C", "D"], &distances, method).unwrap()
+    }
+
+    #[test]
+    fn upgma_tree() {
+        let tree = two_pairs(TreeMethod::Upgma);
+        assert_eq!(7, tree.nodes().len());
+        assert_eq!([0, 1], tree.node(4).children());
+        assert_eq!([2, 3], tree.node(5).children());
+        assert_eq!([4, 5], tree.node(6).children());
+        // The leaves are at the same distance from the root, half of the mean distance
+        // between both pairs.
+        let height = 0.7 / 2.0;
+        assert!((tree.node(0).branch_length().unwrap() - 0.05).abs() < 1e-12);
+        assert!(
+            (tree.node(4).branch_length().unwrap() + 0.05 - height).abs() < 1e-12,
+            "{:?}",
+            tree
+        );
+        assert!(
+            (tree.node(5).branch_length().unwrap() + 0.1 - height).abs() < 1e-12,
+            "{:?}",
+            tree
+        );
+        assert_eq!(None, tree.node(6).branch_length());
+    }
+
+    #[test]
+    fn neighbor_joining_tree() {
+        // The additive distances of a tree recover its branch lengths.
+        let distances = matrix(&[
+            &[0.0, 3.0, 8.0, 9.0],
+            &[3.0, 0.0, 9.0, 10.0],
+            &[8.0, 9.0, 0.0, 9.0],
+            &[9.0, 10.0, 9.0, 0.0],
+        ]);
+        let tree = Tree::build(
+            &["A", "B", "C", "D"],
+            &distances,
+            TreeMethod::NeighborJoining,
+        )
+        .unwrap();
+        assert_eq!("(C:4,D:5,(A:1,B:2):3);", tree.to_newick());
+
+        let tree = two_pairs(TreeMethod::NeighborJoining);
+        let root = tree.node(tree.root());
+        assert_eq!(3, root.children().len());
+        let mut labels = tree.leaf_labels();
+        labels.sort();
+        assert_eq!(vec!["A", "B", "C", "D"], labels);
+        assert!(tree
+            .nodes()
+            .iter()
+            .all(|node| node.branch_length().unwrap_or(0.0) >= 0.0));
+
+        let pair = Tree::build(
+            &["A", "B"],
+            &matrix(&[&[0.0, 0.4], &[0.4, 0.0]]),
+            TreeMethod::NeighborJoining,
+        )
+        .unwrap();
+        assert_eq!("(A:0.2,B:0.2);", pair.to_newick());
+    }
+
+    #[test]
+    fn invalid_matrices() {
+        let empty: [&str; 0] = [];
+        let error = Tree::build(&empty, &Matrix::full(0.0, 0, 0), TreeMethod::Upgma);
+        assert!(error.is_err_and(|e| e.kind() == &TreeErrorKind::EmptyMatrix));
+        let error = Tree::build(&["A", "B"], &Matrix::full(0.0, 3, 3), TreeMethod::Upgma);
+        assert!(error.is_err_and(|e| e.kind() == &TreeErrorKind::InvalidMatrix));
+    }
+
+    #[test]
+    fn newick_round_trip() {
+        for text in [
+            "((A:0.1,B:0.2):0.05,C:0.3);",
+            "(A,B,(C,D)E)F;",
+            "('it''s here':0.0015,'B C':2);",
+            "A;",
+        ] {
+            let tree = Tree::from_newick(text).unwrap();
+            assert_eq!(text, tree.to_newick());
+        }
+        let tree = Tree::from_newick(" ( A : 1 , [comment] B : 2 ) ;\n").unwrap();
+        assert_eq!("(A:1,B:2);", tree.to_newick());
+        let tree = two_pairs(TreeMethod::Upgma);
+        let newick = tree.to_newick();
+        assert_eq!(newick, Tree::from_newick(&newick).unwrap().to_newick());
+    }
+
+    #[test]
+    fn invalid_newick() {
+        for (text, position) in [
+            ("(A,B", 5),
+            ("(A,B);x", 7),
+            ("(A:x,B);", 4),
+            ("('A,B);", 2),
+            ("(A,B)[;", 6),
+            (";", 1),
+            ("();", 3),
+            ("(:1,[comment] ):2;", 18),
+        ] {
+            let error = Tree::from_newick(text).unwrap_err();
+            assert_eq!(&TreeErrorKind::InvalidNewick, error.kind());
+            assert!(
+                error
+                    .to_string()
+                    .contains(&format!("At character {}:", position)),
+                "{text} {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn newick_depth_limit() {
+        let nested =
+            |depth: usize| format!("{}A{};", "(".repeat(depth), ")".repeat(depth));
+        let text = nested(MAX_NEWICK_DEPTH);
+        assert_eq!(text, Tree::from_newick(&text).unwrap().to_newick());
+
+        let error = Tree::from_newick(&nested(MAX_NEWICK_DEPTH + 1)).unwrap_err();
+        assert_eq!(&TreeErrorKind::InvalidNewick, error.kind());
+        assert!(error.to_string().contains("At character 1001:"));
+        let error = Tree::from_newick(&"(".repeat(100_000)).unwrap_err();
+        assert_eq!(&TreeErrorKind::InvalidNewick, error.kind());
+    }
+}